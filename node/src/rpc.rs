@@ -8,7 +8,10 @@
 use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
-use node_runtime::{opaque::Block, AccountId, Balance, Index};
+use node_runtime::{
+	opaque::Block, AccountId, AssetId, Balance, BlockNumber, Index, MaxIpfsCidLength,
+	MaxPollBeneficiaries, MaxPollOptions, PollIndex,
+};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -36,9 +39,21 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_faterium_polls_rpc::FateriumPollsRuntimeApi<
+		Block,
+		AccountId,
+		Balance,
+		AssetId,
+		BlockNumber,
+		PollIndex,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_faterium_polls_rpc::{FateriumPolls, FateriumPollsApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -46,7 +61,8 @@ where
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(FateriumPolls::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
@@ -0,0 +1,70 @@
+//! Runtime API definition for the Faterium Polls pallet.
+//!
+//! Exposes decoded polls, votes and pots so that clients don't have to decode raw storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_faterium_polls::{AccountVotes, Claimable, PollDetails, PollStatus};
+use sp_runtime::{traits::Get, DispatchError};
+
+sp_api::decl_runtime_apis! {
+	/// The API to query Faterium Polls state.
+	///
+	/// Version 2 holds funds of every poll in a pot of its own, instead of one pot of all polls.
+	#[api_version(2)]
+	pub trait FateriumPollsApi<
+		AccountId,
+		Balance,
+		AssetId,
+		BlockNumber,
+		PollIndex,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions,
+	> where
+		AccountId: Codec + Clone + Eq + core::fmt::Debug,
+		Balance: Codec + Clone + Eq + core::fmt::Debug,
+		AssetId: Codec + Clone + Eq + core::fmt::Debug,
+		BlockNumber: Codec + Clone + Eq + core::fmt::Debug,
+		PollIndex: Codec,
+		MaxIpfsCidLength: Get<u32>,
+		MaxPollBeneficiaries: Get<u32>,
+		MaxPollOptions: Get<u32>,
+	{
+		/// Returns details of the poll, if it exists.
+		fn poll_details(
+			poll_id: PollIndex,
+		) -> Option<PollDetails<
+			Balance,
+			AccountId,
+			AssetId,
+			BlockNumber,
+			MaxIpfsCidLength,
+			MaxPollBeneficiaries,
+			MaxPollOptions,
+		>>;
+		/// Returns votes of the account in the poll, if there are any.
+		fn account_votes(
+			who: AccountId,
+			poll_id: PollIndex,
+		) -> Option<AccountVotes<Balance, MaxPollOptions>>;
+		/// Returns the amount of native currency in the pot of all polls.
+		#[changed_in(2)]
+		fn balances_pot() -> Balance;
+		/// Returns the amount of native currency in the pot of the poll.
+		fn balances_pot(poll_id: PollIndex) -> Balance;
+		/// Returns the amount of the given asset in the pot of all polls.
+		#[changed_in(2)]
+		fn asset_pot(asset_id: AssetId) -> Balance;
+		/// Returns the amount of the given asset in the pot of the poll.
+		fn asset_pot(poll_id: PollIndex, asset_id: AssetId) -> Balance;
+		/// Returns status of the poll, if it exists.
+		fn poll_status(poll_id: PollIndex) -> Option<PollStatus<BlockNumber>>;
+		/// Returns what the account can collect from the poll, without collecting it.
+		fn claimable(
+			who: AccountId,
+			poll_id: PollIndex,
+		) -> Result<Claimable<Balance, MaxPollOptions>, DispatchError>;
+	}
+}
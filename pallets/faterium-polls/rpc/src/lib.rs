@@ -0,0 +1,359 @@
+//! RPC interface for the Faterium Polls pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_faterium_polls::{AccountVotes, Claimable, PollDetails, PollStatus, Votes};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Get},
+	DispatchError,
+};
+
+pub use pallet_faterium_polls_rpc_runtime_api::FateriumPollsApi as FateriumPollsRuntimeApi;
+
+/// Faterium Polls RPC methods.
+///
+/// Responses that contain bounded types are generic, so that bounds don't need to be serializable.
+/// Balances of pots, votes and claimable amounts are returned as `NumberOrHex`, so that clients
+/// can read them exactly.
+#[rpc(client, server)]
+pub trait FateriumPollsApi<
+	BlockHash,
+	AccountId,
+	AssetId,
+	BlockNumber,
+	PollIndex,
+	PollDetailsResponse,
+	AccountVotesResponse,
+	ClaimableResponse,
+>
+{
+	/// Returns details of the poll at the given block.
+	#[method(name = "faterium_pollDetails")]
+	fn poll_details(
+		&self,
+		poll_id: PollIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PollDetailsResponse>>;
+
+	/// Returns votes of the account in the poll at the given block.
+	#[method(name = "faterium_accountVotes")]
+	fn account_votes(
+		&self,
+		who: AccountId,
+		poll_id: PollIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountVotesResponse>>;
+
+	/// Returns the amount of native currency in the pot of the poll at the given block.
+	#[method(name = "faterium_balancesPot")]
+	fn balances_pot(&self, poll_id: PollIndex, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	/// Returns the amount of the asset in the pot of the poll at the given block.
+	#[method(name = "faterium_assetPot")]
+	fn asset_pot(
+		&self,
+		poll_id: PollIndex,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	/// Returns status of the poll at the given block.
+	#[method(name = "faterium_pollStatus")]
+	fn poll_status(
+		&self,
+		poll_id: PollIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PollStatus<BlockNumber>>>;
+
+	/// Returns what the account can collect from the poll at the given block.
+	#[method(name = "faterium_claimable")]
+	fn claimable(
+		&self,
+		who: AccountId,
+		poll_id: PollIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<ClaimableResponse>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The runtime returned a dispatch error.
+	DispatchError,
+	/// The runtime API at the block doesn't support the call.
+	UnsupportedVersion,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DispatchError => 2,
+			Error::UnsupportedVersion => 3,
+		}
+	}
+}
+
+/// Provides RPC methods to query Faterium Polls state.
+pub struct FateriumPolls<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> FateriumPolls<C, Block> {
+	/// Creates a new instance of the FateriumPolls RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query Faterium Polls state.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Converts a dispatch error returned by the runtime into an RPC error.
+fn dispatch_error_into_rpc_err(err: DispatchError) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::DispatchError.into(),
+		"Faterium Polls call returned an error.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Ensures the runtime API at the block holds funds of every poll in a pot of its own, which
+/// was added in version 2. All polls shared one pot before.
+fn ensure_pot_per_poll(version: Option<u32>) -> RpcResult<()> {
+	match version {
+		Some(version) if version >= 2 => Ok(()),
+		_ => Err(CallError::Custom(ErrorObject::owned(
+			Error::UnsupportedVersion.into(),
+			"Pots of polls aren't supported by the runtime at this block.",
+			Some(format!("FateriumPollsApi version: {:?}", version)),
+		))
+		.into()),
+	}
+}
+
+/// Converts balances of votes into `NumberOrHex`.
+fn votes_into_rpc<Balance, MaxPollOptions>(
+	votes: Votes<Balance, MaxPollOptions>,
+) -> RpcResult<Votes<NumberOrHex, MaxPollOptions>>
+where
+	Balance: Clone + Eq + std::fmt::Debug + Into<NumberOrHex>,
+	MaxPollOptions: Get<u32>,
+{
+	let balances: Vec<NumberOrHex> = votes.0.into_iter().map(Into::into).collect();
+	balances.try_into().map(Votes).map_err(runtime_error_into_rpc_err)
+}
+
+#[async_trait]
+impl<
+		C,
+		Block,
+		AccountId,
+		Balance,
+		AssetId,
+		BlockNumber,
+		PollIndex,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions,
+	>
+	FateriumPollsApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		PollIndex,
+		PollDetails<
+			Balance,
+			AccountId,
+			AssetId,
+			BlockNumber,
+			MaxIpfsCidLength,
+			MaxPollBeneficiaries,
+			MaxPollOptions,
+		>,
+		AccountVotes<NumberOrHex, MaxPollOptions>,
+		Claimable<NumberOrHex, MaxPollOptions>,
+	> for FateriumPolls<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: FateriumPollsRuntimeApi<
+		Block,
+		AccountId,
+		Balance,
+		AssetId,
+		BlockNumber,
+		PollIndex,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions,
+	>,
+	AccountId:
+		Codec + Clone + Eq + std::fmt::Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec
+		+ Clone
+		+ Eq
+		+ std::fmt::Debug
+		+ Serialize
+		+ Into<NumberOrHex>
+		+ Send
+		+ Sync
+		+ 'static,
+	AssetId:
+		Codec + Clone + Eq + std::fmt::Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Clone + Eq + std::fmt::Debug + Serialize + Send + Sync + 'static,
+	PollIndex: Codec + DeserializeOwned + Send + Sync + 'static,
+	MaxIpfsCidLength: Get<u32> + Send + Sync + 'static,
+	MaxPollBeneficiaries: Get<u32> + Send + Sync + 'static,
+	MaxPollOptions: Get<u32> + Send + Sync + 'static,
+{
+	fn poll_details(
+		&self,
+		poll_id: PollIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<
+		Option<
+			PollDetails<
+				Balance,
+				AccountId,
+				AssetId,
+				BlockNumber,
+				MaxIpfsCidLength,
+				MaxPollBeneficiaries,
+				MaxPollOptions,
+			>,
+		>,
+	> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.poll_details(&at, poll_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn account_votes(
+		&self,
+		who: AccountId,
+		poll_id: PollIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AccountVotes<NumberOrHex, MaxPollOptions>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let account_votes =
+			api.account_votes(&at, who, poll_id).map_err(runtime_error_into_rpc_err)?;
+		match account_votes {
+			Some(account_votes) => Ok(Some(AccountVotes {
+				votes: votes_into_rpc(account_votes.votes)?,
+				collected: account_votes.collected,
+			})),
+			None => Ok(None),
+		}
+	}
+
+	fn balances_pot(
+		&self,
+		poll_id: PollIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let version = api
+			.api_version::<dyn FateriumPollsRuntimeApi<
+				Block,
+				AccountId,
+				Balance,
+				AssetId,
+				BlockNumber,
+				PollIndex,
+				MaxIpfsCidLength,
+				MaxPollBeneficiaries,
+				MaxPollOptions,
+			>>(&at)
+			.map_err(runtime_error_into_rpc_err)?;
+		ensure_pot_per_poll(version)?;
+		api.balances_pot(&at, poll_id)
+			.map(Into::into)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn asset_pot(
+		&self,
+		poll_id: PollIndex,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let version = api
+			.api_version::<dyn FateriumPollsRuntimeApi<
+				Block,
+				AccountId,
+				Balance,
+				AssetId,
+				BlockNumber,
+				PollIndex,
+				MaxIpfsCidLength,
+				MaxPollBeneficiaries,
+				MaxPollOptions,
+			>>(&at)
+			.map_err(runtime_error_into_rpc_err)?;
+		ensure_pot_per_poll(version)?;
+		api.asset_pot(&at, poll_id, asset_id)
+			.map(Into::into)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn poll_status(
+		&self,
+		poll_id: PollIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PollStatus<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.poll_status(&at, poll_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn claimable(
+		&self,
+		who: AccountId,
+		poll_id: PollIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Claimable<NumberOrHex, MaxPollOptions>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let claimable = api
+			.claimable(&at, who, poll_id)
+			.map_err(runtime_error_into_rpc_err)?
+			.map_err(dispatch_error_into_rpc_err)?;
+		Ok(Claimable {
+			beneficiary_interest: claimable.beneficiary_interest.into(),
+			beneficiary_collected: claimable.beneficiary_collected.into(),
+			voter_return: votes_into_rpc(claimable.voter_return)?,
+			voter_reward: claimable.voter_reward.into(),
+			voter_collected: claimable.voter_collected,
+			voter_locked: claimable.voter_locked.into(),
+			creator_refund: claimable.creator_refund.into(),
+			escrow_refund: claimable.escrow_refund.into(),
+		})
+	}
+}
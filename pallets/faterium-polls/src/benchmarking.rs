@@ -4,17 +4,523 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as FateriumPolls;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash, One};
+
+const SEED: u32 = 0;
+
+/// Returns an account with enough native currency to vote.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	let _ = T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Creates a poll with `b` beneficiaries and `o` options, which starts at the current block.
+fn setup_poll<T: Config>(
+	b: u32,
+	o: u32,
+	voting_mode: VotingMode,
+) -> Result<T::PollIndex, &'static str> {
+	setup_poll_with::<T>(b, o, voting_mode, None)
+}
+
+/// Creates a poll like `setup_poll`, which is sealed if the settings are given.
+fn setup_poll_with<T: Config>(
+	b: u32,
+	o: u32,
+	voting_mode: VotingMode,
+	sealed: Option<SealedSettings<T::BlockNumber>>,
+) -> Result<T::PollIndex, &'static str> {
+	let creator = funded_account::<T>("creator", 0);
+	// Keep the pot and beneficiaries alive, so they can receive any amount.
+	let ed = <T::Currency as Currency<T::AccountId>>::minimum_balance();
+	let beneficiaries = (0..b)
+		.map(|i| {
+			let who: T::AccountId = account("beneficiary", i, SEED);
+			let _ = T::Currency::make_free_balance_be(&who, ed);
+			(T::Lookup::unlookup(who), 10_000 / b, None)
+		})
+		.collect();
+	let start = frame_system::Pallet::<T>::block_number();
+	let end = start.saturating_add(T::MinPollDuration::get().max(One::one()));
+	FateriumPolls::<T>::create_poll(
+		RawOrigin::Signed(creator).into(),
+		b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec(),
+		beneficiaries,
+		RewardSettings::None,
+		T::MinPollGoal::get(),
+		GoalMode::TotalCapital,
+		o as u8,
+		true,
+		true,
+		TiePolicy::Refund,
+		voting_mode,
+		sealed,
+		Eligibility::Anyone,
+		PollKind::Plurality,
+		PollCurrency::Native,
+		start,
+		end,
+	)?;
+	let poll_id = PollCount::<T>::get();
+	let _ = T::Currency::make_free_balance_be(&FateriumPolls::<T>::pot_account_id(poll_id), ed);
+	Ok(poll_id)
+}
+
+/// Returns a vesting schedule of the beneficiary interest, which is validated on poll creation.
+fn vesting<T: Config>() -> Option<VestingSchedule<T::BlockNumber>> {
+	Some(VestingSchedule { start_offset: One::one(), duration: One::one(), cliff: One::one() })
+}
+
+/// Returns votes on all `o` options, with the highest stake on the first one.
+fn votes<T: Config>(o: u32) -> VotesOf<T> {
+	let stake: BalanceOf<T> = 10_000u32.into();
+	let votes: Vec<_> = (0..o)
+		.map(|i| if i == 0 { stake.saturating_mul(2u32.into()) } else { stake })
+		.collect();
+	Votes(votes.try_into().expect("options count is within the bound; qed"))
+}
+
+/// Sets the kind of a poll, which is `Plurality` when it's created by `setup_poll`.
+fn set_kind<T: Config>(poll_id: T::PollIndex, kind: PollKind) {
+	PollDetailsOf::<T>::mutate(poll_id, |poll| {
+		if let Some(poll) = poll {
+			poll.kind = kind;
+		}
+	});
+}
+
+/// Creates a finished poll with one beneficiary and a single milestone, where the returned
+/// voter voted for the winning option.
+fn setup_escrow<T: Config>() -> Result<(T::PollIndex, T::AccountId), &'static str> {
+	let poll_id = setup_poll::<T>(1, 2, VotingMode::Transfer)?;
+	let milestones = vec![10_000].try_into().map_err(|_| "too few milestones")?;
+	let escrow: EscrowOf<T> = Escrow::new(milestones);
+	EscrowsOf::<T>::insert(poll_id, escrow);
+	let voter = funded_account::<T>("voter", 0);
+	FateriumPolls::<T>::vote(RawOrigin::Signed(voter.clone()).into(), poll_id, votes::<T>(2))?;
+	FateriumPolls::<T>::enact_poll_end(RawOrigin::Root.into(), poll_id)?;
+	Ok((poll_id, voter))
+}
+
+/// Delegates voting power in native currency of `d` funded accounts to the delegate.
+fn setup_delegators<T: Config>(delegate: &T::AccountId, d: u32) -> Result<(), &'static str> {
+	for i in 0..d {
+		let delegator = funded_account::<T>("delegator", i);
+		FateriumPolls::<T>::delegate(
+			RawOrigin::Signed(delegator).into(),
+			T::Lookup::unlookup(delegate.clone()),
+			PollCurrency::Native,
+			10_000u32.into(),
+		)?;
+	}
+	Ok(())
+}
 
 benchmarks! {
 	create_poll {
-		let s in 0 .. 10;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), Vec::new(), Vec::new(), RewardSettings::None, 100u32.into(), s as u8, true, PollCurrency::Native, 10u32.into(), 20u32.into())
+		let b in 0 .. T::MaxPollBeneficiaries::get();
+		let o in 2 .. T::MaxPollOptions::get();
+		let caller = funded_account::<T>("creator", 0);
+		let beneficiaries: Vec<_> = (0..b)
+			.map(|i| {
+				(T::Lookup::unlookup(account("beneficiary", i, SEED)), 1u32, vesting::<T>())
+			})
+			.collect();
+		// Goals of every option are the longest goal mode to validate.
+		let goals: Vec<_> = (0..o).map(|_| T::MinPollGoal::get()).collect();
+		let goal_mode = GoalMode::PerOption(
+			goals.try_into().expect("options count is within the bound; qed"),
+		);
+		let start = frame_system::Pallet::<T>::block_number();
+		let end = start.saturating_add(T::MinPollDuration::get().max(One::one()));
+	}: _(
+		RawOrigin::Signed(caller),
+		b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec(),
+		beneficiaries,
+		RewardSettings::None,
+		T::MinPollGoal::get(),
+		goal_mode,
+		o as u8,
+		true,
+		true,
+		TiePolicy::Refund,
+		VotingMode::Transfer,
+		None,
+		Eligibility::Anyone,
+		PollKind::Plurality,
+		PollCurrency::Native,
+		start,
+		end
+	)
+	verify {
+		assert_eq!(PollCount::<T>::get(), One::one());
+	}
+
+	update_poll {
+		let b in 0 .. T::MaxPollBeneficiaries::get();
+		let caller = funded_account::<T>("creator", 0);
+		let start = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+		let end = start.saturating_add(T::MinPollDuration::get().max(One::one()));
+		FateriumPolls::<T>::create_poll(
+			RawOrigin::Signed(caller.clone()).into(),
+			b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec(),
+			vec![],
+			RewardSettings::None,
+			T::MinPollGoal::get(),
+			GoalMode::TotalCapital,
+			2,
+			true,
+			true,
+			TiePolicy::Refund,
+			VotingMode::Transfer,
+			None,
+			Eligibility::Anyone,
+			PollKind::Plurality,
+			PollCurrency::Native,
+			start,
+			end,
+		)?;
+		let poll_id = PollCount::<T>::get();
+		let beneficiaries: Vec<_> = (0..b)
+			.map(|i| {
+				(T::Lookup::unlookup(account("beneficiary", i, SEED)), 1u32, vesting::<T>())
+			})
+			.collect();
+		let reward_settings = RewardSettings::CreatorPool(10_000u32.into());
+		let end = start.saturating_add(T::MaxPollDuration::get());
+		// The base16 form is the longest to decode.
+		let ipfs_cid =
+			b"f017012209d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf".to_vec();
+	}: _(
+		RawOrigin::Signed(caller),
+		poll_id,
+		Some(ipfs_cid),
+		Some(beneficiaries),
+		Some(reward_settings),
+		Some(T::MinPollGoal::get().saturating_add(One::one())),
+		Some(end)
+	)
+	verify {
+		let poll = PollDetailsOf::<T>::get(poll_id).unwrap();
+		assert_eq!(poll.beneficiaries.len(), b as usize);
+		assert_eq!(poll.reward_settings, reward_settings);
+	}
+
+	emergency_cancel {
+		let poll_id = setup_poll::<T>(0, 2, VotingMode::Transfer)?;
+		let caller: T::AccountId = account("creator", 0, SEED);
+	}: _(RawOrigin::Signed(caller), poll_id)
+	verify {
+		let poll = PollDetailsOf::<T>::get(poll_id).unwrap();
+		assert!(matches!(poll.status, PollStatus::Cancelled(_)));
+	}
+
+	force_cancel {
+		let poll_id = setup_poll::<T>(0, 2, VotingMode::Transfer)?;
+		let origin = T::ForceOrigin::successful_origin();
+	}: _<T::Origin>(origin, poll_id)
+	verify {
+		let poll = PollDetailsOf::<T>::get(poll_id).unwrap();
+		assert!(matches!(poll.status, PollStatus::Cancelled(_)));
+		assert!(poll.deposit.is_zero());
+	}
+
+	vote {
+		let o in 2 .. T::MaxPollOptions::get();
+		let d in 0 .. T::MaxDelegators::get();
+		// Voter in transfer mode spends voting power of every delegator.
+		let poll_id = setup_poll::<T>(0, o, VotingMode::Transfer)?;
+		let voter = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&voter, d)?;
+		let votes = votes::<T>(o);
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, votes)
+	verify {
+		assert!(VotingOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	remove_vote {
+		let o in 2 .. T::MaxPollOptions::get();
+		let d in 0 .. T::MaxDelegators::get();
+		// Voter in transfer mode pays back every delegator.
+		let poll_id = setup_poll::<T>(0, o, VotingMode::Transfer)?;
+		let voter = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&voter, d)?;
+		FateriumPolls::<T>::vote(RawOrigin::Signed(voter.clone()).into(), poll_id, votes::<T>(o))?;
+	}: _(RawOrigin::Signed(voter.clone()), poll_id)
+	verify {
+		assert!(!VotingOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	withdraw_votes {
+		let o in 2 .. T::MaxPollOptions::get();
+		let d in 0 .. T::MaxDelegators::get();
+		// Voter in transfer mode pays back every delegator.
+		let poll_id = setup_poll::<T>(0, o, VotingMode::Transfer)?;
+		let voter = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&voter, d)?;
+		FateriumPolls::<T>::vote(RawOrigin::Signed(voter.clone()).into(), poll_id, votes::<T>(o))?;
+		// Withdraw from every option, which keeps only a half of the stake on the first one.
+		let stake: BalanceOf<T> = 10_000u32.into();
+		let withdrawn: Vec<_> = (0..o).map(|_| stake).collect();
+		let withdrawn = Votes(withdrawn.try_into().expect("options count is within the bound; qed"));
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, withdrawn)
+	verify {
+		assert_eq!(VotingOf::<T>::get((voter, poll_id)).unwrap().votes.capital(), stake);
+	}
+
+	move_votes {
+		let o in 2 .. T::MaxPollOptions::get();
+		let poll_id = setup_poll::<T>(0, o, VotingMode::Lock)?;
+		let voter = funded_account::<T>("voter", 0);
+		FateriumPolls::<T>::vote(RawOrigin::Signed(voter.clone()).into(), poll_id, votes::<T>(o))?;
+		let stake: BalanceOf<T> = 10_000u32.into();
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, 0, (o - 1) as u8, stake)
+	verify {
+		let votes = VotingOf::<T>::get((voter, poll_id)).unwrap().votes;
+		assert_eq!(votes.0[(o - 1) as usize], stake.saturating_mul(2u32.into()));
+	}
+
+	delegate {
+		let d in 0 .. T::MaxDelegators::get() - 1;
+		let delegate = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&delegate, d)?;
+		let caller = funded_account::<T>("delegator", d);
+		let amount: BalanceOf<T> = 10_000u32.into();
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		T::Lookup::unlookup(delegate.clone()),
+		PollCurrency::Native,
+		amount
+	)
+	verify {
+		let delegators = DelegationsOf::<T>::get((delegate, PollCurrency::Native));
+		assert_eq!(delegators.last(), Some(&(caller, amount)));
+	}
+
+	undelegate {
+		let d in 1 .. T::MaxDelegators::get();
+		let delegate = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&delegate, d)?;
+		let caller: T::AccountId = account("delegator", d - 1, SEED);
+	}: _(
+		RawOrigin::Signed(caller),
+		T::Lookup::unlookup(delegate.clone()),
+		PollCurrency::Native
+	)
+	verify {
+		let delegators = DelegationsOf::<T>::get((delegate, PollCurrency::Native));
+		assert_eq!(delegators.len(), (d - 1) as usize);
+	}
+
+	commit_votes {
+		let sealed = SealedSettings { reveal_period: One::one(), unrevealed_penalty: 1_000 };
+		let poll_id = setup_poll_with::<T>(0, 2, VotingMode::Lock, Some(sealed))?;
+		let voter = funded_account::<T>("voter", 0);
+		let hash = T::Hashing::hash_of(&(votes::<T>(2), [0u8; 32]));
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, hash, votes::<T>(2).capital())
+	verify {
+		assert!(CommitmentsOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	reveal_votes {
+		let o in 2 .. T::MaxPollOptions::get();
+		let sealed = SealedSettings { reveal_period: One::one(), unrevealed_penalty: 1_000 };
+		let poll_id = setup_poll_with::<T>(0, o, VotingMode::Lock, Some(sealed))?;
+		let voter = funded_account::<T>("voter", 0);
+		let votes = votes::<T>(o);
+		let hash = T::Hashing::hash_of(&(&votes, [0u8; 32]));
+		FateriumPolls::<T>::commit_votes(
+			RawOrigin::Signed(voter.clone()).into(),
+			poll_id,
+			hash,
+			votes.capital(),
+		)?;
+		let end = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::MinPollDuration::get().max(One::one()));
+		frame_system::Pallet::<T>::set_block_number(end);
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, votes, [0u8; 32])
+	verify {
+		assert!(VotingOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	refund_commitment {
+		// Penalty in transfer mode is slashed from the pot, which is the worst case.
+		let sealed = SealedSettings { reveal_period: One::one(), unrevealed_penalty: 1_000 };
+		let poll_id = setup_poll_with::<T>(0, 2, VotingMode::Transfer, Some(sealed))?;
+		let voter = funded_account::<T>("voter", 0);
+		let hash = T::Hashing::hash_of(&(votes::<T>(2), [0u8; 32]));
+		FateriumPolls::<T>::commit_votes(
+			RawOrigin::Signed(voter.clone()).into(),
+			poll_id,
+			hash,
+			votes::<T>(2).capital(),
+		)?;
+		FateriumPolls::<T>::enact_poll_end(RawOrigin::Root.into(), poll_id)?;
+	}: _(RawOrigin::Signed(voter.clone()), poll_id)
+	verify {
+		assert!(!CommitmentsOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	update_allowlist {
+		let a in 1 .. 100;
+		let poll_id = setup_poll::<T>(0, 2, VotingMode::Transfer)?;
+		PollDetailsOf::<T>::mutate(poll_id, |poll| {
+			if let Some(poll) = poll {
+				poll.eligibility = Eligibility::Allowlist;
+			}
+		});
+		let accounts: Vec<_> =
+			(0..a).map(|i| T::Lookup::unlookup(account("voter", i, SEED))).collect();
+		let creator: T::AccountId = account("creator", 0, SEED);
+	}: _(RawOrigin::Signed(creator), poll_id, accounts, true)
+	verify {
+		let voter: T::AccountId = account("voter", 0, SEED);
+		assert!(AllowlistOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	prove_eligibility {
+		// Depth of the Merkle tree, which is enough for 2^32 leaves.
+		let p in 0 .. 32;
+		let poll_id = setup_poll::<T>(0, 2, VotingMode::Transfer)?;
+		let voter: T::AccountId = account("voter", 0, SEED);
+		let proof: Vec<_> = (0..p).map(|i| H256::repeat_byte(i as u8)).collect();
+		let root = FateriumPolls::<T>::merkle_root(&voter, &proof);
+		PollDetailsOf::<T>::mutate(poll_id, |poll| {
+			if let Some(poll) = poll {
+				poll.eligibility = Eligibility::MerkleRoot(root);
+			}
+		});
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, proof)
+	verify {
+		assert!(AllowlistOf::<T>::contains_key((voter, poll_id)));
+	}
+
+	set_milestones {
+		let m in 1 .. T::MaxMilestones::get();
+		let poll_id = setup_poll::<T>(1, 2, VotingMode::Transfer)?;
+		// Milestones can be set only before the start of the poll.
+		PollDetailsOf::<T>::mutate(poll_id, |poll| {
+			if let Some(PollDetails { status: PollStatus::Ongoing { start, .. }, .. }) = poll {
+				*start = start.saturating_add(One::one());
+			}
+		});
+		// The first milestone takes the rounding remainder, so shares sum to 10_000.
+		let milestones: Vec<u32> = (0..m)
+			.map(|i| if i == 0 { 10_000 - 10_000 / m * (m - 1) } else { 10_000 / m })
+			.collect();
+		let creator: T::AccountId = account("creator", 0, SEED);
+	}: _(RawOrigin::Signed(creator), poll_id, milestones)
+	verify {
+		assert_eq!(EscrowsOf::<T>::get(poll_id).unwrap().milestones.len(), m as usize);
+	}
+
+	request_sign_off {
+		let (poll_id, _) = setup_escrow::<T>()?;
+		let creator: T::AccountId = account("creator", 0, SEED);
+	}: _(RawOrigin::Signed(creator), poll_id)
+	verify {
+		assert!(EscrowsOf::<T>::get(poll_id).unwrap().sign_off.is_some());
+	}
+
+	sign_off {
+		let (poll_id, voter) = setup_escrow::<T>()?;
+		let creator: T::AccountId = account("creator", 0, SEED);
+		FateriumPolls::<T>::request_sign_off(RawOrigin::Signed(creator).into(), poll_id)?;
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, true)
+	verify {
+		assert_eq!(EscrowParticipationOf::<T>::get((voter, poll_id)).signed_off, 1);
+	}
+
+	close_sign_off {
+		let (poll_id, voter) = setup_escrow::<T>()?;
+		let creator: T::AccountId = account("creator", 0, SEED);
+		FateriumPolls::<T>::request_sign_off(RawOrigin::Signed(creator).into(), poll_id)?;
+		FateriumPolls::<T>::sign_off(RawOrigin::Signed(voter.clone()).into(), poll_id, true)?;
+		let end = frame_system::Pallet::<T>::block_number().saturating_add(T::SignOffPeriod::get());
+		frame_system::Pallet::<T>::set_block_number(end);
+	}: _(RawOrigin::Signed(voter), poll_id)
+	verify {
+		assert_eq!(EscrowsOf::<T>::get(poll_id).unwrap().released, 1);
+	}
+
+	collect {
+		let b in 1 .. T::MaxPollBeneficiaries::get();
+		let o in 2 .. T::MaxPollOptions::get();
+		let d in 0 .. T::MaxDelegators::get();
+		// Voter in lock mode pays every beneficiary on collect, and in transfer mode pays every
+		// delegator, so the mode is picked by which of them there are.
+		let voting_mode = if d == 0 { VotingMode::Lock } else { VotingMode::Transfer };
+		let poll_id = setup_poll::<T>(b, o, voting_mode)?;
+		let voter = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&voter, d)?;
+		FateriumPolls::<T>::vote(RawOrigin::Signed(voter.clone()).into(), poll_id, votes::<T>(o))?;
+		FateriumPolls::<T>::enact_poll_end(RawOrigin::Root.into(), poll_id)?;
+	}: _(RawOrigin::Signed(voter.clone()), poll_id)
+	verify {
+		assert!(VotingOf::<T>::get((voter, poll_id)).unwrap().collected);
+	}
+
+	enact_poll_end {
+		let o in 2 .. T::MaxPollOptions::get();
+		let r in 0 .. T::MaxRankings::get();
+		let poll_id = setup_poll::<T>(0, o, VotingMode::Transfer)?;
+		let voter = funded_account::<T>("voter", 0);
+		FateriumPolls::<T>::vote(RawOrigin::Signed(voter).into(), poll_id, votes::<T>(o))?;
+		// Instant-runoff is computed over every ranking, ranking all options.
+		if r > 0 {
+			set_kind::<T>(poll_id, PollKind::RankedChoice);
+			let stake: BalanceOf<T> = 10_000u32.into();
+			let rankings: Vec<_> = (0..r)
+				.map(|i| {
+					let ranking: Vec<_> = (0..o).map(|j| ((i + j) % o) as u8).collect();
+					let ranking = ranking.try_into().expect("options count is within the bound; qed");
+					(ranking, if i == 0 { stake.saturating_mul(2u32.into()) } else { stake })
+				})
+				.collect();
+			RankingsOf::<T>::insert(
+				poll_id,
+				RankingStakesOf::<T>::try_from(rankings).map_err(|_| "too many rankings")?,
+			);
+		}
+	}: _(RawOrigin::Root, poll_id)
+	verify {
+		let poll = PollDetailsOf::<T>::get(poll_id).unwrap();
+		assert!(!poll.status.is_ongoing());
+	}
+
+	reap_poll {
+		let poll_id = setup_poll::<T>(1, 2, VotingMode::Transfer)?;
+		let voter = funded_account::<T>("voter", 0);
+		FateriumPolls::<T>::vote(RawOrigin::Signed(voter.clone()).into(), poll_id, votes::<T>(2))?;
+		FateriumPolls::<T>::enact_poll_end(RawOrigin::Root.into(), poll_id)?;
+		// Beneficiary hasn't collected, so the reap delay should pass.
+		SettlementQueue::<T>::kill();
+		let end = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::MinPollDuration::get().max(One::one()));
+		frame_system::Pallet::<T>::set_block_number(end.saturating_add(T::ReapDelay::get()));
+	}: _(RawOrigin::Signed(voter), poll_id)
+	verify {
+		assert!(ReapQueue::<T>::get().contains(&poll_id));
+	}
+
+	cast_ballot {
+		let o in 2 .. T::MaxPollOptions::get();
+		let d in 0 .. T::MaxDelegators::get();
+		// Stake of the ballot is spread pro rata to scores of all options.
+		let poll_id = setup_poll::<T>(0, o, VotingMode::Transfer)?;
+		set_kind::<T>(poll_id, PollKind::Score { max_score: u8::MAX });
+		let voter = funded_account::<T>("voter", 0);
+		setup_delegators::<T>(&voter, d)?;
+		let scores: Vec<_> = (0..o).map(|i| (i + 1) as u8).collect();
+		let scores = scores.try_into().expect("options count is within the bound; qed");
+		let ballot = Ballot::Score { scores, stake: 10_000u32.into() };
+	}: _(RawOrigin::Signed(voter.clone()), poll_id, ballot)
 	verify {
-		assert_eq!(PollCount::<T>::get(), s.into());
+		assert!(BallotsOf::<T>::contains_key((voter, poll_id)));
 	}
 
-	impl_benchmark_test_suite!(FateriumPolls, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(FateriumPolls, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -0,0 +1,217 @@
+//! A minimal `no_std` decoder of IPFS CIDs.
+//!
+//! Supports CIDv0, which is a base58btc encoded sha2-256 multihash, and CIDv1 either in the binary
+//! form or in the base58btc, base32 and base16 multibase encodings. Decoded CIDs are normalized to
+//! the binary CIDv1 form, so the same content always has the same on-chain representation.
+
+use frame_support::RuntimeDebug;
+use scale_info::prelude::vec::Vec;
+
+/// Multicodec codes of the content types of a CID.
+pub mod codec {
+	/// Raw binary.
+	pub const RAW: u64 = 0x55;
+	/// MerkleDAG protobuf, the only codec of CIDv0.
+	pub const DAG_PB: u64 = 0x70;
+	/// MerkleDAG cbor.
+	pub const DAG_CBOR: u64 = 0x71;
+	/// MerkleDAG json.
+	pub const DAG_JSON: u64 = 0x0129;
+}
+
+/// Multicodec codes of the hash functions of a multihash.
+pub mod hash {
+	/// sha2-256, the only hash function of CIDv0.
+	pub const SHA2_256: u64 = 0x12;
+	/// sha2-512.
+	pub const SHA2_512: u64 = 0x13;
+	/// sha3-256.
+	pub const SHA3_256: u64 = 0x16;
+	/// keccak-256.
+	pub const KECCAK_256: u64 = 0x1b;
+	/// blake3, with the default 32 bytes output.
+	pub const BLAKE3: u64 = 0x1e;
+	/// blake2b-256.
+	pub const BLAKE2B_256: u64 = 0xb220;
+}
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+/// The maximum length of an unsigned varint, according to the multiformats spec.
+const MAX_VARINT_LEN: usize = 9;
+
+/// Reasons why a CID can't be decoded.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum CidError {
+	/// The CID is not properly encoded in a supported multibase.
+	InvalidEncoding,
+	/// The CID version is neither 0 nor 1.
+	UnsupportedVersion,
+	/// The multihash of the CID is malformed or doesn't match its hash function.
+	InvalidMultihash,
+}
+
+/// A decoded CID.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Cid {
+	/// The version of the CID as it was given, either 0 or 1.
+	pub version: u8,
+	/// Multicodec code of the content type.
+	pub codec: u64,
+	/// Multicodec code of the hash function.
+	pub hash: u64,
+	/// The digest of the content.
+	pub digest: Vec<u8>,
+}
+
+impl Cid {
+	/// Decodes the CID from its textual or binary form.
+	pub fn parse(input: &[u8]) -> Result<Self, CidError> {
+		match input {
+			// CIDv0 is always 46 characters long and starts with "Qm".
+			[b'Q', b'm', ..] if input.len() == 46 => {
+				let (hash, digest) = decode_multihash(&base58_decode(input)?)?;
+				if hash != hash::SHA2_256 {
+					return Err(CidError::InvalidMultihash)
+				}
+				Ok(Self { version: 0, codec: codec::DAG_PB, hash, digest })
+			},
+			// Binary CIDv1 starts with its version.
+			[0x01, ..] => Self::decode_v1(input),
+			[b'z', rest @ ..] => Self::decode_v1(&base58_decode(rest)?),
+			[b'b', rest @ ..] => Self::decode_v1(&base32_decode(rest, false)?),
+			[b'B', rest @ ..] => Self::decode_v1(&base32_decode(rest, true)?),
+			[b'f', rest @ ..] => Self::decode_v1(&base16_decode(rest, false)?),
+			[b'F', rest @ ..] => Self::decode_v1(&base16_decode(rest, true)?),
+			_ => Err(CidError::InvalidEncoding),
+		}
+	}
+
+	/// Decodes the binary form of CIDv1.
+	fn decode_v1(bytes: &[u8]) -> Result<Self, CidError> {
+		let (version, rest) = decode_varint(bytes).ok_or(CidError::InvalidEncoding)?;
+		if version != 1 {
+			return Err(CidError::UnsupportedVersion)
+		}
+		let (codec, rest) = decode_varint(rest).ok_or(CidError::InvalidEncoding)?;
+		let (hash, digest) = decode_multihash(rest)?;
+		Ok(Self { version: 1, codec, hash, digest })
+	}
+
+	/// Returns the binary form of the CID as CIDv1.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(self.digest.len() + 4 * MAX_VARINT_LEN);
+		encode_varint(1, &mut bytes);
+		encode_varint(self.codec, &mut bytes);
+		encode_varint(self.hash, &mut bytes);
+		encode_varint(self.digest.len() as u64, &mut bytes);
+		bytes.extend_from_slice(&self.digest);
+		bytes
+	}
+}
+
+/// Returns the digest length of the known hash functions.
+fn digest_len(hash: u64) -> Option<usize> {
+	match hash {
+		hash::SHA2_256 | hash::SHA3_256 | hash::KECCAK_256 | hash::BLAKE3 | hash::BLAKE2B_256 =>
+			Some(32),
+		hash::SHA2_512 => Some(64),
+		_ => None,
+	}
+}
+
+/// Decodes the multihash and returns its hash function and digest.
+fn decode_multihash(bytes: &[u8]) -> Result<(u64, Vec<u8>), CidError> {
+	let (hash, rest) = decode_varint(bytes).ok_or(CidError::InvalidMultihash)?;
+	let (len, digest) = decode_varint(rest).ok_or(CidError::InvalidMultihash)?;
+	let len = usize::try_from(len).map_err(|_| CidError::InvalidMultihash)?;
+	if len == 0 || len != digest.len() || digest_len(hash).map_or(false, |l| l != len) {
+		return Err(CidError::InvalidMultihash)
+	}
+	Ok((hash, digest.to_vec()))
+}
+
+/// Decodes an unsigned varint and returns it with the rest of the bytes.
+fn decode_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+	let mut value = 0u64;
+	for (i, byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+		value |= u64::from(byte & 0x7f) << (i * 7);
+		if byte & 0x80 == 0 {
+			// Reject non-minimal encodings, so every CID has a single binary form.
+			if i > 0 && *byte == 0 {
+				return None
+			}
+			return Some((value, &bytes[i + 1..]))
+		}
+	}
+	None
+}
+
+fn encode_varint(mut value: u64, bytes: &mut Vec<u8>) {
+	while value >= 0x80 {
+		bytes.push((value as u8) | 0x80);
+		value >>= 7;
+	}
+	bytes.push(value as u8);
+}
+
+fn base58_decode(input: &[u8]) -> Result<Vec<u8>, CidError> {
+	// Big number in little-endian order.
+	let mut bytes = Vec::with_capacity(input.len());
+	for c in input {
+		let mut carry =
+			BASE58_ALPHABET.iter().position(|a| a == c).ok_or(CidError::InvalidEncoding)? as u32;
+		for byte in bytes.iter_mut() {
+			carry += u32::from(*byte) * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+	// Leading ones encode leading zeros.
+	bytes.extend(input.iter().take_while(|c| **c == b'1').map(|_| 0));
+	bytes.reverse();
+	Ok(bytes)
+}
+
+fn base32_decode(input: &[u8], upper: bool) -> Result<Vec<u8>, CidError> {
+	let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+	let (mut buffer, mut bits) = (0u32, 0u32);
+	for c in input {
+		let c = match upper {
+			true if c.is_ascii_lowercase() => return Err(CidError::InvalidEncoding),
+			true => c.to_ascii_lowercase(),
+			false => *c,
+		};
+		let value =
+			BASE32_ALPHABET.iter().position(|a| *a == c).ok_or(CidError::InvalidEncoding)? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	// Multibase base32 has no padding, so only zero bits of the last character may remain.
+	if bits >= 5 || buffer != 0 {
+		return Err(CidError::InvalidEncoding)
+	}
+	Ok(bytes)
+}
+
+fn base16_decode(input: &[u8], upper: bool) -> Result<Vec<u8>, CidError> {
+	let digit = |c: u8| match c {
+		b'0'..=b'9' => Ok(c - b'0'),
+		b'a'..=b'f' if !upper => Ok(c - b'a' + 10),
+		b'A'..=b'F' if upper => Ok(c - b'A' + 10),
+		_ => Err(CidError::InvalidEncoding),
+	};
+	if input.len() % 2 != 0 {
+		return Err(CidError::InvalidEncoding)
+	}
+	input.chunks(2).map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?)).collect()
+}
@@ -9,39 +9,56 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod cid;
+pub mod migrations;
 #[cfg(test)]
 pub mod tests;
 mod types;
+pub mod weights;
 
 pub use pallet::*;
 pub use types::*;
+pub use weights::WeightInfo;
 
-use codec::{Encode, HasCompact};
+use cid::{Cid, CidError};
+use codec::{Decode, Encode, HasCompact};
 use frame_support::{
 	ensure,
 	inherent::Vec,
+	storage::{with_transaction, TransactionOutcome},
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed},
-		tokens::fungibles::{Balanced, Inspect, Transfer},
-		Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, ReservableCurrency,
+		tokens::{
+			fungible,
+			fungibles::{Balanced, CreditOf, Inspect, Transfer},
+		},
+		ConstU32, Contains, Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier,
+		LockableCurrency, OnUnbalanced, Randomness, ReservableCurrency, WithdrawReasons,
 	},
-	PalletId,
+	weights::Weight,
+	BoundedVec, PalletId,
 };
 use frame_system::Config as SystemConfig;
 use scale_info::prelude::*;
+use sp_core::{hashing::blake2_256, H256};
 use sp_runtime::{
 	traits::{
-		AccountIdConversion, AtLeast32BitUnsigned, CheckedDiv, Dispatchable, Saturating,
-		StaticLookup, Zero,
+		AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedSub,
+		Dispatchable, Hash, Saturating, StaticLookup, TrailingZeroInput, Zero,
 	},
 	ArithmeticError, DispatchError, DispatchResult,
 };
 
 const FATERIUM_POLLS_ID: LockIdentifier = *b"faterium";
+pub(crate) const LOG_TARGET: &str = "runtime::faterium-polls";
 
 /// Balance type alias.
 pub(crate) type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+/// Negative imbalance type alias.
+pub(crate) type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
 /// Account id lookup type alias.
 pub(crate) type AccountIdLookupOf<T> =
 	<<T as frame_system::Config>::Lookup as StaticLookup>::Source;
@@ -56,7 +73,51 @@ pub(crate) type PollTypeOf<T> = PollDetails<
 	<T as frame_system::Config>::AccountId,
 	AssetIdOf<T>,
 	BlockNumberOf<T>,
+	<T as Config>::MaxIpfsCidLength,
+	<T as Config>::MaxPollBeneficiaries,
+	<T as Config>::MaxPollOptions,
+>;
+/// Beneficiary type alias.
+pub(crate) type BeneficiaryOf<T> =
+	Beneficiary<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberOf<T>>;
+/// Beneficiaries with their interests and vesting schedules, as given to calls, type alias.
+pub(crate) type BeneficiariesArgOf<T> =
+	Vec<(AccountIdLookupOf<T>, u32, Option<VestingSchedule<BlockNumberOf<T>>>)>;
+/// Votes type alias.
+pub(crate) type VotesOf<T> = Votes<BalanceOf<T>, <T as Config>::MaxPollOptions>;
+/// Account votes type alias.
+pub(crate) type AccountVotesOf<T> = AccountVotes<BalanceOf<T>, <T as Config>::MaxPollOptions>;
+/// Goal mode type alias.
+pub(crate) type GoalModeOf<T> = GoalMode<BalanceOf<T>, <T as Config>::MaxPollOptions>;
+/// Ballot type alias.
+pub(crate) type BallotOf<T> = Ballot<BalanceOf<T>, <T as Config>::MaxPollOptions>;
+/// Ranking of options in a ballot type alias.
+pub(crate) type RankingOf<T> = BoundedVec<u8, <T as Config>::MaxPollOptions>;
+/// Distinct rankings of a ranked-choice poll with the stakes of their ballots type alias.
+pub(crate) type RankingStakesOf<T> =
+	BoundedVec<(RankingOf<T>, BalanceOf<T>), <T as Config>::MaxRankings>;
+/// Claimable type alias.
+pub(crate) type ClaimableOf<T> = Claimable<BalanceOf<T>, <T as Config>::MaxPollOptions>;
+/// Commitment type alias.
+pub(crate) type CommitmentOf<T> = Commitment<BalanceOf<T>, <T as frame_system::Config>::Hash>;
+/// Escrow type alias.
+pub(crate) type EscrowOf<T> = Escrow<BalanceOf<T>, BlockNumberOf<T>, <T as Config>::MaxMilestones>;
+/// Delegators with their allowances or stakes type alias.
+pub(crate) type DelegatorsOf<T> = BoundedVec<
+	(<T as frame_system::Config>::AccountId, BalanceOf<T>),
+	<T as Config>::MaxDelegators,
+>;
+/// Poll summary type alias.
+pub(crate) type PollSummaryOf<T> = PollSummary<
+	BalanceOf<T>,
+	<T as frame_system::Config>::AccountId,
+	AssetIdOf<T>,
+	BlockNumberOf<T>,
+	<T as Config>::MaxIpfsCidLength,
 >;
+/// Raw storage key of a participant of a poll, where its settlement or reaping is resumed, type
+/// alias.
+pub(crate) type SettlementCursor = BoundedVec<u8, ConstU32<128>>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -64,10 +125,12 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
-	// TODO: Remove without_storage_info macro. And somehow replace Vectors in storages.
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(10);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// The module configuration trait.
@@ -87,7 +150,8 @@ pub mod pallet {
 		/// Currency type for this pallet.
 		/// The Balance type should be the same as in assets pallet.
 		type Currency: ReservableCurrency<Self::AccountId>
-			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>
+			+ fungible::Inspect<Self::AccountId, Balance = BalanceOf<Self>>;
 
 		/// Identifier and index for polls.
 		type PollIndex: Member
@@ -106,13 +170,113 @@ pub mod pallet {
 		/// The Scheduler.
 		type Scheduler: ScheduleNamed<Self::BlockNumber, Self::PollCall, Self::PalletsOrigin>;
 
+		/// Something that provides randomness in the runtime, used to break ties of polls with
+		/// `TiePolicy::Random`. Block authors can bias a weak source, so the winner of a tie is
+		/// only as fair as this source.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
 		/// The polls' pallet id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
-		/// Maximum amount of funds that should be placed in a deposit for making a proposal.
+		/// The maximum length of IPFS CID of a poll, in the binary form.
+		#[pallet::constant]
+		type MaxIpfsCidLength: Get<u32>;
+
+		/// Multicodecs of the content types allowed in IPFS CIDs of polls.
+		type AllowedCidCodecs: Contains<u64>;
+
+		/// Multicodecs of the hash functions allowed in IPFS CIDs of polls.
+		type AllowedCidHashes: Contains<u64>;
+
+		/// The maximum number of beneficiaries of a poll.
 		#[pallet::constant]
 		type MaxPollBeneficiaries: Get<u32>;
+
+		/// The maximum number of options of a poll.
+		#[pallet::constant]
+		type MaxPollOptions: Get<u32>;
+
+		/// The maximum number of distinct rankings of options in ballots of a ranked-choice poll.
+		#[pallet::constant]
+		type MaxRankings: Get<u32>;
+
+		/// The minimum stake of a ballot in a ranked-choice poll, so the distinct rankings of the
+		/// poll can't be filled up cheaply.
+		#[pallet::constant]
+		type MinRankingStake: Get<BalanceOf<Self>>;
+
+		/// The maximum number of delegators of an account in one currency, and of delegators
+		/// with stakes in the votes of an account in one poll.
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+
+		/// The maximum number of milestones in which the interest of beneficiaries is released.
+		#[pallet::constant]
+		type MaxMilestones: Get<u32>;
+
+		/// The number of blocks during which voters can sign off a milestone.
+		#[pallet::constant]
+		type SignOffPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks per milestone, in which the creator of a poll should start the
+		/// sign-off of the milestone. The sign-off of the milestone `n` can be started by anyone
+		/// once `(n + 1) * MilestonePeriod` blocks have passed since the end of the poll.
+		#[pallet::constant]
+		type MilestonePeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of ended polls waiting to be settled, and of polls waiting to be
+		/// reaped, in the idle time of blocks.
+		#[pallet::constant]
+		type MaxSettlementQueue: Get<u32>;
+
+		/// The number of blocks after the end of a poll, after which it can be reaped even if its
+		/// beneficiaries haven't collected all their interest.
+		#[pallet::constant]
+		type ReapDelay: Get<Self::BlockNumber>;
+
+		/// Handler for the funds left in the pot by reaped polls in the native currency.
+		type Dust: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Handler for the assets left in the pot by reaped polls.
+		type AssetDust: OnUnbalanced<CreditOf<Self::AccountId, Self::Fungibles>>;
+
+		/// Membership of accounts in collections, e.g. of NFTs, checked for polls open only to
+		/// members of a collection.
+		type Collections: Contains<(u32, Self::AccountId)>;
+
+		/// The minimum number of blocks between the start and the end of a poll.
+		#[pallet::constant]
+		type MinPollDuration: Get<Self::BlockNumber>;
+
+		/// The maximum number of blocks between the start and the end of a poll.
+		#[pallet::constant]
+		type MaxPollDuration: Get<Self::BlockNumber>;
+
+		/// The minimum goal of a poll.
+		#[pallet::constant]
+		type MinPollGoal: Get<BalanceOf<Self>>;
+
+		/// The base amount of currency reserved from the creator of a poll.
+		#[pallet::constant]
+		type PollDepositBase: Get<DepositBalanceOf<Self>>;
+
+		/// The additional amount of currency reserved per byte of IPFS CID of a poll.
+		#[pallet::constant]
+		type PollDepositPerByte: Get<DepositBalanceOf<Self>>;
+
+		/// The additional amount of currency reserved per beneficiary of a poll.
+		#[pallet::constant]
+		type PollDepositPerBeneficiary: Get<DepositBalanceOf<Self>>;
+
+		/// Origin from which a poll may be forcibly cancelled and its deposit slashed.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the unbalanced reduction when slashing a poll deposit.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	/// The number of polls that have been made so far.
@@ -123,44 +287,257 @@ pub mod pallet {
 	/// Details of polls.
 	#[pallet::storage]
 	#[pallet::getter(fn poll_details_of)]
-	pub(super) type PollDetailsOf<T: Config> = StorageMap<
-		_,
-		Blake2_128Concat,
-		T::PollIndex,
-		PollDetails<BalanceOf<T>, T::AccountId, AssetIdOf<T>, BlockNumberOf<T>>,
-	>;
+	pub(super) type PollDetailsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PollIndex, PollTypeOf<T>>;
 
 	/// All votes for a particular voter.
 	#[pallet::storage]
 	#[pallet::getter(fn voting_of)]
 	pub type VotingOf<T: Config> =
-		StorageMap<_, Blake2_128Concat, (T::AccountId, T::PollIndex), AccountVotes<BalanceOf<T>>>;
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::PollIndex), AccountVotesOf<T>>;
+
+	/// Ballot cast by an account in a poll of a kind other than `Plurality`.
+	#[pallet::storage]
+	#[pallet::getter(fn ballots_of)]
+	pub type BallotsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::PollIndex), BallotOf<T>>;
+
+	/// Sum of weights of all ballots on every option of an `Approval` or a `Score` poll.
+	#[pallet::storage]
+	#[pallet::getter(fn tally_of)]
+	pub type TallyOf<T: Config> = StorageMap<_, Blake2_128Concat, T::PollIndex, VotesOf<T>>;
+
+	/// Distinct rankings of options in ballots of a `RankedChoice` poll, with the sum of stakes
+	/// of ballots with each ranking.
+	#[pallet::storage]
+	#[pallet::getter(fn rankings_of)]
+	pub type RankingsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PollIndex, RankingStakesOf<T>, ValueQuery>;
+
+	/// Votes committed by an account to a sealed poll, which are not revealed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn commitments_of)]
+	pub type CommitmentsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::PollIndex), CommitmentOf<T>>;
+
+	/// Escrow of the beneficiaries' interest in polls with milestones.
+	#[pallet::storage]
+	#[pallet::getter(fn escrows_of)]
+	pub type EscrowsOf<T: Config> = StorageMap<_, Blake2_128Concat, T::PollIndex, EscrowOf<T>>;
+
+	/// Participation of a voter in the escrow of a poll.
+	#[pallet::storage]
+	#[pallet::getter(fn escrow_participation_of)]
+	pub type EscrowParticipationOf<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, T::PollIndex),
+		EscrowParticipation,
+		ValueQuery,
+	>;
+
+	/// Accounts allowed to vote in polls with an allowlist, or which proved to be in the
+	/// Merkle tree of a poll.
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_of)]
+	pub type AllowlistOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::PollIndex), ()>;
+
+	/// Total stake locked by an account in all polls with `Lock` voting mode.
+	#[pallet::storage]
+	#[pallet::getter(fn locked_of)]
+	pub type LockedOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Delegators of an account in a currency, with amounts the account can still spend.
+	#[pallet::storage]
+	#[pallet::getter(fn delegations_of)]
+	pub type DelegationsOf<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, PollCurrency<AssetIdOf<T>>),
+		DelegatorsOf<T>,
+		ValueQuery,
+	>;
+
+	/// Delegators with stakes in the votes of an account in a poll.
+	#[pallet::storage]
+	#[pallet::getter(fn delegated_stakes_of)]
+	pub type DelegatedStakesOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AccountId, T::PollIndex), DelegatorsOf<T>, ValueQuery>;
+
+	/// Accounts with votes, commitments or allowlist entries in a poll, so the accounts of one
+	/// poll are visited without iterating the entries of all polls.
+	#[pallet::storage]
+	pub type ParticipantsOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::PollIndex, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Ended polls, whose votes are paid out in the idle time of blocks, in the order they ended.
+	/// Polls which ended while the queue was full aren't queued, and are paid out when collected
+	/// or reaped.
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_queue)]
+	pub type SettlementQueue<T: Config> =
+		StorageValue<_, BoundedVec<T::PollIndex, T::MaxSettlementQueue>, ValueQuery>;
+
+	/// The last visited participant of the first poll in the settlement queue.
+	#[pallet::storage]
+	#[pallet::getter(fn settlement_cursor_of)]
+	pub type SettlementCursorOf<T: Config> = StorageValue<_, SettlementCursor>;
+
+	/// Polls, whose storage is removed in the idle time of blocks, in the order they were reaped.
+	#[pallet::storage]
+	#[pallet::getter(fn reap_queue)]
+	pub type ReapQueue<T: Config> =
+		StorageValue<_, BoundedVec<T::PollIndex, T::MaxSettlementQueue>, ValueQuery>;
+
+	/// The storage of the first poll in the reap queue which is being removed, and the last
+	/// visited participant of it.
+	#[pallet::storage]
+	#[pallet::getter(fn reap_cursor_of)]
+	pub type ReapCursorOf<T: Config> = StorageValue<_, (ReapPhase, Option<SettlementCursor>)>;
+
+	/// Summaries of reaped polls.
+	#[pallet::storage]
+	#[pallet::getter(fn archive_of)]
+	pub type ArchiveOf<T: Config> = StorageMap<_, Blake2_128Concat, T::PollIndex, PollSummaryOf<T>>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// A poll was created.
+		/// A poll was created, with its IPFS CID in the binary CIDv1 form.
 		Created { poll_id: T::PollIndex, cid: IpfsCid, creator: T::AccountId },
+		/// A poll was updated by its creator, with the fields which were changed.
+		Updated {
+			poll_id: T::PollIndex,
+			cid: Option<IpfsCid>,
+			beneficiaries: Option<Vec<BeneficiaryOf<T>>>,
+			reward_settings: Option<RewardSettings<BalanceOf<T>>>,
+			goal: Option<BalanceOf<T>>,
+			end: Option<BlockNumberOf<T>>,
+		},
 		/// A poll has been cancelled.
 		Cancelled { poll_id: T::PollIndex },
+		/// A deposit of the poll creator has been slashed.
+		DepositSlashed { poll_id: T::PollIndex, who: T::AccountId, amount: BalanceOf<T> },
 		/// An account has voted in a poll.
-		Voted { voter: T::AccountId, poll_id: T::PollIndex, votes: Votes<BalanceOf<T>> },
+		Voted { voter: T::AccountId, poll_id: T::PollIndex, votes: VotesOf<T> },
+		/// An account has cast a ballot in a poll, with its stake spread across options.
+		BallotCast {
+			voter: T::AccountId,
+			poll_id: T::PollIndex,
+			ballot: BallotOf<T>,
+			votes: VotesOf<T>,
+		},
 		/// An account has voted in a poll.
 		VoteRemoved { voter: T::AccountId, poll_id: T::PollIndex },
+		/// An account has withdrawn a part of his votes from a poll.
+		VotesWithdrawn { voter: T::AccountId, poll_id: T::PollIndex, votes: VotesOf<T> },
+		/// An account has moved a part of his votes from one poll option to another.
+		VotesMoved {
+			voter: T::AccountId,
+			poll_id: T::PollIndex,
+			from: u8,
+			to: u8,
+			amount: BalanceOf<T>,
+		},
+		/// An account has committed the hash of his votes to a sealed poll.
+		VotesCommitted { voter: T::AccountId, poll_id: T::PollIndex, stake: BalanceOf<T> },
+		/// An account has revealed his votes committed to a sealed poll.
+		VotesRevealed { voter: T::AccountId, poll_id: T::PollIndex, votes: VotesOf<T> },
+		/// The stake of votes which were not revealed was refunded, and the penalty slashed.
+		CommitmentRefunded {
+			voter: T::AccountId,
+			poll_id: T::PollIndex,
+			amount: BalanceOf<T>,
+			penalty: BalanceOf<T>,
+		},
+		/// Accounts were added to or removed from the allowlist of a poll by its creator.
+		AllowlistUpdated { poll_id: T::PollIndex, accounts: Vec<T::AccountId>, allowed: bool },
+		/// An account has proven to be in the Merkle tree of a poll.
+		EligibilityProven { who: T::AccountId, poll_id: T::PollIndex },
+		/// Milestones in which the interest of beneficiaries is released were set for a poll.
+		MilestonesSet { poll_id: T::PollIndex, milestones: Vec<u32> },
+		/// Sign-off of the next milestone of a poll was started by its creator.
+		SignOffStarted { poll_id: T::PollIndex, milestone: u8, end: BlockNumberOf<T> },
+		/// A voter has approved or rejected a milestone of a poll.
+		SignedOff {
+			voter: T::AccountId,
+			poll_id: T::PollIndex,
+			milestone: u8,
+			approve: bool,
+			stake: BalanceOf<T>,
+		},
+		/// A milestone was approved, so its share of the interest can be collected.
+		MilestoneApproved { poll_id: T::PollIndex, milestone: u8 },
+		/// A milestone was rejected, so the rest of the escrow is returned to voters.
+		MilestoneRejected { poll_id: T::PollIndex, milestone: u8 },
 		/// Voter/beneficiary collected his vote/interest.
 		Collected { who: T::AccountId, poll_id: T::PollIndex, amount: BalanceOf<T> },
+		/// An account has delegated voting power to another account.
+		Delegated {
+			delegator: T::AccountId,
+			delegate: T::AccountId,
+			currency: PollCurrency<AssetIdOf<T>>,
+			amount: BalanceOf<T>,
+		},
+		/// An account has revoked voting power delegated to another account.
+		Undelegated {
+			delegator: T::AccountId,
+			delegate: T::AccountId,
+			currency: PollCurrency<AssetIdOf<T>>,
+		},
+		/// A delegator was paid his share of what was returned from the votes of his delegate.
+		DelegatorPaid {
+			delegator: T::AccountId,
+			delegate: T::AccountId,
+			poll_id: T::PollIndex,
+			amount: BalanceOf<T>,
+		},
 		/// A poll was finished.
 		Finished { poll_id: T::PollIndex },
+		/// All votes of an ended poll were paid out, together with its beneficiaries and creator.
+		Settled { poll_id: T::PollIndex },
+		/// A poll was queued to be reaped.
+		ReapQueued { poll_id: T::PollIndex },
+		/// Storage of a poll was removed and its summary archived, with funds left in the pot.
+		Reaped { poll_id: T::PollIndex, dust: BalanceOf<T> },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Too high a balance was provided that the account cannot afford.
 		InsufficientFunds,
+		/// The creator can't afford the deposit of the poll.
+		InsufficientDeposit,
 		/// Invalid poll details given.
 		InvalidPollDetails,
 		/// Invalid poll start or end given.
 		InvalidPollPeriod,
+		/// IPFS CID is longer than allowed.
+		InvalidIpfsCid,
+		/// IPFS CID is not properly encoded in a supported multibase.
+		InvalidCidEncoding,
+		/// IPFS CID version is neither 0 nor 1.
+		UnsupportedCidVersion,
+		/// IPFS CID content type is not allowed.
+		UnsupportedCidCodec,
+		/// IPFS CID hash function is not allowed.
+		UnsupportedCidHash,
+		/// IPFS CID multihash is malformed.
+		InvalidCidMultihash,
+		/// A poll should have at least two options.
+		TooFewPollOptions,
+		/// A poll has more options than allowed.
+		TooManyPollOptions,
+		/// A poll has more beneficiaries than allowed.
+		TooManyBeneficiaries,
+		/// The poll goal is lower than allowed.
+		PollGoalTooLow,
+		/// The poll duration is shorter than allowed.
+		PollTooShort,
+		/// The poll duration is longer than allowed.
+		PollTooLong,
 		/// Invalid poll currency given.
 		InvalidPollCurrency,
 		/// Invalid poll_id given for a poll.
@@ -169,8 +546,12 @@ pub mod pallet {
 		InvalidPollVotes,
 		/// Multiple votes on the poll are not allowed.
 		MultipleVotesNotAllowed,
+		/// Moving votes between options is not allowed in the poll.
+		VoteMovesNotAllowed,
 		/// The poll has not yet started.
 		PollNotStarted,
+		/// The poll has already started.
+		PollAlreadyStarted,
 		/// The poll has already finished.
 		PollAlreadyFinished,
 		/// Can't collect from Ongoing Poll.
@@ -183,56 +564,167 @@ pub mod pallet {
 		NothingToCollect,
 		/// The account currently has no votes attached to a poll.
 		VotesNotExist,
-		/// FATAL ERROR: The pot account cannot afford to transfer requested funds.
+		/// Can't withdraw more votes than the account has on an option.
+		InsufficientVotes,
+		/// The call doesn't support the kind of the poll.
+		InvalidPollKind,
+		/// Invalid ballot given for the kind of the poll.
+		InvalidBallot,
+		/// The account has already cast a ballot in the poll.
+		BallotAlreadyCast,
+		/// The ballots of the poll have more distinct rankings than allowed.
+		TooManyRankings,
+		/// Votes in the poll are sealed, so they should be committed and revealed.
+		PollSealed,
+		/// Votes in the poll are public, so they can't be committed.
+		PollNotSealed,
+		/// The account has already committed or revealed his votes in the poll.
+		CommitmentAlreadyExists,
+		/// The account hasn't committed votes to the poll, or has already revealed them.
+		CommitmentNotExist,
+		/// Revealed votes or salt don't match the commitment.
+		CommitmentMismatch,
+		/// Votes can be committed only before the end of the poll.
+		CommitPeriodEnded,
+		/// Votes can be revealed only after the end of the poll.
+		RevealPeriodNotStarted,
+		/// Milestones should release the whole interest, and only in `Transfer` voting mode.
+		InvalidMilestones,
+		/// The poll has more milestones than allowed.
+		TooManyMilestones,
+		/// The poll has no milestones.
+		EscrowNotExist,
+		/// All milestones of the poll are released, or one of them was rejected.
+		EscrowSettled,
+		/// Interest of the poll goes to nobody, as no option has won.
+		NoWinningOptions,
+		/// Sign-off of a milestone is already in progress.
+		SignOffInProgress,
+		/// There is no sign-off of a milestone in progress.
+		SignOffNotInProgress,
+		/// The sign-off of the milestone has already ended.
+		SignOffEnded,
+		/// The sign-off of the milestone hasn't ended yet.
+		SignOffNotEnded,
+		/// The account has already signed off the milestone.
+		AlreadySignedOff,
+		/// The account doesn't satisfy the eligibility rule of the poll.
+		NotEligible,
+		/// The eligibility rule of the poll doesn't support this call.
+		InvalidEligibilityRule,
+		/// The Merkle proof doesn't lead to the root of the poll.
+		InvalidMerkleProof,
+		/// Voting power can be delegated only to another account, and in a non-zero amount.
+		InvalidDelegation,
+		/// The account hasn't delegated voting power to the given account in the currency.
+		DelegationNotExist,
+		/// The account has more delegators than allowed.
+		TooManyDelegators,
+		/// Votes of the poll are still being settled.
+		PollNotSettled,
+		/// The poll is already queued to be reaped.
+		PollAlreadyReaping,
+		/// Beneficiaries haven't collected their interest, and the reap delay hasn't passed.
+		PollNotReapable,
+		/// Too many polls are queued to be reaped.
+		ReapQueueFull,
+		/// The poll already has votes, so it can't be updated.
+		PollHasVotes,
+		/// Interest of the poll is still vesting, or held in an unsettled escrow.
+		InterestNotReleased,
+		/// The stake of a ranked ballot is lower than the minimum.
+		BallotStakeTooLow,
+		/// FATAL ERROR: The pot account of the poll cannot afford to transfer requested funds.
 		PotInsufficientFunds,
 		/// FATAL ERROR: The unexpected behavior occur.
 		UnexpectedBehavior,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used = Self::settle_polls(remaining_weight);
+			used.saturating_add(Self::reap_polls(remaining_weight.saturating_sub(used)))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a poll.
 		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
-		/// - `ipfs_cid`: The IPFS CID of the poll.
-		/// - `beneficiaries`: Those who will get winning deposit, summary min=0, max=10_000.
+		/// - `ipfs_cid`: The IPFS CID of the poll, either CIDv0, CIDv1 in base58btc, base32 or
+		///   base16 multibase, or binary CIDv1. It's stored in the binary CIDv1 form.
+		/// - `beneficiaries`: Those who will get winning deposit, summary min=0, max=10_000, with
+		///   optional vesting schedules of their interest.
 		/// - `reward_settings`: Reward settings of the poll.
-		/// - `goal`: The goal or minimum target amount on one option for the poll to happen.
+		/// - `goal`: The goal, or the minimum capital of all options for the poll to happen.
+		/// - `goal_mode`: What else the poll should reach to happen: the goal on the winning
+		///   option, goals of every option, or a part of the capital on the winning option.
 		/// - `options_count`: The number of poll options.
 		/// - `multiple_votes`: Make it possible to vote for multiple options.
+		/// - `movable_votes`: Make it possible for voters to move their votes between options.
+		/// - `tie_policy`: How the poll is settled if several options have the same highest stake.
+		/// - `voting_mode`: Whether stakes are transferred to the pot or locked in voters'
+		///   accounts.
+		/// - `sealed`: Settings of a sealed poll, where votes are committed as hashes until the end
+		///   and revealed after it, or `None` for public votes.
+		/// - `eligibility`: The rule an account should satisfy to vote in the poll.
+		/// - `kind`: The rule deciding the winner of the poll. Polls of kinds other than
+		///   `Plurality` are voted in with `cast_ballot`, can't be sealed and can't split interest
+		///   on a tie.
 		/// - `currency`: Currency of the poll.
 		/// - `start`: When voting on this poll will begin.
 		/// - `end`: When voting on this poll will end.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2).ref_time())]
+		#[pallet::weight(T::WeightInfo::create_poll(
+			beneficiaries.len() as u32,
+			(*options_count).into(),
+		))]
 		pub fn create_poll(
 			origin: OriginFor<T>,
 			ipfs_cid: IpfsCid,
-			beneficiaries: Vec<(AccountIdLookupOf<T>, u32)>,
-			reward_settings: RewardSettings,
+			beneficiaries: BeneficiariesArgOf<T>,
+			reward_settings: RewardSettings<BalanceOf<T>>,
 			goal: BalanceOf<T>,
+			goal_mode: GoalModeOf<T>,
 			options_count: u8,
 			multiple_votes: bool,
+			movable_votes: bool,
+			tie_policy: TiePolicy,
+			voting_mode: VotingMode,
+			sealed: Option<SealedSettings<BlockNumberOf<T>>>,
+			eligibility: Eligibility<AssetIdOf<T>, BalanceOf<T>>,
+			kind: PollKind,
 			currency: PollCurrency<AssetIdOf<T>>,
 			start: BlockNumberOf<T>,
 			end: BlockNumberOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			// Lookup for accounts of beneficiaries.
-			let mut benfs = vec![];
-			for b in beneficiaries {
-				let account = T::Lookup::lookup(b.0)?;
-				benfs.push(Beneficiary::new(account, b.1));
-			}
+			let benfs = Self::lookup_beneficiaries(beneficiaries)?;
+			let cid = Self::normalize_cid(&ipfs_cid)?;
+			let bounded_cid = cid.clone().try_into().map_err(|_| Error::<T>::InvalidIpfsCid)?;
 			// Create poll details struct.
 			let poll = PollDetails::new(
 				who.clone(),
-				ipfs_cid.clone(),
+				bounded_cid,
 				benfs,
 				reward_settings,
 				goal,
+				goal_mode,
 				options_count,
 				multiple_votes,
+				movable_votes,
+				tie_policy,
+				voting_mode,
+				sealed,
+				eligibility,
+				kind,
 				currency,
 				start,
 				end,
@@ -240,7 +732,59 @@ pub mod pallet {
 			// Call inner function.
 			let poll_id = Self::try_create_poll(poll)?;
 			// Emit an event.
-			Self::deposit_event(Event::Created { poll_id, cid: ipfs_cid, creator: who });
+			Self::deposit_event(Event::Created { poll_id, cid, creator: who });
+			Ok(())
+		}
+
+		/// Update a poll before its start.
+		///
+		/// Only given fields are changed. Voting isn't possible before the start of a poll, so
+		/// there are no votes yet, and a poll with votes is never updated. The deposit of the
+		/// creator and the reward pool are adjusted to the new poll details.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the creator of the poll.
+		///
+		/// - `poll_id`: The index of the poll to update.
+		/// - `ipfs_cid`: The new IPFS CID of the poll, in any form accepted by `create_poll`.
+		/// - `beneficiaries`: The new beneficiaries, summary min=0, max=10_000, with optional
+		///   vesting schedules of their interest.
+		/// - `reward_settings`: The new reward settings of the poll.
+		/// - `goal`: The new goal of the poll.
+		/// - `end`: When voting on this poll will end, the end of the poll is rescheduled.
+		#[pallet::weight(T::WeightInfo::update_poll(
+			beneficiaries.as_ref().map_or(0, |b| b.len() as u32),
+		))]
+		pub fn update_poll(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			ipfs_cid: Option<IpfsCid>,
+			beneficiaries: Option<BeneficiariesArgOf<T>>,
+			reward_settings: Option<RewardSettings<BalanceOf<T>>>,
+			goal: Option<BalanceOf<T>>,
+			end: Option<BlockNumberOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let benfs = beneficiaries.map(Self::lookup_beneficiaries).transpose()?;
+			let cid = ipfs_cid.map(|cid| Self::normalize_cid(&cid)).transpose()?;
+			// Call inner function.
+			Self::try_update_poll(
+				&who,
+				poll_id,
+				cid.clone(),
+				benfs.clone(),
+				reward_settings,
+				goal,
+				end,
+			)?;
+			// Emit an event.
+			Self::deposit_event(Event::Updated {
+				poll_id,
+				cid,
+				beneficiaries: benfs.map(|b| b.into_inner()),
+				reward_settings,
+				goal,
+				end,
+			});
 			Ok(())
 		}
 
@@ -251,7 +795,7 @@ pub mod pallet {
 		/// The dispatch origin of this call must be _Signed_.
 		///
 		/// - `poll_id`: The index of the poll to cancel.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2).ref_time())]
+		#[pallet::weight(T::WeightInfo::emergency_cancel())]
 		pub fn emergency_cancel(
 			origin: OriginFor<T>,
 			#[pallet::compact] poll_id: T::PollIndex,
@@ -264,19 +808,47 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Forcibly cancel a poll, slashing the deposit of its creator.
+		///
+		/// Used to remove abusive polls. A poll which already ended or was cancelled by its
+		/// creator isn't cancelled again, but its deposit is still slashed until the poll is
+		/// reaped. Can't be called if poll already finished and has no deposit. Voters can
+		/// collect their stakes as from any cancelled poll.
+		///
+		/// The dispatch origin of this call must be `ForceOrigin`.
+		///
+		/// - `poll_id`: The index of the poll to cancel.
+		#[pallet::weight(T::WeightInfo::force_cancel())]
+		pub fn force_cancel(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			// Call inner function.
+			let cancelled = Self::try_force_cancel(poll_id)?;
+			// Emit an event.
+			if cancelled {
+				Self::deposit_event(Event::<T>::Cancelled { poll_id });
+			}
+			Ok(())
+		}
+
 		/// Vote in a poll.
 		///
+		/// In `Transfer` voting mode, origin pays with its own funds first, and voting power
+		/// delegated to origin in the poll currency is spent only for the rest.
+		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
 		/// - `poll_id`: The index of the poll to vote for.
 		/// - `votes`: The votes balances, should match number of options.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2).ref_time())]
+		#[pallet::weight(T::WeightInfo::vote(votes.0.len() as u32, T::MaxDelegators::get()))]
 		pub fn vote(
 			origin: OriginFor<T>,
 			#[pallet::compact] poll_id: T::PollIndex,
 			// TODO: Perhaps it's better to receive a vec of Balances with poll_option index
 			// mapping, and then convert it to Votes struct. Instead of receiving zeros.
-			votes: Votes<BalanceOf<T>>,
+			votes: VotesOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			// Call inner function.
@@ -289,14 +861,18 @@ pub mod pallet {
 		/// Remove vote from a poll.
 		///
 		/// Origin can remove only own vote. If this function called - all account Votes will be
-		/// removed from a poll, and all staked balances will be returned to origin.
+		/// removed from a poll, and all staked balances will be returned to origin. The ballot of
+		/// origin is removed from the tally of the poll, so another one can be cast.
 		///
 		/// Can't be called after finish of a poll.
 		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
 		/// - `poll_id`: The index of the poll to remove votes.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2).ref_time())]
+		#[pallet::weight(T::WeightInfo::remove_vote(
+			T::MaxPollOptions::get(),
+			T::MaxDelegators::get(),
+		))]
 		pub fn remove_vote(
 			origin: OriginFor<T>,
 			#[pallet::compact] poll_id: T::PollIndex,
@@ -309,104 +885,516 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Collect a vote stake or/and winning option from a poll.
+		/// Withdraw a part of votes from a poll.
 		///
-		/// This function will check if account is one of: in benefitiaries,
-		/// or is a voter (poll cancelled or his vote on poll option won/lost).
+		/// Origin can withdraw only own votes. Withdrawn stakes are returned to origin, and the
+		/// rest of his votes stay in the poll. If all votes are withdrawn, it's the same as
+		/// `remove_vote`.
+		///
+		/// Can't be called after finish of a poll.
 		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
-		/// - `poll_id`: The index of the poll to collect.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2).ref_time())]
-		pub fn collect(
+		/// - `poll_id`: The index of the poll to withdraw votes from.
+		/// - `votes`: The votes balances to withdraw, should match number of options.
+		#[pallet::weight(T::WeightInfo::withdraw_votes(
+			votes.0.len() as u32,
+			T::MaxDelegators::get(),
+		))]
+		pub fn withdraw_votes(
 			origin: OriginFor<T>,
 			#[pallet::compact] poll_id: T::PollIndex,
+			votes: VotesOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			// Call inner function.
-			let amount = Self::try_collect(&who, poll_id)?;
+			Self::try_withdraw_votes(&who, poll_id, &votes)?;
 			// Emit an event.
-			Self::deposit_event(Event::Collected { who, poll_id, amount });
+			Self::deposit_event(Event::VotesWithdrawn { voter: who, poll_id, votes });
 			Ok(())
 		}
 
-		/// Enact poll end.
+		/// Move a part of votes from one poll option to another.
 		///
-		/// The dispatch origin of this call must be _ROOT_.
+		/// Origin can move only own votes, no balance is transferred or locked. In a poll without
+		/// multiple votes, all votes of origin should be moved at once.
 		///
-		/// - `poll_id`: The index of the poll to enact end.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2).ref_time())]
-		pub fn enact_poll_end(origin: OriginFor<T>, poll_id: T::PollIndex) -> DispatchResult {
-			ensure_root(origin)?;
-			Self::do_enact_poll_end(poll_id)?;
+		/// Can't be called after finish of a poll, or if the poll doesn't allow to move votes.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll to move votes in.
+		/// - `from`: The poll option to move votes from.
+		/// - `to`: The poll option to move votes to.
+		/// - `amount`: The stake to move.
+		#[pallet::weight(T::WeightInfo::move_votes(T::MaxPollOptions::get()))]
+		pub fn move_votes(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			from: u8,
+			to: u8,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			Self::try_move_votes(&who, poll_id, from, to, amount)?;
 			// Emit an event.
-			Self::deposit_event(Event::Finished { poll_id });
+			Self::deposit_event(Event::VotesMoved { voter: who, poll_id, from, to, amount });
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// The account ID of the faterium polls pot.
-	///
-	/// This actually does computation. If you need to keep using it, then make sure you cache the
-	/// value and only call this once.
-	pub fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account_truncating()
-	}
+		/// Delegate voting power to another account.
+		///
+		/// The delegate can spend up to `amount` of origin's funds in `currency` on his votes in
+		/// polls with `Transfer` voting mode. Funds stay in origin's account until they are spent,
+		/// and what is returned from the votes is paid back to origin, pro rata to his stake in
+		/// them. Replaces the previous delegation of origin to the same account and currency.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `to`: The account to delegate voting power to.
+		/// - `currency`: The currency of the delegated voting power.
+		/// - `amount`: The maximum amount the delegate can spend.
+		#[pallet::weight(T::WeightInfo::delegate(T::MaxDelegators::get()))]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			to: AccountIdLookupOf<T>,
+			currency: PollCurrency<AssetIdOf<T>>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			// Call inner function.
+			Self::try_delegate(&who, &to, currency, amount)?;
+			// Emit an event.
+			Self::deposit_event(Event::Delegated {
+				delegator: who,
+				delegate: to,
+				currency,
+				amount,
+			});
+			Ok(())
+		}
 
-	/// Return the amount of money in the balances pot.
-	/// The existential deposit is not part of the pot so account never gets deleted.
-	pub fn balances_pot() -> BalanceOf<T> {
-		T::Currency::free_balance(&Self::account_id())
-			// Must never be less than 0 but better be safe.
-			.saturating_sub(T::Currency::minimum_balance())
-			.into()
-	}
+		/// Revoke voting power delegated to another account.
+		///
+		/// Stakes which were already spent by the delegate stay in polls, and are paid back to
+		/// origin as usual.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `to`: The account to revoke voting power from.
+		/// - `currency`: The currency of the delegated voting power.
+		#[pallet::weight(T::WeightInfo::undelegate(T::MaxDelegators::get()))]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			to: AccountIdLookupOf<T>,
+			currency: PollCurrency<AssetIdOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			// Call inner function.
+			Self::try_undelegate(&who, &to, currency)?;
+			// Emit an event.
+			Self::deposit_event(Event::Undelegated { delegator: who, delegate: to, currency });
+			Ok(())
+		}
 
-	/// Return the amount of money in the asset pot by asset_id.
-	pub fn asset_pot(asset_id: AssetIdOf<T>) -> BalanceOf<T> {
-		<T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, &Self::account_id()).into()
-	}
+		/// Commit votes to a sealed poll, as the hash of the votes and a salt.
+		///
+		/// The stake is transferred or locked according to the voting mode of the poll, and
+		/// should be equal to the capital of the votes. Votes are revealed with `reveal_votes`
+		/// after the end of the poll, otherwise the stake can only be refunded with
+		/// `refund_commitment`, and the penalty of the poll is slashed from it.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the sealed poll to commit votes to.
+		/// - `hash`: The hash of the SCALE-encoded tuple of the votes and a 32 byte salt.
+		/// - `stake`: The capital of the votes.
+		#[pallet::weight(T::WeightInfo::commit_votes())]
+		pub fn commit_votes(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			hash: T::Hash,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			Self::try_commit_votes(&who, poll_id, hash, stake)?;
+			// Emit an event.
+			Self::deposit_event(Event::VotesCommitted { voter: who, poll_id, stake });
+			Ok(())
+		}
 
-	/// Returns Ok(PollDetails) if the given poll.status is Ongoing,
-	/// Error::PollInvalid or Error::PollAlreadyFinished otherwise.
-	fn poll_status(
-		poll_id: T::PollIndex,
-	) -> Result<PollDetails<BalanceOf<T>, T::AccountId, AssetIdOf<T>, T::BlockNumber>, DispatchError>
-	{
-		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
-		match poll.status.is_ongoing() {
-			true => Ok(poll),
-			_ => Err(Error::<T>::PollAlreadyFinished.into()),
+		/// Reveal votes committed to a sealed poll.
+		///
+		/// Can be called only in the reveal period after the end of the poll. Revealed votes are
+		/// tallied as usual, and can be collected after the poll is settled.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the sealed poll to reveal votes in.
+		/// - `votes`: The committed votes.
+		/// - `salt`: The salt of the commitment.
+		#[pallet::weight(T::WeightInfo::reveal_votes(votes.0.len() as u32))]
+		pub fn reveal_votes(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			votes: VotesOf<T>,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			Self::try_reveal_votes(&who, poll_id, votes.clone(), salt)?;
+			// Emit an event.
+			Self::deposit_event(Event::VotesRevealed { voter: who, poll_id, votes });
+			Ok(())
 		}
-	}
 
-	fn check_balance(
-		who: &T::AccountId,
-		currency: PollCurrency<AssetIdOf<T>>,
-		cap: BalanceOf<T>,
-	) -> bool {
-		match currency {
-			PollCurrency::Native => cap <= T::Currency::free_balance(who).into(),
-			PollCurrency::Asset(asset_id) =>
-				cap <= <T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, who).into(),
+		/// Refund the stake of votes which were committed to a sealed poll, but not revealed.
+		///
+		/// Can be called only after the poll is settled. The penalty of the poll is slashed from
+		/// the stake, unless the poll was cancelled.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the sealed poll.
+		#[pallet::weight(T::WeightInfo::refund_commitment())]
+		pub fn refund_commitment(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			let (amount, penalty) = Self::try_refund_commitment(&who, poll_id)?;
+			// Emit an event.
+			Self::deposit_event(Event::CommitmentRefunded { voter: who, poll_id, amount, penalty });
+			Ok(())
 		}
-	}
 
-	fn transfer_balance(
-		source: &T::AccountId,
-		dest: &T::AccountId,
-		currency: PollCurrency<AssetIdOf<T>>,
-		balance: BalanceOf<T>,
-	) -> DispatchResult {
-		match currency {
-			PollCurrency::Native => {
-				// TODO: Perhaps we want make some other function here to not pay fees.
-				T::Currency::transfer(source, dest, balance, ExistenceRequirement::AllowDeath)?;
-			},
-			PollCurrency::Asset(asset_id) => {
-				// TODO: Perhaps we want make something like `teleport` here to not pay fees.
+		/// Add accounts to or remove them from the allowlist of a poll.
+		///
+		/// Can be called only by the creator of an ongoing poll with the `Allowlist` eligibility
+		/// rule.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll.
+		/// - `accounts`: Accounts to add or remove.
+		/// - `allowed`: Whether accounts are added or removed.
+		#[pallet::weight(T::WeightInfo::update_allowlist(accounts.len() as u32))]
+		pub fn update_allowlist(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			accounts: Vec<AccountIdLookupOf<T>>,
+			allowed: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let accounts =
+				accounts.into_iter().map(T::Lookup::lookup).collect::<Result<Vec<_>, _>>()?;
+			// Call inner function.
+			Self::try_update_allowlist(&who, poll_id, &accounts, allowed)?;
+			// Emit an event.
+			Self::deposit_event(Event::AllowlistUpdated { poll_id, accounts, allowed });
+			Ok(())
+		}
+
+		/// Prove that the origin is in the Merkle tree of a poll, to be able to vote in it.
+		///
+		/// Can be called only for an ongoing poll with the `MerkleRoot` eligibility rule.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll.
+		/// - `proof`: Hashes of sibling nodes on the path from the leaf of origin to the root.
+		#[pallet::weight(T::WeightInfo::prove_eligibility(proof.len() as u32))]
+		pub fn prove_eligibility(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			proof: Vec<H256>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			Self::try_prove_eligibility(&who, poll_id, &proof)?;
+			// Emit an event.
+			Self::deposit_event(Event::EligibilityProven { who, poll_id });
+			Ok(())
+		}
+
+		/// Set milestones in which the interest of beneficiaries is released, before the start of
+		/// a poll.
+		///
+		/// The interest is kept in escrow after the end of the poll, and each milestone releases
+		/// its share only after voters of the winning options approve it. If a milestone is
+		/// rejected, the rest of the escrow is returned to the voters. Only polls with `Transfer`
+		/// voting mode can have milestones.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the creator of the poll.
+		///
+		/// - `poll_id`: The index of the poll.
+		/// - `milestones`: Shares of the interest released by milestones, in basis points summing
+		///   to 10_000, or empty to pay the interest at once.
+		#[pallet::weight(T::WeightInfo::set_milestones(milestones.len() as u32))]
+		pub fn set_milestones(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			milestones: Vec<u32>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			Self::try_set_milestones(&who, poll_id, milestones.clone())?;
+			// Emit an event.
+			Self::deposit_event(Event::MilestonesSet { poll_id, milestones });
+			Ok(())
+		}
+
+		/// Start the sign-off of the next milestone of a finished poll.
+		///
+		/// Voters of the winning options can approve or reject the milestone with `sign_off`
+		/// during `SignOffPeriod` blocks.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the creator of the poll, or by any
+		/// account once the deadline of the milestone has passed, so the escrow is never held by
+		/// an inactive creator.
+		///
+		/// - `poll_id`: The index of the poll.
+		#[pallet::weight(T::WeightInfo::request_sign_off())]
+		pub fn request_sign_off(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			let (milestone, end) = Self::try_request_sign_off(&who, poll_id)?;
+			// Emit an event.
+			Self::deposit_event(Event::SignOffStarted { poll_id, milestone, end });
+			Ok(())
+		}
+
+		/// Approve or reject the milestone whose sign-off is in progress.
+		///
+		/// The decision is weighted by the stake of the voter on the winning options.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll.
+		/// - `approve`: Whether the milestone is approved or rejected.
+		#[pallet::weight(T::WeightInfo::sign_off())]
+		pub fn sign_off(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			approve: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			let (milestone, stake) = Self::try_sign_off(&who, poll_id, approve)?;
+			// Emit an event.
+			Self::deposit_event(Event::SignedOff {
+				voter: who,
+				poll_id,
+				milestone,
+				approve,
+				stake,
+			});
+			Ok(())
+		}
+
+		/// Close the sign-off of a milestone after its end.
+		///
+		/// The milestone is approved if the stake of approvals is higher than the stake of
+		/// rejections, otherwise the milestone is rejected.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll.
+		#[pallet::weight(T::WeightInfo::close_sign_off())]
+		pub fn close_sign_off(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			// Call inner function.
+			let (milestone, approved) = Self::try_close_sign_off(poll_id)?;
+			// Emit an event.
+			match approved {
+				true => Self::deposit_event(Event::MilestoneApproved { poll_id, milestone }),
+				false => Self::deposit_event(Event::MilestoneRejected { poll_id, milestone }),
+			}
+			Ok(())
+		}
+
+		/// Collect a vote stake or/and winning option from a poll.
+		///
+		/// This function will check if account is one of: in benefitiaries,
+		/// or is a voter (poll cancelled or his vote on poll option won/lost).
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll to collect.
+		#[pallet::weight(T::WeightInfo::collect(
+			T::MaxPollBeneficiaries::get(),
+			T::MaxPollOptions::get(),
+			T::MaxDelegators::get(),
+		))]
+		pub fn collect(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			let amount = Self::try_collect(&who, poll_id)?;
+			// Emit an event.
+			Self::deposit_event(Event::Collected { who, poll_id, amount });
+			Ok(())
+		}
+
+		/// Enact poll end.
+		///
+		/// The dispatch origin of this call must be _ROOT_.
+		///
+		/// - `poll_id`: The index of the poll to enact end.
+		#[pallet::weight(T::WeightInfo::enact_poll_end(
+			T::MaxPollOptions::get(),
+			T::MaxRankings::get(),
+		))]
+		pub fn enact_poll_end(origin: OriginFor<T>, poll_id: T::PollIndex) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::do_enact_poll_end(poll_id)?;
+			// Emit an event.
+			Self::deposit_event(Event::Finished { poll_id });
+			Ok(())
+		}
+
+		/// Queue an ended poll to be reaped, removing its storage in the idle time of blocks.
+		///
+		/// Remaining votes, commitments and interest are paid out as on collect, and funds left
+		/// in the pot by the poll go to the `Dust` or `AssetDust` handler. The deposit of the
+		/// creator is returned, and only the summary of the poll is kept.
+		///
+		/// Can be called once all votes of the poll are settled, its interest is vested and its
+		/// escrow settled, and its beneficiaries collected their interest, or the reap delay has
+		/// passed since its end.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll to reap.
+		#[pallet::weight(T::WeightInfo::reap_poll())]
+		pub fn reap_poll(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			// Call inner function.
+			Self::try_reap_poll(poll_id)?;
+			// Emit an event.
+			Self::deposit_event(Event::ReapQueued { poll_id });
+			Ok(())
+		}
+
+		/// Cast a ballot in a poll of a kind other than `Plurality`.
+		///
+		/// The stake of the ballot is spread across options in the votes of origin, and is paid
+		/// as in `vote`. The ballot is added into the tally of the poll kind, which decides the
+		/// winner. Only one ballot can be cast, and it can be replaced after `remove_vote`. The
+		/// stake of a ranked ballot should be at least `MinRankingStake`.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_id`: The index of the poll to cast the ballot in.
+		/// - `ballot`: The ballot of the poll kind, with its stake.
+		#[pallet::weight(T::WeightInfo::cast_ballot(
+			T::MaxPollOptions::get(),
+			T::MaxDelegators::get(),
+		))]
+		pub fn cast_ballot(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_id: T::PollIndex,
+			ballot: BallotOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Call inner function.
+			let votes = Self::try_cast_ballot(&who, poll_id, ballot.clone())?;
+			// Emit an event.
+			Self::deposit_event(Event::BallotCast { voter: who, poll_id, ballot, votes });
+			Ok(())
+		}
+	}
+}
+
+impl<T> From<CidError> for Error<T> {
+	fn from(error: CidError) -> Self {
+		match error {
+			CidError::InvalidEncoding => Error::<T>::InvalidCidEncoding,
+			CidError::UnsupportedVersion => Error::<T>::UnsupportedCidVersion,
+			CidError::InvalidMultihash => Error::<T>::InvalidCidMultihash,
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account ID of the faterium polls pot, which held funds of all polls before each poll
+	/// got a pot of its own.
+	///
+	/// This actually does computation. If you need to keep using it, then make sure you cache the
+	/// value and only call this once.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The account ID of the pot of the poll, which holds its stakes and reward pool.
+	///
+	/// The account is kept alive from the creation of the poll until it's reaped.
+	pub fn pot_account_id(poll_id: T::PollIndex) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(poll_id)
+	}
+
+	/// Return the amount of money in the balances pot of the poll.
+	pub fn balances_pot(poll_id: T::PollIndex) -> BalanceOf<T> {
+		T::Currency::free_balance(&Self::pot_account_id(poll_id))
+	}
+
+	/// Return the amount of money in the asset pot of the poll by asset_id.
+	pub fn asset_pot(poll_id: T::PollIndex, asset_id: AssetIdOf<T>) -> BalanceOf<T> {
+		<T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, &Self::pot_account_id(poll_id))
+			.into()
+	}
+
+	/// Returns Ok(PollDetails) if the given poll.status is Ongoing,
+	/// Error::PollInvalid or Error::PollAlreadyFinished otherwise.
+	fn poll_status(poll_id: T::PollIndex) -> Result<PollTypeOf<T>, DispatchError> {
+		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		match poll.status.is_ongoing() {
+			true => Ok(poll),
+			_ => Err(Error::<T>::PollAlreadyFinished.into()),
+		}
+	}
+
+	fn check_balance(
+		who: &T::AccountId,
+		currency: PollCurrency<AssetIdOf<T>>,
+		cap: BalanceOf<T>,
+	) -> bool {
+		match currency {
+			PollCurrency::Native => cap <= T::Currency::free_balance(who).into(),
+			PollCurrency::Asset(asset_id) =>
+				cap <= <T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, who).into(),
+		}
+	}
+
+	fn transfer_balance(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		currency: PollCurrency<AssetIdOf<T>>,
+		balance: BalanceOf<T>,
+	) -> DispatchResult {
+		match currency {
+			PollCurrency::Native => {
+				// TODO: Perhaps we want make some other function here to not pay fees.
+				T::Currency::transfer(source, dest, balance, ExistenceRequirement::AllowDeath)?;
+			},
+			PollCurrency::Asset(asset_id) => {
+				// TODO: Perhaps we want make something like `teleport` here to not pay fees.
 				<T::Fungibles as Transfer<T::AccountId>>::transfer(
 					asset_id, source, dest, balance, false,
 				)?;
@@ -415,8 +1403,625 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Actually create a poll.
-	fn try_create_poll(poll: PollTypeOf<T>) -> Result<T::PollIndex, DispatchError> {
+	/// Transfers the balance like `transfer_balance`, but never kills the source account.
+	fn transfer_balance_keep_alive(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		currency: PollCurrency<AssetIdOf<T>>,
+		balance: BalanceOf<T>,
+	) -> DispatchResult {
+		match currency {
+			PollCurrency::Native => {
+				T::Currency::transfer(source, dest, balance, ExistenceRequirement::KeepAlive)?;
+			},
+			PollCurrency::Asset(asset_id) => {
+				<T::Fungibles as Transfer<T::AccountId>>::transfer(
+					asset_id, source, dest, balance, true,
+				)?;
+			},
+		};
+		Ok(())
+	}
+
+	/// Returns true if the account can lock given amount on top of his already locked stakes.
+	fn check_lockable_balance(who: &T::AccountId, cap: BalanceOf<T>) -> bool {
+		LockedOf::<T>::get(who).saturating_add(cap) <= T::Currency::free_balance(who)
+	}
+
+	/// Sets the lock of the account to the given total amount, or removes it if zero.
+	fn set_locked(who: &T::AccountId, locked: BalanceOf<T>) {
+		if locked.is_zero() {
+			T::Currency::remove_lock(FATERIUM_POLLS_ID, who);
+			LockedOf::<T>::remove(who);
+		} else {
+			T::Currency::set_lock(FATERIUM_POLLS_ID, who, locked, WithdrawReasons::all());
+			LockedOf::<T>::insert(who, locked);
+		}
+	}
+
+	/// Locks the stake in the account, in addition to his already locked stakes.
+	fn lock_stake(who: &T::AccountId, amount: BalanceOf<T>) {
+		Self::set_locked(who, LockedOf::<T>::get(who).saturating_add(amount));
+	}
+
+	/// Unlocks the stake in the account.
+	fn unlock_stake(who: &T::AccountId, amount: BalanceOf<T>) {
+		Self::set_locked(who, LockedOf::<T>::get(who).saturating_sub(amount));
+	}
+
+	/// Decodes the IPFS CID and returns it in the binary CIDv1 form, if its content type and hash
+	/// function are allowed.
+	pub fn normalize_cid(ipfs_cid: &[u8]) -> Result<IpfsCid, DispatchError> {
+		// The longest supported form is base16, which takes two characters per byte.
+		let max_len = T::MaxIpfsCidLength::get().saturating_mul(2).saturating_add(1);
+		ensure!(ipfs_cid.len() as u32 <= max_len, Error::<T>::InvalidIpfsCid);
+		let cid = Cid::parse(ipfs_cid).map_err(Error::<T>::from)?;
+		ensure!(T::AllowedCidCodecs::contains(&cid.codec), Error::<T>::UnsupportedCidCodec);
+		ensure!(T::AllowedCidHashes::contains(&cid.hash), Error::<T>::UnsupportedCidHash);
+		Ok(cid.to_bytes())
+	}
+
+	/// Looks up accounts of the beneficiaries.
+	fn lookup_beneficiaries(
+		beneficiaries: BeneficiariesArgOf<T>,
+	) -> Result<BoundedVec<BeneficiaryOf<T>, T::MaxPollBeneficiaries>, DispatchError> {
+		let mut benfs = vec![];
+		for b in beneficiaries {
+			let account = T::Lookup::lookup(b.0)?;
+			benfs.push(Beneficiary::new(account, b.1, b.2));
+		}
+		benfs.try_into().map_err(|_| Error::<T>::TooManyBeneficiaries.into())
+	}
+
+	/// Returns the deposit required to create the given poll.
+	pub fn poll_deposit(poll: &PollTypeOf<T>) -> BalanceOf<T> {
+		let cid_len = BalanceOf::<T>::from(poll.ipfs_cid.len() as u32);
+		let bnfs_count = BalanceOf::<T>::from(poll.beneficiaries.len() as u32);
+		T::PollDepositBase::get()
+			.saturating_add(T::PollDepositPerByte::get().saturating_mul(cid_len))
+			.saturating_add(T::PollDepositPerBeneficiary::get().saturating_mul(bnfs_count))
+	}
+
+	/// Returns the deposit of the poll to its creator.
+	fn release_deposit(poll: &mut PollTypeOf<T>) {
+		T::Currency::unreserve(&poll.created_by, poll.deposit);
+		poll.deposit = Zero::zero();
+	}
+
+	/// Cancels the scheduled end of the poll and marks it as cancelled.
+	fn cancel_poll(poll_id: T::PollIndex, poll: &mut PollTypeOf<T>) -> DispatchResult {
+		T::Scheduler::cancel_named((FATERIUM_POLLS_ID, poll_id).encode())
+			.map_err(|_| Error::<T>::UnexpectedBehavior)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		poll.status = PollStatus::Cancelled(now);
+		Self::queue_settlement(poll_id);
+		Ok(())
+	}
+
+	/// Adds an ended poll to the settlement queue, if there is room for it. Votes of a poll which
+	/// doesn't fit the queue are paid out when collected, or when the poll is reaped, so ending a
+	/// poll never depends on the queue.
+	fn queue_settlement(poll_id: T::PollIndex) {
+		if SettlementQueue::<T>::try_append(poll_id).is_err() {
+			log::warn!(target: LOG_TARGET, "Settlement queue is full, poll {:?} isn't queued", poll_id);
+		}
+	}
+
+	/// Records the account as a participant of the poll, so it's visited by the settlement and
+	/// the reaping of the poll.
+	fn add_participant(who: &T::AccountId, poll_id: T::PollIndex) {
+		ParticipantsOf::<T>::insert(poll_id, who, ());
+	}
+
+	/// Pays out votes of ended polls from the settlement queue, within the given weight.
+	///
+	/// Participants of a poll are visited in the order of their storage keys, and the last
+	/// visited one is kept, so the settlement resumes from it in the next block. Once all
+	/// participants of a poll are visited, its beneficiaries and creator are paid out, and the
+	/// poll is removed from the queue.
+	///
+	/// Returns the consumed weight.
+	fn settle_polls(limit: Weight) -> Weight {
+		let db = T::DbWeight::get();
+		// Reads and writes of the queue and the cursor.
+		let mut used = db.reads_writes(2, 2);
+		if used > limit {
+			return Weight::zero()
+		}
+		let mut queue = SettlementQueue::<T>::get();
+		if queue.is_empty() {
+			return db.reads(1)
+		}
+		let vote_weight =
+			T::WeightInfo::collect(0, T::MaxPollOptions::get(), T::MaxDelegators::get());
+		let mut cursor = SettlementCursorOf::<T>::get();
+		while let Some(&poll_id) = queue.first() {
+			let settle = |who: &T::AccountId| Self::settle(who, poll_id);
+			match Self::visit_participants(
+				poll_id,
+				&mut cursor,
+				&mut used,
+				limit,
+				vote_weight,
+				settle,
+			) {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(_) => {
+					// Settlement can't be resumed, so the rest of the voters collect themselves.
+					log::warn!(target: LOG_TARGET, "Settlement of poll {:?} stopped", poll_id);
+					queue.remove(0);
+					cursor = None;
+					continue
+				},
+			}
+			// All votes were paid out, the rest of the poll is settled at once.
+			used = used.saturating_add(db.reads(1));
+			let settled = match PollDetailsOf::<T>::get(poll_id) {
+				Some(poll) => {
+					let count = poll.beneficiaries.len() as u32;
+					let poll_weight = T::WeightInfo::collect(count, T::MaxPollOptions::get(), 0)
+						.saturating_mul(1 + count as u64);
+					if used.saturating_add(poll_weight) > limit {
+						break
+					}
+					used = used.saturating_add(poll_weight);
+					for bnf in poll.beneficiaries.iter() {
+						Self::settle(&bnf.who, poll_id);
+					}
+					Self::settle(&poll.created_by, poll_id);
+					true
+				},
+				None => false,
+			};
+			queue.remove(0);
+			cursor = None;
+			if settled {
+				Self::deposit_event(Event::Settled { poll_id });
+			}
+		}
+		SettlementQueue::<T>::put(queue);
+		SettlementCursorOf::<T>::set(cursor);
+		used
+	}
+
+	/// Visits participants of the poll, starting after the cursor and within the weight limit.
+	/// `f` is called for every participant, and is expected to consume `weight`.
+	///
+	/// Returns whether all participants were visited. Otherwise the cursor points to the last
+	/// visited participant. Fails if the key of a participant doesn't fit the cursor, so the
+	/// visit can't be resumed.
+	fn visit_participants(
+		poll_id: T::PollIndex,
+		cursor: &mut Option<SettlementCursor>,
+		used: &mut Weight,
+		limit: Weight,
+		weight: Weight,
+		mut f: impl FnMut(&T::AccountId),
+	) -> Result<bool, DispatchError> {
+		let db = T::DbWeight::get();
+		let mut keys = match cursor {
+			Some(key) => ParticipantsOf::<T>::iter_key_prefix_from(poll_id, key.to_vec()),
+			None => ParticipantsOf::<T>::iter_key_prefix(poll_id),
+		};
+		loop {
+			if used.saturating_add(weight) > limit {
+				return Ok(false)
+			}
+			*used = used.saturating_add(db.reads(1));
+			match keys.next() {
+				Some(who) => {
+					let key = keys.last_raw_key().to_vec();
+					*cursor = Some(key.try_into().map_err(|_| Error::<T>::UnexpectedBehavior)?);
+					*used = used.saturating_add(weight);
+					f(&who);
+				},
+				None => return Ok(true),
+			}
+		}
+	}
+
+	/// Removes storage of polls from the reap queue, within the given weight.
+	///
+	/// Votes, unrevealed commitments and the allowlist of a poll are removed in phases, each
+	/// visiting participants of the poll, which are resumed from the last visited participant in
+	/// the next block, like the settlement. Then the rest of the poll is paid out, and its
+	/// details are replaced by a summary.
+	///
+	/// Returns the consumed weight.
+	fn reap_polls(limit: Weight) -> Weight {
+		let db = T::DbWeight::get();
+		// Reads and writes of the queue and the cursor.
+		let mut used = db.reads_writes(2, 2);
+		if used > limit {
+			return Weight::zero()
+		}
+		let mut queue = ReapQueue::<T>::get();
+		if queue.is_empty() {
+			return db.reads(1)
+		}
+		let vote_weight =
+			T::WeightInfo::collect(0, T::MaxPollOptions::get(), T::MaxDelegators::get())
+				.saturating_add(db.writes(4));
+		let commitment_weight = T::WeightInfo::refund_commitment().saturating_add(db.writes(1));
+		let (mut phase, mut cursor) = ReapCursorOf::<T>::get().unwrap_or((ReapPhase::Votes, None));
+		while let Some(&poll_id) = queue.first() {
+			let visited = match phase {
+				ReapPhase::Votes => {
+					let reap = |who: &T::AccountId| {
+						Self::settle(who, poll_id);
+						VotingOf::<T>::remove((who, poll_id));
+						BallotsOf::<T>::remove((who, poll_id));
+						EscrowParticipationOf::<T>::remove((who, poll_id));
+						DelegatedStakesOf::<T>::remove((who, poll_id));
+					};
+					Self::visit_participants(
+						poll_id,
+						&mut cursor,
+						&mut used,
+						limit,
+						vote_weight,
+						reap,
+					)
+				},
+				ReapPhase::Commitments => {
+					let reap = |who: &T::AccountId| {
+						Self::refund(who, poll_id);
+						CommitmentsOf::<T>::remove((who, poll_id));
+					};
+					Self::visit_participants(
+						poll_id,
+						&mut cursor,
+						&mut used,
+						limit,
+						commitment_weight,
+						reap,
+					)
+				},
+				ReapPhase::Allowlist => {
+					// Participants are visited for the last time.
+					let reap = |who: &T::AccountId| {
+						AllowlistOf::<T>::remove((who, poll_id));
+						ParticipantsOf::<T>::remove(poll_id, who);
+					};
+					Self::visit_participants(
+						poll_id,
+						&mut cursor,
+						&mut used,
+						limit,
+						db.writes(2),
+						reap,
+					)
+				},
+				ReapPhase::Archive => {
+					used = used.saturating_add(db.reads(1));
+					let count = PollDetailsOf::<T>::get(poll_id)
+						.map_or(0, |poll| poll.beneficiaries.len() as u32);
+					let poll_weight = T::WeightInfo::collect(count, T::MaxPollOptions::get(), 0)
+						.saturating_mul(1 + count as u64)
+						.saturating_add(db.reads_writes(1, 5));
+					let fits = used.saturating_add(poll_weight) <= limit;
+					if fits {
+						used = used.saturating_add(poll_weight);
+						let dust = Self::archive_poll(poll_id);
+						Self::deposit_event(Event::Reaped { poll_id, dust });
+					}
+					Ok(fits)
+				},
+			};
+			match visited {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(_) => {
+					// Reaping can't be resumed, so the poll is left as it is, and can be
+					// reaped again.
+					log::warn!(target: LOG_TARGET, "Reaping of poll {:?} stopped", poll_id);
+					queue.remove(0);
+					phase = ReapPhase::Votes;
+					cursor = None;
+					continue
+				},
+			}
+			cursor = None;
+			phase = match phase {
+				ReapPhase::Votes => ReapPhase::Commitments,
+				ReapPhase::Commitments => ReapPhase::Allowlist,
+				ReapPhase::Allowlist => ReapPhase::Archive,
+				ReapPhase::Archive => {
+					queue.remove(0);
+					ReapPhase::Votes
+				},
+			};
+		}
+		ReapQueue::<T>::put(queue);
+		match (phase, &cursor) {
+			(ReapPhase::Votes, None) => ReapCursorOf::<T>::kill(),
+			_ => ReapCursorOf::<T>::put((phase, cursor)),
+		}
+		used
+	}
+
+	/// Pays out beneficiaries and the creator of a reaped poll, takes funds it left in the pot,
+	/// and replaces its details with a summary.
+	///
+	/// Returns the amount of funds left in the pot.
+	fn archive_poll(poll_id: T::PollIndex) -> BalanceOf<T> {
+		if let Some(poll) = PollDetailsOf::<T>::get(poll_id) {
+			for bnf in poll.beneficiaries.iter() {
+				Self::settle(&bnf.who, poll_id);
+			}
+			Self::settle(&poll.created_by, poll_id);
+		}
+		// Details are read again, as collected interest was updated.
+		let mut poll = match PollDetailsOf::<T>::take(poll_id) {
+			Some(poll) => poll,
+			None => return Zero::zero(),
+		};
+		// Storage of the poll is removed, so the deposit for it is returned.
+		Self::release_deposit(&mut poll);
+		let dust = Self::take_dust(poll_id, poll.currency);
+		// Fails only if the pot still holds other assets, which are kept then.
+		let _ = frame_system::Pallet::<T>::dec_providers(&Self::pot_account_id(poll_id));
+		EscrowsOf::<T>::remove(poll_id);
+		TallyOf::<T>::remove(poll_id);
+		RankingsOf::<T>::remove(poll_id);
+		let winning_capital = poll.votes.capital_of(&poll.winning_options());
+		let summary = PollSummary {
+			created_by: poll.created_by,
+			ipfs_cid: poll.ipfs_cid,
+			currency: poll.currency,
+			status: poll.status,
+			capital: poll.votes.capital(),
+			winning_capital,
+			dust,
+		};
+		ArchiveOf::<T>::insert(poll_id, summary);
+		dust
+	}
+
+	/// Returns the interest of beneficiaries of an ended poll which they haven't collected yet,
+	/// and which isn't returned to voters.
+	fn leftover_of(poll_id: T::PollIndex, poll: &PollTypeOf<T>) -> BalanceOf<T> {
+		let win_opts = poll.winning_options();
+		// In lock voting mode the interest is paid by voters, so it's never in the pot.
+		if poll.voting_mode == VotingMode::Lock || win_opts.is_empty() {
+			return Zero::zero()
+		}
+		let winning_capital = poll.votes.capital_of(&win_opts);
+		// The rest of the rejected escrow is returned to voters.
+		let escrow = EscrowsOf::<T>::get(poll_id).filter(|escrow| escrow.rejected);
+		poll.beneficiaries.iter().fold(Zero::zero(), |sum, bnf| {
+			let interest = pro_rata(winning_capital, bnf.interest.into(), 10_000u32.into());
+			let owed = match &escrow {
+				Some(escrow) => escrow.released_of(interest, escrow.released),
+				None => interest,
+			};
+			owed.saturating_sub(bnf.collected).saturating_add(sum)
+		})
+	}
+
+	/// Returns the amount which the poll owes from its pot to its creator and beneficiaries:
+	/// the reward pool of an ongoing poll, or its unspent part and uncollected interest of an
+	/// ended one.
+	fn owed_by_poll(poll_id: T::PollIndex, poll: &PollTypeOf<T>) -> BalanceOf<T> {
+		if poll.status.is_ongoing() {
+			return match poll.reward_settings {
+				RewardSettings::CreatorPool(pool) => pool,
+				_ => Zero::zero(),
+			}
+		}
+		let refund = Self::claimable(&poll.created_by, poll_id)
+			.map_or(Zero::zero(), |claimable| claimable.creator_refund);
+		refund.saturating_add(Self::leftover_of(poll_id, poll))
+	}
+
+	/// Returns the amount which the poll owes from its pot to the voter: the stake in an ongoing
+	/// poll, or what the voter is left to collect from an ended one.
+	fn owed_to_voter(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		poll: &PollTypeOf<T>,
+	) -> BalanceOf<T> {
+		// In lock voting mode the stake is in the account of the voter, so it isn't in the pot.
+		let custodial = poll.voting_mode == VotingMode::Transfer;
+		if poll.status.is_ongoing() {
+			return match (custodial, VotingOf::<T>::get((who, poll_id))) {
+				(true, Some(voter)) => voter.votes.capital(),
+				_ => Zero::zero(),
+			}
+		}
+		match Self::claimable(who, poll_id) {
+			Ok(claimable) => {
+				let stake = if custodial { claimable.voter_return.capital() } else { Zero::zero() };
+				stake
+					.saturating_add(claimable.voter_reward)
+					.saturating_add(claimable.escrow_refund)
+			},
+			Err(_) => Zero::zero(),
+		}
+	}
+
+	/// Ensures invariants of the storage: pots of polls can afford what they owe, votes of
+	/// accounts add up to votes of their polls, ballots add up to tallies of their polls,
+	/// accounts with entries in a poll are its participants, and interest of beneficiaries of a
+	/// poll doesn't exceed 100%.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		use scale_info::prelude::collections::BTreeMap;
+		let mut votes = BTreeMap::<T::PollIndex, VotesOf<T>>::new();
+		let mut owed = BTreeMap::<T::PollIndex, BalanceOf<T>>::new();
+		for ((who, poll_id), voter) in VotingOf::<T>::iter() {
+			let poll =
+				PollDetailsOf::<T>::get(poll_id).ok_or("votes in a poll which doesn't exist")?;
+			ensure!(
+				ParticipantsOf::<T>::contains_key(poll_id, &who),
+				"account with votes in a poll isn't its participant",
+			);
+			votes
+				.entry(poll_id)
+				.or_insert_with(|| Votes::new(poll.options_count))
+				.add(&voter.votes)
+				.ok_or("votes of accounts in a poll are invalid")?;
+			let amount = Self::owed_to_voter(&who, poll_id, &poll);
+			let sum = owed.entry(poll_id).or_insert_with(Zero::zero);
+			*sum = sum.saturating_add(amount);
+		}
+		for ((who, poll_id), commitment) in CommitmentsOf::<T>::iter() {
+			let poll = PollDetailsOf::<T>::get(poll_id)
+				.ok_or("commitment to a poll which doesn't exist")?;
+			ensure!(
+				ParticipantsOf::<T>::contains_key(poll_id, &who),
+				"account with a commitment to a poll isn't its participant",
+			);
+			if poll.voting_mode == VotingMode::Transfer {
+				let sum = owed.entry(poll_id).or_insert_with(Zero::zero);
+				*sum = sum.saturating_add(commitment.stake);
+			}
+		}
+		for (who, poll_id) in AllowlistOf::<T>::iter_keys() {
+			ensure!(
+				ParticipantsOf::<T>::contains_key(poll_id, &who),
+				"allowed account of a poll isn't its participant",
+			);
+		}
+		let mut tallies = BTreeMap::<T::PollIndex, VotesOf<T>>::new();
+		let mut rankings = BTreeMap::<T::PollIndex, Vec<(Vec<u8>, BalanceOf<T>)>>::new();
+		for ((who, poll_id), ballot) in BallotsOf::<T>::iter() {
+			let poll =
+				PollDetailsOf::<T>::get(poll_id).ok_or("ballot in a poll which doesn't exist")?;
+			ensure!(
+				VotingOf::<T>::get((&who, poll_id)).map(|voter| voter.votes) ==
+					Some(ballot.to_votes(poll.options_count)),
+				"votes of an account don't match its ballot",
+			);
+			match &ballot {
+				Ballot::Ranked { ranking, stake } => {
+					let sums = rankings.entry(poll_id).or_default();
+					match sums.iter_mut().find(|(r, _)| r.as_slice() == ranking.as_slice()) {
+						Some((_, sum)) => *sum = sum.saturating_add(*stake),
+						None => sums.push((ranking.to_vec(), *stake)),
+					}
+				},
+				_ => {
+					let weights = ballot
+						.weights(poll.options_count)
+						.ok_or("ballots of accounts in a poll are invalid")?;
+					tallies
+						.entry(poll_id)
+						.or_insert_with(|| Votes::new(poll.options_count))
+						.add(&weights)
+						.ok_or("ballots of accounts in a poll are invalid")?;
+				},
+			}
+		}
+		let reaping = ReapQueue::<T>::get();
+		for (poll_id, poll) in PollDetailsOf::<T>::iter() {
+			ensure!(poll.beneficiary_sum() <= 10_000, "interest of beneficiaries exceeds 100%");
+			// Votes of a poll which is being reaped are removed before the poll.
+			if !reaping.contains(&poll_id) {
+				let sum = votes.remove(&poll_id).unwrap_or_else(|| Votes::new(poll.options_count));
+				ensure!(sum == poll.votes, "votes of accounts don't add up to votes of a poll");
+				let zero = || Votes::new(poll.options_count);
+				let tally = tallies.remove(&poll_id).unwrap_or_else(zero);
+				ensure!(
+					tally == TallyOf::<T>::get(poll_id).unwrap_or_else(zero),
+					"ballots of accounts don't add up to the tally of a poll",
+				);
+				let mut sums = rankings.remove(&poll_id).unwrap_or_default();
+				let mut stored: Vec<_> = RankingsOf::<T>::get(poll_id)
+					.into_iter()
+					.map(|(ranking, stake)| (ranking.into_inner(), stake))
+					.collect();
+				sums.sort();
+				stored.sort();
+				ensure!(sums == stored, "ballots of accounts don't add up to rankings of a poll");
+			}
+			let amount = owed
+				.remove(&poll_id)
+				.unwrap_or_else(Zero::zero)
+				.saturating_add(Self::owed_by_poll(poll_id, &poll));
+			ensure!(
+				Self::check_balance(&Self::pot_account_id(poll_id), poll.currency, amount),
+				"pot of a poll can't afford what it owes",
+			);
+		}
+		Ok(())
+	}
+
+	/// Takes funds left in the pot of a reaped poll, which empties the pot. Native currency goes
+	/// to the `Dust` handler, and assets go to the `AssetDust` handler.
+	///
+	/// Returns the taken amount.
+	fn take_dust(poll_id: T::PollIndex, currency: PollCurrency<AssetIdOf<T>>) -> BalanceOf<T> {
+		let pot = Self::pot_account_id(poll_id);
+		let taken = match currency {
+			PollCurrency::Native => {
+				let amount = T::Currency::free_balance(&pot);
+				if amount.is_zero() {
+					return Zero::zero()
+				}
+				let reasons = WithdrawReasons::TRANSFER;
+				T::Currency::withdraw(&pot, amount, reasons, ExistenceRequirement::AllowDeath)
+					.map(|imbalance| T::Dust::on_unbalanced(imbalance))
+					.map(|_| amount)
+			},
+			PollCurrency::Asset(asset_id) => {
+				let amount = <T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, &pot);
+				if amount.is_zero() {
+					return Zero::zero()
+				}
+				<T::Fungibles as Balanced<T::AccountId>>::withdraw(asset_id, &pot, amount)
+					.map(|credit| T::AssetDust::on_unbalanced(credit))
+					.map(|_| amount)
+			},
+		};
+		taken.unwrap_or_else(|_| {
+			log::warn!(target: LOG_TARGET, "Pot of the poll can't be emptied");
+			Zero::zero()
+		})
+	}
+
+	/// Refunds the unrevealed commitment of the account to a reaped poll, if it can be refunded.
+	/// Changes are reverted if the refund fails.
+	fn refund(who: &T::AccountId, poll_id: T::PollIndex) {
+		let result = with_transaction(|| match Self::try_refund_commitment(who, poll_id) {
+			Ok(refund) => TransactionOutcome::Commit(Ok(refund)),
+			Err(error) => TransactionOutcome::Rollback(Err(error)),
+		});
+		if let Ok((amount, penalty)) = result {
+			let voter = who.clone();
+			Self::deposit_event(Event::CommitmentRefunded { voter, poll_id, amount, penalty });
+		}
+	}
+
+	/// Collects for the account from an ended poll, if there is anything to collect. Changes are
+	/// reverted if the collection fails.
+	fn settle(who: &T::AccountId, poll_id: T::PollIndex) {
+		let result = with_transaction(|| match Self::try_collect(who, poll_id) {
+			Ok(amount) => TransactionOutcome::Commit(Ok(amount)),
+			Err(error) => TransactionOutcome::Rollback(Err(error)),
+		});
+		if let Ok(amount) = result {
+			Self::deposit_event(Event::Collected { who: who.clone(), poll_id, amount });
+		}
+	}
+
+	/// Ensures the poll fits the configured limits and can be started. Returns the block at which
+	/// the poll is settled, which is the end of the reveal period for sealed polls.
+	fn validate_poll(poll: &PollTypeOf<T>) -> Result<BlockNumberOf<T>, DispatchError> {
+		// Ensure poll fits the configured limits.
+		ensure!(poll.options_count >= 2, Error::<T>::TooFewPollOptions);
+		ensure!(
+			u32::from(poll.options_count) <= T::MaxPollOptions::get(),
+			Error::<T>::TooManyPollOptions,
+		);
+		ensure!(poll.goal >= T::MinPollGoal::get(), Error::<T>::PollGoalTooLow);
+		if let GoalMode::PerOption(goals) = &poll.goal_mode {
+			ensure!(
+				goals.iter().all(|goal| *goal >= T::MinPollGoal::get()),
+				Error::<T>::PollGoalTooLow,
+			);
+		}
 		// Validate poll details.
 		ensure!(poll.validate(), Error::<T>::InvalidPollDetails);
 		let (start, end) = match poll.status {
@@ -426,21 +2031,71 @@ impl<T: Config> Pallet<T> {
 		// Ensure start and end blocks are valid.
 		let now = <frame_system::Pallet<T>>::block_number();
 		ensure!(start >= now && end > now && end > start, Error::<T>::InvalidPollPeriod);
+		let duration = end.saturating_sub(start);
+		ensure!(duration >= T::MinPollDuration::get(), Error::<T>::PollTooShort);
+		ensure!(duration <= T::MaxPollDuration::get(), Error::<T>::PollTooLong);
 		// Ensure currency asset exists.
 		if let PollCurrency::Asset(asset_id) = poll.currency {
 			let total_issuance = <T::Fungibles as Inspect<T::AccountId>>::total_issuance(asset_id);
 			ensure!(total_issuance > BalanceOf::<T>::zero(), Error::<T>::InvalidPollCurrency);
 		}
+		// Only native currency can be locked.
+		if poll.voting_mode == VotingMode::Lock {
+			ensure!(poll.currency == PollCurrency::Native, Error::<T>::InvalidPollCurrency);
+		}
+		// Ensure the asset of holders allowed to vote exists.
+		if let Eligibility::AssetHolder { asset_id, .. } = poll.eligibility {
+			let total_issuance = <T::Fungibles as Inspect<T::AccountId>>::total_issuance(asset_id);
+			ensure!(total_issuance > BalanceOf::<T>::zero(), Error::<T>::InvalidPollDetails);
+		}
+		match poll.sealed {
+			Some(sealed) => {
+				ensure!(!sealed.reveal_period.is_zero(), Error::<T>::InvalidPollPeriod);
+				Ok(end.saturating_add(sealed.reveal_period))
+			},
+			None => Ok(end),
+		}
+	}
+
+	/// Transfers the reward pool of the poll, if any, from its creator to the pot of the poll.
+	fn transfer_reward_pool(poll_id: T::PollIndex, poll: &PollTypeOf<T>) -> DispatchResult {
+		if let RewardSettings::CreatorPool(pool) = poll.reward_settings {
+			ensure!(
+				Self::check_balance(&poll.created_by, poll.currency, pool),
+				Error::<T>::InsufficientFunds,
+			);
+			Self::transfer_balance(
+				&poll.created_by,
+				&Self::pot_account_id(poll_id),
+				poll.currency,
+				pool,
+			)?;
+		}
+		Ok(())
+	}
+
+	/// Actually create a poll.
+	fn try_create_poll(mut poll: PollTypeOf<T>) -> Result<T::PollIndex, DispatchError> {
+		let settlement = Self::validate_poll(&poll)?;
+		// Reserve the deposit from the creator.
+		let deposit = Self::poll_deposit(&poll);
+		T::Currency::reserve(&poll.created_by, deposit)
+			.map_err(|_| Error::<T>::InsufficientDeposit)?;
+		poll.deposit = deposit;
 		// Get next poll_id from storage.
 		let mut poll_id = PollCount::<T>::get();
 		poll_id.saturating_inc();
+		// Keep the pot of the poll alive, so it can hold assets until the poll is reaped.
+		frame_system::Pallet::<T>::inc_providers(&Self::pot_account_id(poll_id));
+		// Transfer reward pool from the creator to the pot of the poll.
+		Self::transfer_reward_pool(poll_id, &poll)?;
 		PollDetailsOf::<T>::insert(poll_id, poll);
 		// Updates poll count.
 		PollCount::<T>::put(poll_id);
 		// Actually schedule end of the poll.
 		if T::Scheduler::schedule_named(
 			(FATERIUM_POLLS_ID, poll_id).encode(),
-			DispatchTime::At(end),
+			DispatchTime::At(settlement),
 			None,
 			63,
 			frame_system::RawOrigin::Root.into(),
@@ -453,186 +2108,1185 @@ impl<T: Config> Pallet<T> {
 		Ok(poll_id)
 	}
 
+	/// Actually update a poll, if legit.
+	fn try_update_poll(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		cid: Option<IpfsCid>,
+		beneficiaries: Option<BoundedVec<BeneficiaryOf<T>, T::MaxPollBeneficiaries>>,
+		reward_settings: Option<RewardSettings<BalanceOf<T>>>,
+		goal: Option<BalanceOf<T>>,
+		end: Option<BlockNumberOf<T>>,
+	) -> DispatchResult {
+		let mut poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		// Check if origin is entitled to update the poll.
+		ensure!(poll.created_by.eq(who), Error::<T>::AccountNotAuthor);
+		let (start, old_end) = match poll.status {
+			PollStatus::Ongoing { start, end } => (start, end),
+			_ => return Err(Error::<T>::PollAlreadyFinished.into()),
+		};
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(now < start, Error::<T>::PollAlreadyStarted);
+		// Voters must not find the poll changed after they voted.
+		ensure!(poll.votes.capital().is_zero(), Error::<T>::PollHasVotes);
+		// Update and validate poll details.
+		let old_reward_settings = poll.reward_settings;
+		if let Some(cid) = cid {
+			poll.ipfs_cid = cid.try_into().map_err(|_| Error::<T>::InvalidIpfsCid)?;
+		}
+		if let Some(beneficiaries) = beneficiaries {
+			poll.beneficiaries = beneficiaries;
+		}
+		if let Some(reward_settings) = reward_settings {
+			poll.reward_settings = reward_settings;
+		}
+		if let Some(goal) = goal {
+			poll.goal = goal;
+		}
+		let end = end.unwrap_or(old_end);
+		poll.status = PollStatus::Ongoing { start, end };
+		let settlement = Self::validate_poll(&poll)?;
+		// Reserve or return the difference of the deposit.
+		let deposit = Self::poll_deposit(&poll);
+		if deposit > poll.deposit {
+			T::Currency::reserve(who, deposit.saturating_sub(poll.deposit))
+				.map_err(|_| Error::<T>::InsufficientDeposit)?;
+		} else {
+			T::Currency::unreserve(who, poll.deposit.saturating_sub(deposit));
+		}
+		poll.deposit = deposit;
+		// Return the previous reward pool and transfer the new one.
+		if poll.reward_settings != old_reward_settings {
+			if let RewardSettings::CreatorPool(pool) = old_reward_settings {
+				Self::transfer_balance(&Self::pot_account_id(poll_id), who, poll.currency, pool)?;
+			}
+			Self::transfer_reward_pool(poll_id, &poll)?;
+		}
+		// Reschedule the end of the poll.
+		if end != old_end {
+			T::Scheduler::reschedule_named(
+				(FATERIUM_POLLS_ID, poll_id).encode(),
+				DispatchTime::At(settlement),
+			)
+			.map_err(|_| Error::<T>::UnexpectedBehavior)?;
+		}
+		PollDetailsOf::<T>::insert(poll_id, poll);
+		Ok(())
+	}
+
 	fn try_emergency_cancel(who: &T::AccountId, poll_id: T::PollIndex) -> DispatchResult {
 		let mut poll = Self::poll_status(poll_id)?;
 		// Check if origin is entitled to cancel the poll.
 		ensure!(poll.created_by.eq(who), Error::<T>::AccountNotAuthor);
-		// Cancel dispatch.
-		T::Scheduler::cancel_named((FATERIUM_POLLS_ID, poll_id).encode())
-			.map_err(|_| Error::<T>::UnexpectedBehavior)?;
-		// Set status to Cancelled and update polls storage.
-		let now = <frame_system::Pallet<T>>::block_number();
-		poll.status = PollStatus::Cancelled(now);
+		// Cancel dispatch, the deposit is returned when the poll is reaped.
+		Self::cancel_poll(poll_id, &mut poll)?;
+		// Update polls storage.
+		PollDetailsOf::<T>::insert(poll_id, poll);
+		Ok(())
+	}
+
+	/// Returns whether the poll was cancelled, or only its deposit slashed.
+	fn try_force_cancel(poll_id: T::PollIndex) -> Result<bool, DispatchError> {
+		let mut poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		// Deposit of an ended poll is slashed as well, until it's returned on reap.
+		let cancel = poll.status.is_ongoing();
+		ensure!(cancel || !poll.deposit.is_zero(), Error::<T>::PollAlreadyFinished);
+		// Cancel dispatch and slash the deposit.
+		if cancel {
+			Self::cancel_poll(poll_id, &mut poll)?;
+		}
+		let (imbalance, _) = T::Currency::slash_reserved(&poll.created_by, poll.deposit);
+		T::Slashed::on_unbalanced(imbalance);
+		Self::deposit_event(Event::<T>::DepositSlashed {
+			poll_id,
+			who: poll.created_by.clone(),
+			amount: poll.deposit,
+		});
+		poll.deposit = Zero::zero();
+		// Update polls storage.
+		PollDetailsOf::<T>::insert(poll_id, poll);
+		Ok(cancel)
+	}
+
+	/// Actually enact a vote, if legit.
+	fn try_vote(who: &T::AccountId, poll_id: T::PollIndex, votes: VotesOf<T>) -> DispatchResult {
+		let mut poll = Self::poll_status(poll_id)?;
+		ensure!(poll.sealed.is_none(), Error::<T>::PollSealed);
+		ensure!(poll.kind == PollKind::Plurality, Error::<T>::InvalidPollKind);
+		ensure!(Self::is_eligible(who, poll_id, &poll), Error::<T>::NotEligible);
+		// Check if Votes has valid number of options.
+		ensure!(votes.validate(poll.options_count), Error::<T>::InvalidPollVotes);
+		// Check if Votes capital is more than zero.
+		let votes_capital = votes.capital();
+		ensure!(votes_capital > Zero::zero(), Error::<T>::InvalidPollVotes);
+		// Check if Multiple Votes are allowed.
+		ensure!(
+			(poll.multiple_votes && votes.non_zero_count() >= 1) ||
+				(!poll.multiple_votes && votes.non_zero_count() == 1),
+			Error::<T>::MultipleVotesNotAllowed,
+		);
+		if !poll.multiple_votes {
+			let voting = VotingOf::<T>::get((who, poll_id));
+			ensure!(voting.is_none(), Error::<T>::MultipleVotesNotAllowed);
+		}
+		// Ensure start and end blocks are valid.
+		if let PollStatus::Ongoing { start, .. } = poll.status {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(start <= now, Error::<T>::PollNotStarted);
+		}
+		Self::pay_stake(who, poll_id, &poll, votes_capital)?;
+		// Set or increase Votes on the poll.
+		Self::add_participant(who, poll_id);
+		VotingOf::<T>::try_mutate((who, poll_id), |voting| -> DispatchResult {
+			if let Some(v) = voting {
+				// Shouldn't be possible to fail, but we handle it gracefully.
+				v.votes.add(&votes).ok_or(ArithmeticError::Overflow)?;
+			} else {
+				*voting = Some(AccountVotes { votes: votes.clone(), collected: false });
+			}
+			// Shouldn't be possible to fail, but we handle it gracefully.
+			poll.votes.add(&votes).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+		// Update poll in storage.
+		PollDetailsOf::<T>::insert(poll_id, poll);
+		Ok(())
+	}
+
+	/// Actually cast a ballot in a poll, if legit. Returns the votes the stake is spread into.
+	fn try_cast_ballot(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		ballot: BallotOf<T>,
+	) -> Result<VotesOf<T>, DispatchError> {
+		let mut poll = Self::poll_status(poll_id)?;
+		ensure!(poll.kind != PollKind::Plurality, Error::<T>::InvalidPollKind);
+		ensure!(Self::is_eligible(who, poll_id, &poll), Error::<T>::NotEligible);
+		// Check if the ballot is of the poll kind, and its stake is more than zero.
+		ensure!(ballot.validate(poll.kind, poll.options_count), Error::<T>::InvalidBallot);
+		let stake = ballot.stake();
+		ensure!(stake > Zero::zero(), Error::<T>::InvalidBallot);
+		if let Ballot::Ranked { .. } = ballot {
+			ensure!(stake >= T::MinRankingStake::get(), Error::<T>::BallotStakeTooLow);
+		}
+		ensure!(!BallotsOf::<T>::contains_key((who, poll_id)), Error::<T>::BallotAlreadyCast);
+		// Ensure start and end blocks are valid.
+		if let PollStatus::Ongoing { start, .. } = poll.status {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(start <= now, Error::<T>::PollNotStarted);
+		}
+		Self::pay_stake(who, poll_id, &poll, stake)?;
+		// Add the ballot into the tally of the poll kind.
+		match &ballot {
+			Ballot::Ranked { ranking, .. } =>
+				RankingsOf::<T>::try_mutate(poll_id, |rankings| -> DispatchResult {
+					match rankings.iter_mut().find(|(r, _)| r == ranking) {
+						Some((_, s)) =>
+							*s = s.checked_add(&stake).ok_or(ArithmeticError::Overflow)?,
+						None => rankings
+							.try_push((ranking.clone(), stake))
+							.map_err(|_| Error::<T>::TooManyRankings)?,
+					}
+					Ok(())
+				})?,
+			_ => {
+				let weights =
+					ballot.weights(poll.options_count).ok_or(Error::<T>::UnexpectedBehavior)?;
+				TallyOf::<T>::try_mutate(poll_id, |tally| -> DispatchResult {
+					// Shouldn't be possible to fail, but we handle it gracefully.
+					tally
+						.get_or_insert_with(|| Votes::new(poll.options_count))
+						.add(&weights)
+						.ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+			},
+		}
+		// Spread the stake into votes of the account and of the poll.
+		let votes = ballot.to_votes(poll.options_count);
+		poll.votes.add(&votes).ok_or(ArithmeticError::Overflow)?;
+		Self::add_participant(who, poll_id);
+		VotingOf::<T>::insert(
+			(who, poll_id),
+			AccountVotes { votes: votes.clone(), collected: false },
+		);
+		BallotsOf::<T>::insert((who, poll_id), ballot);
+		// Update poll in storage.
 		PollDetailsOf::<T>::insert(poll_id, poll);
+		Ok(votes)
+	}
+
+	/// Pays the stake of votes in a poll, by transfer to the pot or lock in the account.
+	fn pay_stake(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		poll: &PollTypeOf<T>,
+		capital: BalanceOf<T>,
+	) -> DispatchResult {
+		match poll.voting_mode {
+			VotingMode::Transfer => {
+				// Spend own funds of origin first, and voting power delegated to origin only for
+				// the rest. Origin is kept alive if delegators pay a part of the stake.
+				let own = match Self::check_balance(who, poll.currency, capital) {
+					true => capital,
+					false => capital.min(Self::spendable_balance(who, poll.currency)),
+				};
+				let rest = capital.saturating_sub(own);
+				let delegated = Self::spend_delegated(who, poll_id, poll.currency, rest)?;
+				// Check if delegators afforded the rest.
+				ensure!(delegated == rest, Error::<T>::InsufficientFunds);
+				// Actually transfer balance to the pot.
+				if own > Zero::zero() {
+					Self::transfer_balance(
+						who,
+						&Self::pot_account_id(poll_id),
+						poll.currency,
+						own,
+					)?;
+				}
+			},
+			VotingMode::Lock => {
+				// Check if origin has enough funds, which are not locked in other polls.
+				ensure!(Self::check_lockable_balance(who, capital), Error::<T>::InsufficientFunds);
+				// Actually lock balance in the account.
+				Self::lock_stake(who, capital);
+			},
+		}
+		Ok(())
+	}
+
+	/// Removes the ballot of an account, if any, from the tally of the poll kind.
+	fn remove_ballot(who: &T::AccountId, poll_id: T::PollIndex) -> DispatchResult {
+		let ballot = match BallotsOf::<T>::take((who, poll_id)) {
+			Some(ballot) => ballot,
+			None => return Ok(()),
+		};
+		let stake = ballot.stake();
+		match &ballot {
+			Ballot::Ranked { ranking, .. } =>
+				RankingsOf::<T>::try_mutate(poll_id, |rankings| -> DispatchResult {
+					// Shouldn't be possible to fail, but we handle it gracefully.
+					let (_, s) = rankings
+						.iter_mut()
+						.find(|(r, _)| r == ranking)
+						.ok_or(Error::<T>::UnexpectedBehavior)?;
+					*s = s.checked_sub(&stake).ok_or(ArithmeticError::Underflow)?;
+					rankings.retain(|(_, s)| !s.is_zero());
+					Ok(())
+				}),
+			_ => TallyOf::<T>::try_mutate(poll_id, |tally| -> DispatchResult {
+				// Shouldn't be possible to fail, but we handle it gracefully.
+				let weights = tally
+					.as_ref()
+					.and_then(|tally| ballot.weights(tally.0.len() as u8))
+					.ok_or(Error::<T>::UnexpectedBehavior)?;
+				tally
+					.as_mut()
+					.and_then(|tally| tally.remove(&weights))
+					.ok_or(ArithmeticError::Underflow)?;
+				Ok(())
+			}),
+		}
+	}
+
+	/// Actually remove a vote from a poll, if legit.
+	fn try_remove_vote(who: &T::AccountId, poll_id: T::PollIndex) -> DispatchResult {
+		let poll = Self::poll_status(poll_id)?;
+		ensure!(poll.sealed.is_none(), Error::<T>::PollSealed);
+		// Get account votes.
+		let voter = VotingOf::<T>::get((who, poll_id)).ok_or(Error::<T>::VotesNotExist)?;
+		// Check if pot has enough funds.
+		if poll.voting_mode == VotingMode::Transfer {
+			ensure!(
+				Self::check_balance(
+					&Self::pot_account_id(poll_id),
+					poll.currency,
+					voter.votes.capital(),
+				),
+				Error::<T>::PotInsufficientFunds,
+			);
+		}
+		// Actually remove the vote, and the ballot it was cast with.
+		VotingOf::<T>::remove((who, poll_id));
+		Self::remove_ballot(who, poll_id)?;
+		// Decrease Votes on the poll.
+		PollDetailsOf::<T>::try_mutate(poll_id, |poll| -> DispatchResult {
+			// Shouldn't be possible to fail, but we handle it gracefully.
+			poll.as_mut()
+				.ok_or(Error::<T>::UnexpectedBehavior)?
+				.votes
+				.remove(&voter.votes)
+				.ok_or(ArithmeticError::Underflow)?;
+			Ok(())
+		})?;
+		let capital = voter.votes.capital();
+		match poll.voting_mode {
+			// Actually transfer balance from the pot to delegators and account.
+			VotingMode::Transfer => {
+				let paid =
+					Self::pay_delegators(who, poll_id, poll.currency, capital, capital, capital)?;
+				if capital > paid {
+					Self::transfer_balance(
+						&Self::pot_account_id(poll_id),
+						who,
+						poll.currency,
+						capital.saturating_sub(paid),
+					)?;
+				}
+			},
+			// Actually unlock balance in the account.
+			VotingMode::Lock => Self::unlock_stake(who, capital),
+		}
+		Ok(())
+	}
+
+	/// Actually withdraw a part of votes from a poll, if legit.
+	fn try_withdraw_votes(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		votes: &VotesOf<T>,
+	) -> DispatchResult {
+		let mut poll = Self::poll_status(poll_id)?;
+		ensure!(poll.sealed.is_none(), Error::<T>::PollSealed);
+		ensure!(poll.kind == PollKind::Plurality, Error::<T>::InvalidPollKind);
+		// Check if Votes has valid number of options and capital is more than zero.
+		ensure!(votes.validate(poll.options_count), Error::<T>::InvalidPollVotes);
+		let capital = votes.capital();
+		ensure!(capital > Zero::zero(), Error::<T>::InvalidPollVotes);
+		// Decrease account votes, which should cover withdrawn ones.
+		let mut voter = VotingOf::<T>::get((who, poll_id)).ok_or(Error::<T>::VotesNotExist)?;
+		let voter_capital = voter.votes.capital();
+		voter.votes.remove(votes).ok_or(Error::<T>::InsufficientVotes)?;
+		// Check if pot has enough funds.
+		if poll.voting_mode == VotingMode::Transfer {
+			ensure!(
+				Self::check_balance(&Self::pot_account_id(poll_id), poll.currency, capital),
+				Error::<T>::PotInsufficientFunds,
+			);
+		}
+		// Decrease Votes on the poll, shouldn't be possible to fail.
+		poll.votes.remove(votes).ok_or(ArithmeticError::Underflow)?;
+		PollDetailsOf::<T>::insert(poll_id, &poll);
+		// Remove account votes if nothing is left.
+		if voter.votes.capital().is_zero() {
+			VotingOf::<T>::remove((who, poll_id));
+		} else {
+			VotingOf::<T>::insert((who, poll_id), &voter);
+		}
+		match poll.voting_mode {
+			// Actually transfer balance from the pot to delegators and account.
+			VotingMode::Transfer => {
+				let paid = Self::pay_delegators(
+					who,
+					poll_id,
+					poll.currency,
+					capital,
+					capital,
+					voter_capital,
+				)?;
+				if capital > paid {
+					Self::transfer_balance(
+						&Self::pot_account_id(poll_id),
+						who,
+						poll.currency,
+						capital.saturating_sub(paid),
+					)?;
+				}
+			},
+			// Actually unlock balance in the account.
+			VotingMode::Lock => Self::unlock_stake(who, capital),
+		}
 		Ok(())
 	}
 
-	/// Actually enact a vote, if legit.
-	fn try_vote(
+	/// Actually move a part of votes between poll options, if legit.
+	fn try_move_votes(
 		who: &T::AccountId,
 		poll_id: T::PollIndex,
-		votes: Votes<BalanceOf<T>>,
+		from: u8,
+		to: u8,
+		amount: BalanceOf<T>,
 	) -> DispatchResult {
 		let mut poll = Self::poll_status(poll_id)?;
-		// Check if Votes has valid number of options.
-		ensure!(votes.validate(poll.options_count), Error::<T>::InvalidPollVotes);
-		// Check if Votes capital is more than zero.
-		let votes_capital = votes.capital();
-		ensure!(votes_capital > Zero::zero(), Error::<T>::InvalidPollVotes);
-		// Check if Multiple Votes are allowed.
+		ensure!(poll.sealed.is_none(), Error::<T>::PollSealed);
+		ensure!(poll.kind == PollKind::Plurality, Error::<T>::InvalidPollKind);
+		ensure!(poll.movable_votes, Error::<T>::VoteMovesNotAllowed);
+		// Check if options are valid and distinct, and amount is more than zero.
 		ensure!(
-			(poll.multiple_votes && votes.non_zero_count() >= 1) ||
-				(!poll.multiple_votes && votes.non_zero_count() == 1),
+			from != to && from < poll.options_count && to < poll.options_count,
+			Error::<T>::InvalidPollVotes,
+		);
+		ensure!(amount > Zero::zero(), Error::<T>::InvalidPollVotes);
+		// Move account votes, which should cover moved ones.
+		let mut voter = VotingOf::<T>::get((who, poll_id)).ok_or(Error::<T>::VotesNotExist)?;
+		voter.votes.move_stake(from, to, amount).ok_or(Error::<T>::InsufficientVotes)?;
+		// Without multiple votes, the stake can't be split between options.
+		ensure!(
+			poll.multiple_votes || voter.votes.non_zero_count() == 1,
 			Error::<T>::MultipleVotesNotAllowed,
 		);
-		if !poll.multiple_votes {
-			let voting = VotingOf::<T>::get((who, poll_id));
-			ensure!(voting.is_none(), Error::<T>::MultipleVotesNotAllowed);
+		// Move Votes on the poll, shouldn't be possible to fail.
+		poll.votes.move_stake(from, to, amount).ok_or(ArithmeticError::Underflow)?;
+		PollDetailsOf::<T>::insert(poll_id, &poll);
+		VotingOf::<T>::insert((who, poll_id), &voter);
+		Ok(())
+	}
+
+	/// Returns whether the account satisfies the eligibility rule of the poll.
+	fn is_eligible(who: &T::AccountId, poll_id: T::PollIndex, poll: &PollTypeOf<T>) -> bool {
+		match poll.eligibility {
+			Eligibility::Anyone => true,
+			Eligibility::Allowlist | Eligibility::MerkleRoot(_) =>
+				AllowlistOf::<T>::contains_key((who, poll_id)),
+			Eligibility::AssetHolder { asset_id, min_balance } =>
+				<T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, who) >= min_balance,
+			Eligibility::CollectionMember(collection) =>
+				T::Collections::contains(&(collection, who.clone())),
 		}
-		// Ensure start and end blocks are valid.
-		if let PollStatus::Ongoing { start, .. } = poll.status {
+	}
+
+	/// Returns the root of the Merkle tree with the leaf of the account and the proof.
+	pub fn merkle_root(who: &T::AccountId, proof: &[H256]) -> H256 {
+		proof.iter().fold(H256(who.using_encoded(blake2_256)), |node, sibling| {
+			let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+			H256(blake2_256(&[left.as_bytes(), right.as_bytes()].concat()))
+		})
+	}
+
+	/// Actually update the allowlist of a poll, if legit.
+	fn try_update_allowlist(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		accounts: &[T::AccountId],
+		allowed: bool,
+	) -> DispatchResult {
+		let poll = Self::poll_status(poll_id)?;
+		ensure!(poll.created_by.eq(who), Error::<T>::AccountNotAuthor);
+		ensure!(poll.eligibility == Eligibility::Allowlist, Error::<T>::InvalidEligibilityRule);
+		for account in accounts {
+			match allowed {
+				true => {
+					Self::add_participant(account, poll_id);
+					AllowlistOf::<T>::insert((account, poll_id), ());
+				},
+				// The account is kept as a participant, as it may have voted.
+				false => AllowlistOf::<T>::remove((account, poll_id)),
+			}
+		}
+		Ok(())
+	}
+
+	/// Actually prove that the account is in the Merkle tree of a poll, if legit.
+	fn try_prove_eligibility(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		proof: &[H256],
+	) -> DispatchResult {
+		let poll = Self::poll_status(poll_id)?;
+		let root = match poll.eligibility {
+			Eligibility::MerkleRoot(root) => root,
+			_ => return Err(Error::<T>::InvalidEligibilityRule.into()),
+		};
+		ensure!(Self::merkle_root(who, proof) == root, Error::<T>::InvalidMerkleProof);
+		Self::add_participant(who, poll_id);
+		AllowlistOf::<T>::insert((who, poll_id), ());
+		Ok(())
+	}
+
+	/// Actually commit votes to a sealed poll, if legit.
+	fn try_commit_votes(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		hash: T::Hash,
+		stake: BalanceOf<T>,
+	) -> DispatchResult {
+		let poll = Self::poll_status(poll_id)?;
+		ensure!(poll.sealed.is_some(), Error::<T>::PollNotSealed);
+		ensure!(Self::is_eligible(who, poll_id, &poll), Error::<T>::NotEligible);
+		// Votes can be committed only once, between the start and the end of the poll.
+		if let PollStatus::Ongoing { start, end } = poll.status {
 			let now = <frame_system::Pallet<T>>::block_number();
 			ensure!(start <= now, Error::<T>::PollNotStarted);
+			ensure!(now < end, Error::<T>::CommitPeriodEnded);
 		}
-		// Check if origin has enough funds.
+		ensure!(stake > Zero::zero(), Error::<T>::InvalidPollVotes);
 		ensure!(
-			Self::check_balance(who, poll.currency, votes_capital),
-			Error::<T>::InsufficientFunds,
+			!CommitmentsOf::<T>::contains_key((who, poll_id)),
+			Error::<T>::CommitmentAlreadyExists,
 		);
-		// Actually transfer balance to the pot.
-		Self::transfer_balance(who, &Self::account_id(), poll.currency, votes_capital)?;
-		// Set or increase Votes on the poll.
-		VotingOf::<T>::try_mutate((who, poll_id), |voting| -> DispatchResult {
-			if let Some(v) = voting {
-				// Shouldn't be possible to fail, but we handle it gracefully.
-				v.votes.add(&votes).ok_or(ArithmeticError::Overflow)?;
-			} else {
-				*voting = Some(AccountVotes { votes: votes.clone(), collected: false });
-			}
-			// Shouldn't be possible to fail, but we handle it gracefully.
-			poll.votes.add(&votes).ok_or(ArithmeticError::Overflow)?;
-			Ok(())
-		})?;
-		// Update poll in storage.
-		PollDetailsOf::<T>::insert(poll_id, poll);
+		match poll.voting_mode {
+			VotingMode::Transfer => {
+				ensure!(
+					Self::check_balance(who, poll.currency, stake),
+					Error::<T>::InsufficientFunds,
+				);
+				Self::transfer_balance(who, &Self::pot_account_id(poll_id), poll.currency, stake)?;
+			},
+			VotingMode::Lock => {
+				ensure!(Self::check_lockable_balance(who, stake), Error::<T>::InsufficientFunds);
+				Self::lock_stake(who, stake);
+			},
+		}
+		Self::add_participant(who, poll_id);
+		CommitmentsOf::<T>::insert((who, poll_id), Commitment { hash, stake });
 		Ok(())
 	}
 
-	/// Actually remove a vote from a poll, if legit.
-	fn try_remove_vote(who: &T::AccountId, poll_id: T::PollIndex) -> DispatchResult {
-		let poll = Self::poll_status(poll_id)?;
-		// Get account votes.
-		let voter = VotingOf::<T>::get((who, poll_id)).ok_or(Error::<T>::VotesNotExist)?;
-		// Check if pot has enough funds.
+	/// Actually reveal votes committed to a sealed poll, if legit.
+	fn try_reveal_votes(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		votes: VotesOf<T>,
+		salt: [u8; 32],
+	) -> DispatchResult {
+		let mut poll = Self::poll_status(poll_id)?;
+		ensure!(poll.sealed.is_some(), Error::<T>::PollNotSealed);
+		if let PollStatus::Ongoing { end, .. } = poll.status {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(end <= now, Error::<T>::RevealPeriodNotStarted);
+		}
+		let commitment =
+			CommitmentsOf::<T>::get((who, poll_id)).ok_or(Error::<T>::CommitmentNotExist)?;
 		ensure!(
-			Self::check_balance(who, poll.currency, voter.votes.capital()),
-			Error::<T>::PotInsufficientFunds,
+			T::Hashing::hash_of(&(&votes, salt)) == commitment.hash &&
+				votes.capital() == commitment.stake,
+			Error::<T>::CommitmentMismatch,
 		);
-		// Actually remove the vote.
-		VotingOf::<T>::remove((who, poll_id));
-		// Decrease Votes on the poll.
-		PollDetailsOf::<T>::try_mutate(poll_id, |poll| -> DispatchResult {
-			// Shouldn't be possible to fail, but we handle it gracefully.
-			poll.as_mut()
-				.ok_or(Error::<T>::UnexpectedBehavior)?
-				.votes
-				.remove(&voter.votes)
-				.ok_or(ArithmeticError::Underflow)?;
+		// Committed votes should be valid as usual.
+		ensure!(votes.validate(poll.options_count), Error::<T>::InvalidPollVotes);
+		ensure!(
+			poll.multiple_votes || votes.non_zero_count() == 1,
+			Error::<T>::MultipleVotesNotAllowed,
+		);
+		// Shouldn't be possible to fail, but we handle it gracefully.
+		poll.votes.add(&votes).ok_or(ArithmeticError::Overflow)?;
+		CommitmentsOf::<T>::remove((who, poll_id));
+		VotingOf::<T>::insert((who, poll_id), AccountVotes { votes, collected: false });
+		PollDetailsOf::<T>::insert(poll_id, poll);
+		Ok(())
+	}
+
+	/// Actually refund the stake of votes which were not revealed, if legit.
+	///
+	/// Returns the refunded amount and the slashed penalty.
+	fn try_refund_commitment(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		ensure!(!poll.status.is_ongoing(), Error::<T>::CollectOnOngoingPoll);
+		let commitment =
+			CommitmentsOf::<T>::take((who, poll_id)).ok_or(Error::<T>::CommitmentNotExist)?;
+		// Voters aren't penalized if the poll was cancelled before they could reveal.
+		let penalty = match (poll.sealed, &poll.status) {
+			(_, PollStatus::Cancelled(_)) | (None, _) => Zero::zero(),
+			(Some(sealed), _) =>
+				pro_rata(commitment.stake, sealed.unrevealed_penalty.into(), 10_000u32.into()),
+		};
+		let amount = commitment.stake.saturating_sub(penalty);
+		match poll.voting_mode {
+			VotingMode::Transfer => {
+				ensure!(
+					Self::check_balance(
+						&Self::pot_account_id(poll_id),
+						poll.currency,
+						commitment.stake
+					),
+					Error::<T>::PotInsufficientFunds,
+				);
+				if amount > Zero::zero() {
+					Self::transfer_balance(
+						&Self::pot_account_id(poll_id),
+						who,
+						poll.currency,
+						amount,
+					)?;
+				}
+				Self::slash_balance(&Self::pot_account_id(poll_id), poll.currency, penalty);
+			},
+			VotingMode::Lock => {
+				Self::unlock_stake(who, commitment.stake);
+				Self::slash_balance(who, poll.currency, penalty);
+			},
+		}
+		Ok((amount, penalty))
+	}
+
+	/// Slashes the amount from the account. Native currency goes to the `Slashed` handler, and
+	/// assets are burned.
+	fn slash_balance(
+		who: &T::AccountId,
+		currency: PollCurrency<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+	) {
+		if amount.is_zero() {
+			return
+		}
+		match currency {
+			PollCurrency::Native => {
+				let (imbalance, _) = T::Currency::slash(who, amount);
+				T::Slashed::on_unbalanced(imbalance);
+			},
+			PollCurrency::Asset(asset_id) => {
+				let _ = <T::Fungibles as Balanced<T::AccountId>>::slash(asset_id, who, amount);
+			},
+		}
+	}
+
+	/// Actually delegate voting power to another account, if legit.
+	fn try_delegate(
+		who: &T::AccountId,
+		to: &T::AccountId,
+		currency: PollCurrency<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(who != to && amount > Zero::zero(), Error::<T>::InvalidDelegation);
+		DelegationsOf::<T>::try_mutate((to, currency), |delegators| -> DispatchResult {
+			match delegators.iter_mut().find(|(delegator, _)| delegator == who) {
+				Some((_, allowance)) => *allowance = amount,
+				None => delegators
+					.try_push((who.clone(), amount))
+					.map_err(|_| Error::<T>::TooManyDelegators)?,
+			}
 			Ok(())
-		})?;
-		// Actually transfer balance from the pot to account.
-		Self::transfer_balance(&Self::account_id(), who, poll.currency, voter.votes.capital())?;
+		})
+	}
+
+	/// Actually revoke voting power delegated to another account, if legit.
+	fn try_undelegate(
+		who: &T::AccountId,
+		to: &T::AccountId,
+		currency: PollCurrency<AssetIdOf<T>>,
+	) -> DispatchResult {
+		let mut delegators = DelegationsOf::<T>::get((to, currency));
+		let count = delegators.len();
+		delegators.retain(|(delegator, _)| delegator != who);
+		ensure!(delegators.len() < count, Error::<T>::DelegationNotExist);
+		if delegators.is_empty() {
+			DelegationsOf::<T>::remove((to, currency));
+		} else {
+			DelegationsOf::<T>::insert((to, currency), delegators);
+		}
 		Ok(())
 	}
 
-	/// Actually collect a vote or winning option, if the account is legit.
-	fn try_collect(
+	/// Returns the balance which the account can spend in the currency, keeping it alive. Frozen
+	/// funds, like stakes locked in polls, can't be spent.
+	fn spendable_balance(who: &T::AccountId, currency: PollCurrency<AssetIdOf<T>>) -> BalanceOf<T> {
+		match currency {
+			PollCurrency::Native =>
+				<T::Currency as fungible::Inspect<T::AccountId>>::reducible_balance(who, true),
+			PollCurrency::Asset(asset_id) =>
+				<T::Fungibles as Inspect<T::AccountId>>::reducible_balance(asset_id, who, true),
+		}
+	}
+
+	/// Spends voting power delegated to the voter on his votes in the poll, by transferring funds
+	/// of delegators to the pot, pro rata to what each of them can spend. Delegators are kept
+	/// alive.
+	///
+	/// Returns the spent amount, which is less than the given one if delegators can't afford it.
+	fn spend_delegated(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		currency: PollCurrency<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut delegators = DelegationsOf::<T>::get((who, currency));
+		// Delegators can't spend more than they allowed, or than they have.
+		let available: Vec<BalanceOf<T>> = delegators
+			.iter()
+			.map(|(delegator, allowance)| {
+				(*allowance).min(Self::spendable_balance(delegator, currency))
+			})
+			.collect();
+		let total = available.iter().fold(BalanceOf::<T>::zero(), |a, b| a.saturating_add(*b));
+		let spent = amount.min(total);
+		if spent.is_zero() {
+			return Ok(spent)
+		}
+		let mut shares: Vec<BalanceOf<T>> =
+			available.iter().map(|a| pro_rata(spent, *a, total)).collect();
+		// Rounding dust is spent from the first delegators who can afford it.
+		let mut dust = shares.iter().fold(spent, |a, b| a.saturating_sub(*b));
+		for (share, available) in shares.iter_mut().zip(available.iter()) {
+			let extra = dust.min(available.saturating_sub(*share));
+			*share = share.saturating_add(extra);
+			dust = dust.saturating_sub(extra);
+		}
+		let mut stakes = DelegatedStakesOf::<T>::get((who, poll_id));
+		for ((delegator, allowance), share) in delegators.iter_mut().zip(shares) {
+			if share.is_zero() {
+				continue
+			}
+			let pot = Self::pot_account_id(poll_id);
+			Self::transfer_balance_keep_alive(delegator, &pot, currency, share)?;
+			*allowance = allowance.saturating_sub(share);
+			match stakes.iter_mut().find(|(d, _)| d == delegator) {
+				Some((_, stake)) => *stake = stake.saturating_add(share),
+				None => stakes
+					.try_push((delegator.clone(), share))
+					.map_err(|_| Error::<T>::TooManyDelegators)?,
+			}
+		}
+		// Delegations which are spent entirely are removed.
+		delegators.retain(|(_, allowance)| !allowance.is_zero());
+		if delegators.is_empty() {
+			DelegationsOf::<T>::remove((who, currency));
+		} else {
+			DelegationsOf::<T>::insert((who, currency), delegators);
+		}
+		DelegatedStakesOf::<T>::insert((who, poll_id), stakes);
+		Ok(spent)
+	}
+
+	/// Pays delegators of the voter their share of the amount from the pot, pro rata to their
+	/// stakes in the votes of the voter with the given capital, and reduces their stakes by their
+	/// share of the withdrawn capital.
+	///
+	/// Returns the paid amount, the rest of the amount belongs to the voter.
+	fn pay_delegators(
 		who: &T::AccountId,
 		poll_id: T::PollIndex,
+		currency: PollCurrency<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+		withdrawn: BalanceOf<T>,
+		capital: BalanceOf<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut stakes = DelegatedStakesOf::<T>::get((who, poll_id));
+		let mut paid = BalanceOf::<T>::zero();
+		for (delegator, stake) in stakes.iter_mut() {
+			let share = pro_rata(amount, *stake, capital);
+			if share > Zero::zero() {
+				Self::transfer_balance(&Self::pot_account_id(poll_id), delegator, currency, share)?;
+				Self::deposit_event(Event::DelegatorPaid {
+					delegator: delegator.clone(),
+					delegate: who.clone(),
+					poll_id,
+					amount: share,
+				});
+				paid = paid.saturating_add(share);
+			}
+			*stake = stake.saturating_sub(pro_rata(withdrawn, *stake, capital));
+		}
+		stakes.retain(|(_, stake)| !stake.is_zero());
+		if stakes.is_empty() {
+			DelegatedStakesOf::<T>::remove((who, poll_id));
+		} else {
+			DelegatedStakesOf::<T>::insert((who, poll_id), stakes);
+		}
+		Ok(paid)
+	}
+
+	/// Returns what the account can collect from a poll, without mutating storage.
+	///
+	/// The poll should be finished, failed or cancelled, and the account should be either a voter
+	/// or a beneficiary of the poll, or the creator of a poll with reward pool.
+	pub fn claimable(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+	) -> Result<ClaimableOf<T>, DispatchError> {
 		// Get poll and check is it finished or cancelled.
-		let mut poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
 		if poll.status.is_ongoing() {
 			return Err(Error::<T>::CollectOnOngoingPoll.into())
 		}
-		// Find out if origin is a beneficiary or voter.
+		// Find out if origin is a beneficiary, voter or creator with reward pool.
 		let bnf = poll.get_beneficiary(who);
 		let voter = VotingOf::<T>::get((who, poll_id));
-		if bnf.is_none() && voter.is_none() {
+		let pool_creator = poll.created_by.eq(who) &&
+			matches!(poll.reward_settings, RewardSettings::CreatorPool(_));
+		if bnf.is_none() && voter.is_none() && !pool_creator {
 			return Err(Error::<T>::AccountNotVoterOrBeneficiary.into())
 		}
 		// Init needed variables.
-		let currency = poll.currency.clone();
-		let win_opt = poll.winning_option();
+		let win_opts = poll.winning_options();
+		let winning_capital = poll.votes.capital_of(&win_opts);
 		let interest_sum = poll.beneficiary_sum();
-		let mut bnf_interest_amount = BalanceOf::<T>::zero();
-		let mut voter_return_amount = BalanceOf::<T>::zero();
-		// Check if win_opt is available.
-		if let Some(win_option) = win_opt {
-			// Check if origin is a beneficiary.
-			if let Some(bnf) = bnf {
-				// Check if origin has funds to collect.
-				if !bnf.collected {
-					bnf_interest_amount = poll.votes.0[win_option as usize]
-						.saturating_mul(bnf.interest.into())
-						.checked_div(&(100u32 * 100u32).into())
-						.ok_or_else(|| ArithmeticError::Underflow)?;
-				}
+		let escrow = EscrowsOf::<T>::get(poll_id);
+		let mut claimable = Claimable {
+			beneficiary_interest: Zero::zero(),
+			beneficiary_collected: Zero::zero(),
+			voter_return: Votes::new(poll.options_count),
+			voter_reward: Zero::zero(),
+			voter_collected: false,
+			voter_locked: Zero::zero(),
+			creator_refund: Zero::zero(),
+			escrow_refund: Zero::zero(),
+		};
+		// Check if origin is a beneficiary.
+		if let Some(bnf) = bnf {
+			claimable.beneficiary_collected = bnf.collected;
+			// Check if winning options are available and origin has funds to collect.
+			// In lock voting mode the interest is paid by the voters on their collect.
+			let custodial = poll.voting_mode == VotingMode::Transfer;
+			if custodial && !win_opts.is_empty() {
+				let interest = winning_capital
+					.saturating_mul(bnf.interest.into())
+					.checked_div(&(100u32 * 100u32).into())
+					.ok_or_else(|| ArithmeticError::Underflow)?;
+				// Only approved milestones are released from the escrow.
+				let released = match &escrow {
+					Some(escrow) => escrow.released_of(interest, escrow.released),
+					None => interest,
+				};
+				// Only the vested part of the released interest can be collected.
+				let vested = match (bnf.vesting, poll.status.end()) {
+					(Some(vesting), Some(end)) => {
+						let now = <frame_system::Pallet<T>>::block_number();
+						vesting.vested(released, end, now)
+					},
+					_ => released,
+				};
+				claimable.beneficiary_interest = vested.saturating_sub(bnf.collected);
 			}
 		}
 		// Check if origin is a voter.
-		if let Some(voter) = &voter {
+		if let Some(voter) = voter {
+			claimable.voter_collected = voter.collected;
 			// Check if origin has funds to collect.
 			if !voter.collected {
-				// FUTURE WORK TODO: Add rewards collect logic here.
-				for (i, bal) in voter.votes.0.iter().enumerate() {
-					if win_opt.is_some() && i == win_opt.unwrap() as usize {
-						let return_percent =
-							BalanceOf::<T>::from(10_000u32).saturating_sub(interest_sum.into());
-						let amount = bal
-							.saturating_mul(return_percent)
-							.checked_div(&(100u32 * 100u32).into())
-							.ok_or_else(|| ArithmeticError::Underflow)?;
-						voter_return_amount = voter_return_amount.saturating_add(amount);
-					} else {
-						voter_return_amount = voter_return_amount.saturating_add(*bal);
-					}
+				let return_percent =
+					BalanceOf::<T>::from(10_000u32).saturating_sub(interest_sum.into());
+				let losing_stakes = poll.reward_settings == RewardSettings::LosingStakes;
+				let voter_return = voter
+					.votes
+					.0
+					.iter()
+					.enumerate()
+					.map(|(i, bal)| {
+						if win_opts.contains(&(i as u8)) {
+							bal.saturating_mul(return_percent)
+								.checked_div(&(100u32 * 100u32).into())
+								.ok_or_else(|| ArithmeticError::Underflow)
+						} else if !win_opts.is_empty() && losing_stakes {
+							// Stakes on lost options are given away to the winners.
+							Ok(Zero::zero())
+						} else {
+							Ok(*bal)
+						}
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				claimable.voter_return =
+					Votes(voter_return.try_into().map_err(|_| Error::<T>::UnexpectedBehavior)?);
+				claimable.voter_reward = Self::voter_reward(&poll, &win_opts, &voter.votes);
+				if poll.voting_mode == VotingMode::Lock {
+					claimable.voter_locked = voter.votes.capital();
+				}
+			}
+			// Voters of the winning options share the escrow left after a rejected milestone.
+			if let Some(escrow) = escrow.filter(|escrow| escrow.rejected) {
+				if !EscrowParticipationOf::<T>::get((who, poll_id)).refunded {
+					let remaining = poll.beneficiaries.iter().fold(Zero::zero(), |sum, bnf| {
+						let interest =
+							pro_rata(winning_capital, bnf.interest.into(), 10_000u32.into());
+						let released = escrow.released_of(interest, escrow.released);
+						interest.saturating_sub(released).saturating_add(sum)
+					});
+					let voter_capital = voter.votes.capital_of(&win_opts);
+					claimable.escrow_refund = pro_rata(remaining, voter_capital, winning_capital);
 				}
 			}
 		}
-		// Check is there anything that origin can collect.
-		if bnf_interest_amount.is_zero() && voter_return_amount.is_zero() {
+		// Check if origin is a creator, who can take back unspent reward pool.
+		if let (true, RewardSettings::CreatorPool(pool)) = (pool_creator, poll.reward_settings) {
+			// Pool is spent only if somebody voted for the winning options.
+			if winning_capital.is_zero() && !poll.reward_pool_collected {
+				claimable.creator_refund = pool;
+			}
+		}
+		Ok(claimable)
+	}
+
+	/// Returns the reward of the voter with given votes, according to poll reward settings.
+	fn voter_reward(poll: &PollTypeOf<T>, win_opts: &[u8], votes: &VotesOf<T>) -> BalanceOf<T> {
+		let winning_capital = poll.votes.capital_of(win_opts);
+		let voter_capital = votes.capital_of(win_opts);
+		match poll.reward_settings {
+			RewardSettings::None => Zero::zero(),
+			RewardSettings::LosingStakes => {
+				let losing_capital = poll.votes.capital().saturating_sub(winning_capital);
+				pro_rata(losing_capital, voter_capital, winning_capital)
+			},
+			RewardSettings::CreatorPool(pool) => pro_rata(pool, voter_capital, winning_capital),
+		}
+	}
+
+	/// Actually set milestones of a poll, if legit.
+	fn try_set_milestones(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		milestones: Vec<u32>,
+	) -> DispatchResult {
+		let poll = Self::poll_status(poll_id)?;
+		ensure!(poll.created_by.eq(who), Error::<T>::AccountNotAuthor);
+		if let PollStatus::Ongoing { start, .. } = poll.status {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < start, Error::<T>::PollAlreadyStarted);
+		}
+		if milestones.is_empty() {
+			EscrowsOf::<T>::remove(poll_id);
+			return Ok(())
+		}
+		// In lock voting mode the interest is paid by voters, so it can't be escrowed.
+		ensure!(poll.voting_mode == VotingMode::Transfer, Error::<T>::InvalidMilestones);
+		let milestones = milestones.try_into().map_err(|_| Error::<T>::TooManyMilestones)?;
+		let escrow: EscrowOf<T> = Escrow::new(milestones);
+		ensure!(escrow.validate(), Error::<T>::InvalidMilestones);
+		EscrowsOf::<T>::insert(poll_id, escrow);
+		Ok(())
+	}
+
+	/// Actually start the sign-off of the next milestone, if legit.
+	///
+	/// Returns the index of the milestone and the end of its sign-off.
+	fn try_request_sign_off(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+	) -> Result<(u8, BlockNumberOf<T>), DispatchError> {
+		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		let mut escrow = EscrowsOf::<T>::get(poll_id).ok_or(Error::<T>::EscrowNotExist)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		// Anyone can start the sign-off once the deadline of the milestone has passed.
+		let overdue = poll
+			.status
+			.end()
+			.map_or(false, |end| now >= Self::milestone_deadline(end, escrow.released));
+		ensure!(poll.created_by.eq(who) || overdue, Error::<T>::AccountNotAuthor);
+		ensure!(!poll.status.is_ongoing(), Error::<T>::CollectOnOngoingPoll);
+		ensure!(!poll.winning_options().is_empty(), Error::<T>::NoWinningOptions);
+		ensure!(!escrow.is_settled(), Error::<T>::EscrowSettled);
+		ensure!(escrow.sign_off.is_none(), Error::<T>::SignOffInProgress);
+		let end = now.saturating_add(T::SignOffPeriod::get());
+		escrow.sign_off = Some(SignOff { end, approvals: Zero::zero(), rejections: Zero::zero() });
+		EscrowsOf::<T>::insert(poll_id, &escrow);
+		Ok((escrow.released, end))
+	}
+
+	/// Returns the block from which anyone can start the sign-off of the milestone, for a poll
+	/// which ended at the given block.
+	fn milestone_deadline(end: BlockNumberOf<T>, milestone: u8) -> BlockNumberOf<T> {
+		let count = BlockNumberOf::<T>::from(u32::from(milestone).saturating_add(1));
+		end.saturating_add(T::MilestonePeriod::get().saturating_mul(count))
+	}
+
+	/// Actually approve or reject a milestone, if legit.
+	///
+	/// Returns the index of the milestone and the stake of the voter on the winning options.
+	fn try_sign_off(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		approve: bool,
+	) -> Result<(u8, BalanceOf<T>), DispatchError> {
+		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		let mut escrow = EscrowsOf::<T>::get(poll_id).ok_or(Error::<T>::EscrowNotExist)?;
+		let milestone = escrow.released;
+		let sign_off = escrow.sign_off.as_mut().ok_or(Error::<T>::SignOffNotInProgress)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(now < sign_off.end, Error::<T>::SignOffEnded);
+		// Only voters of the winning options can sign off, with their stake on them.
+		let voter = VotingOf::<T>::get((who, poll_id)).ok_or(Error::<T>::VotesNotExist)?;
+		let stake = voter.votes.capital_of(&poll.winning_options());
+		ensure!(stake > Zero::zero(), Error::<T>::VotesNotExist);
+		let mut participation = EscrowParticipationOf::<T>::get((who, poll_id));
+		ensure!(participation.signed_off <= milestone, Error::<T>::AlreadySignedOff);
+		match approve {
+			true => sign_off.approvals = sign_off.approvals.saturating_add(stake),
+			false => sign_off.rejections = sign_off.rejections.saturating_add(stake),
+		}
+		participation.signed_off = milestone.saturating_add(1);
+		EscrowParticipationOf::<T>::insert((who, poll_id), participation);
+		EscrowsOf::<T>::insert(poll_id, escrow);
+		Ok((milestone, stake))
+	}
+
+	/// Actually queue a poll to be reaped, if legit.
+	fn try_reap_poll(poll_id: T::PollIndex) -> DispatchResult {
+		let poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		let end = match poll.status {
+			PollStatus::Ongoing { .. } => return Err(Error::<T>::CollectOnOngoingPoll.into()),
+			PollStatus::Cancelled(end) |
+			PollStatus::Finished { end, .. } |
+			PollStatus::Failed(end) |
+			PollStatus::Tied(end) => end,
+		};
+		ensure!(!SettlementQueue::<T>::get().contains(&poll_id), Error::<T>::PollNotSettled);
+		let mut queue = ReapQueue::<T>::get();
+		ensure!(!queue.contains(&poll_id), Error::<T>::PollAlreadyReaping);
+		// Interest must be released entirely before what's left of it can be taken.
+		let now = <frame_system::Pallet<T>>::block_number();
+		if !poll.winning_options().is_empty() {
+			let vested = poll
+				.beneficiaries
+				.iter()
+				.filter_map(|bnf| bnf.vesting)
+				.all(|vesting| now >= vesting.vested_at(end));
+			let escrow_settled =
+				EscrowsOf::<T>::get(poll_id).map_or(true, |escrow| escrow.is_settled());
+			ensure!(vested && escrow_settled, Error::<T>::InterestNotReleased);
+		}
+		// Interest left in the pot is taken only after the reap delay.
+		ensure!(
+			Self::leftover_of(poll_id, &poll).is_zero() ||
+				now >= end.saturating_add(T::ReapDelay::get()),
+			Error::<T>::PollNotReapable,
+		);
+		queue.try_push(poll_id).map_err(|_| Error::<T>::ReapQueueFull)?;
+		ReapQueue::<T>::put(queue);
+		Ok(())
+	}
+
+	/// Actually close the sign-off of a milestone, if legit.
+	///
+	/// Returns the index of the milestone and whether it was approved.
+	fn try_close_sign_off(poll_id: T::PollIndex) -> Result<(u8, bool), DispatchError> {
+		let mut escrow = EscrowsOf::<T>::get(poll_id).ok_or(Error::<T>::EscrowNotExist)?;
+		let sign_off = escrow.sign_off.take().ok_or(Error::<T>::SignOffNotInProgress)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(sign_off.end <= now, Error::<T>::SignOffNotEnded);
+		let milestone = escrow.released;
+		let approved = sign_off.approvals > sign_off.rejections;
+		match approved {
+			true => escrow.released = milestone.saturating_add(1),
+			false => escrow.rejected = true,
+		}
+		EscrowsOf::<T>::insert(poll_id, escrow);
+		Ok((milestone, approved))
+	}
+
+	/// Actually collect a vote or winning option, if the account is legit.
+	fn try_collect(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let claimable = Self::claimable(who, poll_id)?;
+		// Check is there anything that origin can collect or settle.
+		let amount = claimable.total();
+		if amount.is_zero() && claimable.voter_locked.is_zero() {
 			return Err(Error::<T>::NothingToCollect.into())
 		}
+		let mut poll = PollDetailsOf::<T>::get(poll_id).ok_or(Error::<T>::PollInvalid)?;
+		// In lock voting mode the returned stake is unlocked, so it isn't paid from the pot.
+		let pot_amount = match poll.voting_mode {
+			VotingMode::Transfer => amount,
+			VotingMode::Lock => amount.saturating_sub(claimable.voter_return.capital()),
+		};
 		// Check if pot has enough funds.
 		ensure!(
-			Self::check_balance(
-				&Self::account_id(),
-				poll.currency,
-				bnf_interest_amount.saturating_add(voter_return_amount)
-			),
+			Self::check_balance(&Self::pot_account_id(poll_id), poll.currency, pot_amount),
 			Error::<T>::PotInsufficientFunds,
 		);
-		let mut amount = BalanceOf::<T>::zero();
-		if bnf_interest_amount > Zero::zero() {
-			amount = amount.saturating_add(bnf_interest_amount);
+		let currency = poll.currency;
+		if claimable.beneficiary_interest > Zero::zero() {
 			// Must never be an error, but better to be safe.
 			let bnf = poll.get_mut_beneficiary(who).ok_or(Error::<T>::UnexpectedBehavior)?;
-			bnf.collected = true;
+			// Escrowed or vesting interest is collected in parts.
+			bnf.collected = bnf.collected.saturating_add(claimable.beneficiary_interest);
+		}
+		if claimable.escrow_refund > Zero::zero() {
+			EscrowParticipationOf::<T>::mutate((who, poll_id), |participation| {
+				participation.refunded = true
+			});
+		}
+		if claimable.creator_refund > Zero::zero() {
+			poll.reward_pool_collected = true;
+		}
+		if claimable.beneficiary_interest > Zero::zero() || claimable.creator_refund > Zero::zero()
+		{
 			// Update poll in storage.
-			PollDetailsOf::<T>::insert(poll_id, poll);
+			PollDetailsOf::<T>::insert(poll_id, &poll);
 		}
-		if voter_return_amount > Zero::zero() {
-			amount = amount.saturating_add(voter_return_amount);
+		let mut paid = Zero::zero();
+		if claimable.voter_return.capital() > Zero::zero() ||
+			claimable.voter_reward > Zero::zero() ||
+			claimable.voter_locked > Zero::zero()
+		{
 			// Must never be an error, but better to be safe.
-			let mut votes = voter.ok_or(Error::<T>::UnexpectedBehavior)?;
+			let mut votes =
+				VotingOf::<T>::get((who, poll_id)).ok_or(Error::<T>::UnexpectedBehavior)?;
 			votes.collected = true;
 			// Update poll vote in storage.
-			VotingOf::<T>::insert((who, poll_id), votes);
+			VotingOf::<T>::insert((who, poll_id), &votes);
+			if claimable.voter_locked > Zero::zero() {
+				Self::settle_locked_votes(who, poll_id, &poll, &votes.votes)?;
+			}
+		}
+		// Pay delegators their share of the stake, reward and escrow refund of the voter.
+		let voter_amount = claimable
+			.voter_return
+			.capital()
+			.saturating_add(claimable.voter_reward)
+			.saturating_add(claimable.escrow_refund);
+		if poll.voting_mode == VotingMode::Transfer && voter_amount > Zero::zero() {
+			let capital =
+				VotingOf::<T>::get((who, poll_id)).map_or(Zero::zero(), |v| v.votes.capital());
+			// Stakes of delegators are kept, as the escrow may refund the voter later. They are
+			// removed when the poll is reaped.
+			paid =
+				Self::pay_delegators(who, poll_id, currency, voter_amount, Zero::zero(), capital)?;
+		}
+		// Actually transfer balance from the pot.
+		if pot_amount > paid {
+			Self::transfer_balance(
+				&Self::pot_account_id(poll_id),
+				who,
+				currency,
+				pot_amount.saturating_sub(paid),
+			)?;
+		}
+		Ok(amount.saturating_sub(paid))
+	}
+
+	/// Unlocks the stake of the voter in lock voting mode, and pays beneficiaries their interest
+	/// from the voter's stake on the winning options.
+	fn settle_locked_votes(
+		who: &T::AccountId,
+		poll_id: T::PollIndex,
+		poll: &PollTypeOf<T>,
+		votes: &VotesOf<T>,
+	) -> DispatchResult {
+		Self::unlock_stake(who, votes.capital());
+		let winning_stake = votes.capital_of(&poll.winning_options());
+		for bnf in poll.beneficiaries.iter() {
+			let interest = winning_stake
+				.saturating_mul(bnf.interest.into())
+				.checked_div(&(100u32 * 100u32).into())
+				.ok_or_else(|| ArithmeticError::Underflow)?;
+			if interest.is_zero() {
+				continue
+			}
+			Self::transfer_balance(who, &bnf.who, poll.currency, interest)?;
+			Self::deposit_event(Event::Collected {
+				who: bnf.who.clone(),
+				poll_id,
+				amount: interest,
+			});
+		}
+		Ok(())
+	}
+
+	/// Picks one of the tied options using on-chain randomness.
+	fn break_tie(poll_id: T::PollIndex, tied_options: &[u8]) -> u8 {
+		let (seed, _) = T::Randomness::random(&(FATERIUM_POLLS_ID, poll_id).encode());
+		let random = u32::decode(&mut TrailingZeroInput::new(seed.as_ref())).unwrap_or_default();
+		tied_options[random as usize % tied_options.len()]
+	}
+
+	/// Returns all options which won the poll by the rule of its kind. Returns more than one
+	/// option on a tie.
+	fn winning_options_of(poll_id: T::PollIndex, poll: &PollTypeOf<T>) -> Vec<u8> {
+		match poll.kind {
+			PollKind::Plurality => poll.votes.winning_options(),
+			PollKind::RankedChoice => {
+				let rankings = RankingsOf::<T>::get(poll_id);
+				let rankings: Vec<_> =
+					rankings.iter().map(|(ranking, stake)| (ranking.as_slice(), *stake)).collect();
+				instant_runoff(&rankings, poll.options_count)
+			},
+			PollKind::Approval | PollKind::Score { .. } => TallyOf::<T>::get(poll_id)
+				.unwrap_or_else(|| Votes::new(poll.options_count))
+				.winning_options(),
 		}
-		// Actually transfer balance to the pot.
-		Self::transfer_balance(&Self::account_id(), who, currency, amount)?;
-		Ok(amount)
 	}
 
 	/// Actually finish the poll, if the poll is legit.
@@ -645,15 +3299,26 @@ impl<T: Config> Pallet<T> {
 		};
 		// If poll reached it's goal - mark as finished; if not - mark as failed.
 		if poll.votes.capital() >= poll.goal {
-			// Determine winning option and update status.
-			let winning_option =
-				poll.votes.winning_option().ok_or(Error::<T>::UnexpectedBehavior)?;
-			poll.status = PollStatus::Finished { winning_option, end };
+			// Determine winning option and update status, according to the tie policy. Winning
+			// options which don't satisfy the goal mode can't win.
+			let mut winning_options = Self::winning_options_of(poll_id, &poll);
+			winning_options.retain(|o| poll.goal_mode.is_reached_by(*o, poll.goal, &poll.votes));
+			poll.status = match (winning_options.as_slice(), poll.tie_policy) {
+				([], _) => PollStatus::Failed(end),
+				([winning_option], _) =>
+					PollStatus::Finished { winning_option: *winning_option, end },
+				(_, TiePolicy::Random) => {
+					let winning_option = Self::break_tie(poll_id, &winning_options);
+					PollStatus::Finished { winning_option, end }
+				},
+				(_, TiePolicy::Refund | TiePolicy::SplitInterest) => PollStatus::Tied(end),
+			};
 		} else {
 			poll.status = PollStatus::Failed(end);
 		}
 		// Update poll in storage.
 		PollDetailsOf::<T>::insert(poll_id, poll);
+		Self::queue_settlement(poll_id);
 		Ok(())
 	}
 }
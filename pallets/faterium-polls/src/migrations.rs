@@ -0,0 +1,1149 @@
+//! Storage migrations for the polls pallet.
+//!
+//! Every migration is an [`OnRuntimeUpgrade`] that checks the on-chain storage version, so it is
+//! safe to keep it in the runtime's `Executive` after it has been applied. Its `pre_upgrade` and
+//! `post_upgrade` checks are run with the `try-runtime` feature.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+
+/// Migration of the storage to version 1, where polls and votes are bounded.
+pub mod v1 {
+	use super::*;
+
+	/// Beneficiary as it was stored in versions 0 to 5, before vesting.
+	///
+	/// `collected` is the number of collected parts of the interest, which was a flag before
+	/// escrows were added, and is encoded the same.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+	pub struct BeneficiaryV0<AccountId> {
+		pub who: AccountId,
+		pub interest: u32,
+		pub collected: u8,
+	}
+
+	/// Poll details as they were stored in version 0.
+	#[derive(Decode)]
+	pub struct OldPollDetails<Balance, AccountId, AssetId, BlockNumber> {
+		pub created_by: AccountId,
+		pub ipfs_cid: Vec<u8>,
+		pub beneficiaries: Vec<BeneficiaryV0<AccountId>>,
+		/// Only `RewardSettings::None` existed in version 0, which has the same encoding.
+		pub reward_settings: RewardSettings<Balance>,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub votes: Vec<Balance>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Account votes as they were stored in version 0.
+	#[derive(Decode)]
+	pub struct OldAccountVotes<Balance> {
+		pub votes: Vec<Balance>,
+		pub collected: bool,
+	}
+
+	type OldPollDetailsOf<T> = OldPollDetails<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+	>;
+
+	/// Poll details as they are stored in versions 1 and 2.
+	#[derive(Encode, Decode)]
+	pub struct PollDetailsV1<
+		Balance: Clone + Eq + core::fmt::Debug,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions: Get<u32>,
+	> {
+		pub created_by: AccountId,
+		pub deposit: Balance,
+		pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		pub beneficiaries: BoundedVec<BeneficiaryV0<AccountId>, MaxPollBeneficiaries>,
+		pub reward_settings: RewardSettings<Balance>,
+		pub reward_pool_collected: bool,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub tie_policy: TiePolicy,
+		pub voting_mode: VotingMode,
+		pub votes: Votes<Balance, MaxPollOptions>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Poll details of versions 1 and 2 for the given config.
+	pub type PollDetailsV1Of<T> = PollDetailsV1<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+		<T as Config>::MaxIpfsCidLength,
+		<T as Config>::MaxPollBeneficiaries,
+		<T as Config>::MaxPollOptions,
+	>;
+
+	/// Converts old poll details into the bounded ones, or returns None if bounds are exceeded.
+	///
+	/// Polls created before tie policies and voting modes get `TiePolicy::Refund` and
+	/// `VotingMode::Transfer`, as their stakes are already in the pot. Nothing was reserved from
+	/// their creators, so their deposit is zero.
+	fn migrate_poll<T: Config>(old: OldPollDetailsOf<T>) -> Option<PollDetailsV1Of<T>> {
+		Some(PollDetailsV1 {
+			created_by: old.created_by,
+			deposit: Zero::zero(),
+			ipfs_cid: old.ipfs_cid.try_into().ok()?,
+			beneficiaries: old.beneficiaries.try_into().ok()?,
+			reward_settings: old.reward_settings,
+			reward_pool_collected: false,
+			goal: old.goal,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			tie_policy: TiePolicy::Refund,
+			voting_mode: VotingMode::Transfer,
+			votes: Votes(old.votes.try_into().ok()?),
+			currency: old.currency,
+			status: old.status,
+		})
+	}
+
+	/// Migrates polls and votes to the bounded storage of version 1.
+	///
+	/// Entries that exceed the bounds of the runtime can't be kept and are removed, so the
+	/// bounds must not be lower than the limits previously enforced on poll creation.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v1, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<OldPollDetailsOf<T>, _>(|poll_id, old| {
+				translated.saturating_inc();
+				let poll = migrate_poll::<T>(old);
+				if poll.is_none() {
+					log::error!(target: LOG_TARGET, "Poll {:?} exceeds bounds, removed", poll_id);
+				}
+				poll
+			});
+			VotingOf::<T>::translate::<OldAccountVotes<BalanceOf<T>>, _>(|(who, poll_id), old| {
+				translated.saturating_inc();
+				match old.votes.try_into() {
+					Ok(votes) =>
+						Some(AccountVotes { votes: Votes(votes), collected: old.collected }),
+					Err(_) => {
+						log::error!(
+							target: LOG_TARGET,
+							"Votes of {:?} in poll {:?} exceed bounds, removed",
+							who,
+							poll_id,
+						);
+						None
+					},
+				}
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} entries to v1", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Values can't be decoded with the new types yet, so only keys are counted.
+			let polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			let votes = VotingOf::<T>::iter_keys().count() as u32;
+			log::info!(target: LOG_TARGET, "pre-migration: {} polls, {} votes", polls, votes);
+			Ok((polls, votes).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let (polls, votes): (u32, u32) =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			// Later migrations may have already run in the same upgrade.
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 1,
+				"storage version should be at least 1 after the migration",
+			);
+			// Entries which couldn't be migrated are removed, so all the rest should decode.
+			let new_polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			let new_votes = VotingOf::<T>::iter_keys().count() as u32;
+			ensure!(new_polls <= polls && new_votes <= votes, "migration added entries");
+			ensure!(
+				PollDetailsOf::<T>::iter_values().count() as u32 == new_polls,
+				"some polls can't be decoded",
+			);
+			ensure!(
+				VotingOf::<T>::iter_values().count() as u32 == new_votes,
+				"some votes can't be decoded",
+			);
+			for (poll_id, poll) in PollDetailsOf::<T>::iter() {
+				if poll.votes.0.len() != usize::from(poll.options_count) {
+					log::error!(target: LOG_TARGET, "Poll {:?} has invalid votes", poll_id);
+					return Err("poll votes don't match options count")
+				}
+			}
+			log::info!(
+				target: LOG_TARGET,
+				"post-migration: {} of {} polls, {} of {} votes kept",
+				new_polls,
+				polls,
+				new_votes,
+				votes,
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 2, where IPFS CIDs of polls are stored in the binary
+/// CIDv1 form.
+pub mod v2 {
+	use super::*;
+
+	/// Normalizes IPFS CIDs of existing polls.
+	///
+	/// CIDs which can't be decoded, or whose content type or hash function isn't allowed, are kept
+	/// as they are.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v2, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v1::PollDetailsV1Of<T>, _>(|poll_id, mut poll| {
+				translated.saturating_inc();
+				let cid = Pallet::<T>::normalize_cid(&poll.ipfs_cid)
+					.ok()
+					.and_then(|cid| cid.try_into().ok());
+				match cid {
+					Some(cid) => poll.ipfs_cid = cid,
+					None => log::warn!(
+						target: LOG_TARGET,
+						"IPFS CID of poll {:?} can't be normalized, kept as is",
+						poll_id,
+					),
+				}
+				Some(poll)
+			});
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v2", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			Ok((PollDetailsOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			// Later migrations may have already run in the same upgrade.
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 2,
+				"storage version should be at least 2 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			ensure!(PollDetailsOf::<T>::iter_keys().count() as u32 <= polls, "polls were added");
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 3, where voters can move their votes between options.
+pub mod v3 {
+	use super::*;
+
+	/// Poll details as they are stored in version 3.
+	#[derive(Encode, Decode)]
+	pub struct PollDetailsV3<
+		Balance: Clone + Eq + core::fmt::Debug,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions: Get<u32>,
+	> {
+		pub created_by: AccountId,
+		pub deposit: Balance,
+		pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		pub beneficiaries: BoundedVec<v1::BeneficiaryV0<AccountId>, MaxPollBeneficiaries>,
+		pub reward_settings: RewardSettings<Balance>,
+		pub reward_pool_collected: bool,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub movable_votes: bool,
+		pub tie_policy: TiePolicy,
+		pub voting_mode: VotingMode,
+		pub votes: Votes<Balance, MaxPollOptions>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Poll details of version 3 for the given config.
+	pub type PollDetailsV3Of<T> = PollDetailsV3<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+		<T as Config>::MaxIpfsCidLength,
+		<T as Config>::MaxPollBeneficiaries,
+		<T as Config>::MaxPollOptions,
+	>;
+
+	/// Converts poll details of version 2, allowing to move votes in existing polls.
+	fn migrate_poll<T: Config>(old: v1::PollDetailsV1Of<T>) -> PollDetailsV3Of<T> {
+		PollDetailsV3 {
+			created_by: old.created_by,
+			deposit: old.deposit,
+			ipfs_cid: old.ipfs_cid,
+			beneficiaries: old.beneficiaries,
+			reward_settings: old.reward_settings,
+			reward_pool_collected: old.reward_pool_collected,
+			goal: old.goal,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			movable_votes: true,
+			tie_policy: old.tie_policy,
+			voting_mode: old.voting_mode,
+			votes: old.votes,
+			currency: old.currency,
+			status: old.status,
+		}
+	}
+
+	/// Adds the `movable_votes` setting to existing polls.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 2 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v3, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v1::PollDetailsV1Of<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(migrate_poll::<T>(old))
+			});
+			StorageVersion::new(3).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v3", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			Ok((PollDetailsOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			// Later migrations may have already run in the same upgrade.
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 3,
+				"storage version should be at least 3 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			ensure!(PollDetailsOf::<T>::iter_keys().count() as u32 <= polls, "polls were added");
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 4, where polls can be sealed.
+pub mod v4 {
+	use super::*;
+
+	/// Poll details as they are stored in version 4.
+	#[derive(Encode, Decode)]
+	pub struct PollDetailsV4<
+		Balance: Clone + Eq + core::fmt::Debug,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions: Get<u32>,
+	> {
+		pub created_by: AccountId,
+		pub deposit: Balance,
+		pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		pub beneficiaries: BoundedVec<v1::BeneficiaryV0<AccountId>, MaxPollBeneficiaries>,
+		pub reward_settings: RewardSettings<Balance>,
+		pub reward_pool_collected: bool,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub movable_votes: bool,
+		pub tie_policy: TiePolicy,
+		pub voting_mode: VotingMode,
+		pub sealed: Option<SealedSettings<BlockNumber>>,
+		pub votes: Votes<Balance, MaxPollOptions>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Poll details of version 4 for the given config.
+	pub type PollDetailsV4Of<T> = PollDetailsV4<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+		<T as Config>::MaxIpfsCidLength,
+		<T as Config>::MaxPollBeneficiaries,
+		<T as Config>::MaxPollOptions,
+	>;
+
+	/// Converts poll details of version 3, where all polls are public.
+	fn migrate_poll<T: Config>(old: v3::PollDetailsV3Of<T>) -> PollDetailsV4Of<T> {
+		PollDetailsV4 {
+			created_by: old.created_by,
+			deposit: old.deposit,
+			ipfs_cid: old.ipfs_cid,
+			beneficiaries: old.beneficiaries,
+			reward_settings: old.reward_settings,
+			reward_pool_collected: old.reward_pool_collected,
+			goal: old.goal,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			movable_votes: old.movable_votes,
+			tie_policy: old.tie_policy,
+			voting_mode: old.voting_mode,
+			sealed: None,
+			votes: old.votes,
+			currency: old.currency,
+			status: old.status,
+		}
+	}
+
+	/// Adds the `sealed` setting to existing polls.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 3 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v4, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v3::PollDetailsV3Of<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(migrate_poll::<T>(old))
+			});
+			StorageVersion::new(4).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v4", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			Ok((PollDetailsOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			// Later migrations may have already run in the same upgrade.
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 4,
+				"storage version should be at least 4 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			ensure!(PollDetailsOf::<T>::iter_keys().count() as u32 <= polls, "polls were added");
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 5, where voting in polls can be limited to eligible
+/// accounts.
+pub mod v5 {
+	use super::*;
+
+	/// Poll details as they are stored in version 5.
+	#[derive(Encode, Decode)]
+	pub struct PollDetailsV5<
+		Balance: Clone + Eq + core::fmt::Debug,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions: Get<u32>,
+	> {
+		pub created_by: AccountId,
+		pub deposit: Balance,
+		pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		pub beneficiaries: BoundedVec<v1::BeneficiaryV0<AccountId>, MaxPollBeneficiaries>,
+		pub reward_settings: RewardSettings<Balance>,
+		pub reward_pool_collected: bool,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub movable_votes: bool,
+		pub tie_policy: TiePolicy,
+		pub voting_mode: VotingMode,
+		pub sealed: Option<SealedSettings<BlockNumber>>,
+		pub eligibility: Eligibility<AssetId, Balance>,
+		pub votes: Votes<Balance, MaxPollOptions>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Poll details of version 5 for the given config.
+	pub type PollDetailsV5Of<T> = PollDetailsV5<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+		<T as Config>::MaxIpfsCidLength,
+		<T as Config>::MaxPollBeneficiaries,
+		<T as Config>::MaxPollOptions,
+	>;
+
+	/// Converts poll details of version 4, where anyone can vote.
+	fn migrate_poll<T: Config>(old: v4::PollDetailsV4Of<T>) -> PollDetailsV5Of<T> {
+		PollDetailsV5 {
+			created_by: old.created_by,
+			deposit: old.deposit,
+			ipfs_cid: old.ipfs_cid,
+			beneficiaries: old.beneficiaries,
+			reward_settings: old.reward_settings,
+			reward_pool_collected: old.reward_pool_collected,
+			goal: old.goal,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			movable_votes: old.movable_votes,
+			tie_policy: old.tie_policy,
+			voting_mode: old.voting_mode,
+			sealed: old.sealed,
+			eligibility: Eligibility::Anyone,
+			votes: old.votes,
+			currency: old.currency,
+			status: old.status,
+		}
+	}
+
+	/// Adds the `eligibility` rule to existing polls.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 4 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v5, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v4::PollDetailsV4Of<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(migrate_poll::<T>(old))
+			});
+			StorageVersion::new(5).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v5", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			Ok((PollDetailsOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			// Later migrations may have already run in the same upgrade.
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 5,
+				"storage version should be at least 5 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			ensure!(PollDetailsOf::<T>::iter_keys().count() as u32 <= polls, "polls were added");
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 6, where the interest of beneficiaries can vest, and the
+/// collected amount of it is stored instead of the number of collected parts.
+pub mod v6 {
+	use super::*;
+
+	/// Poll details as they are stored in version 6.
+	#[derive(Encode, Decode)]
+	pub struct PollDetailsV6<
+		Balance: Clone + Eq + core::fmt::Debug,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions: Get<u32>,
+	> {
+		pub created_by: AccountId,
+		pub deposit: Balance,
+		pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		pub beneficiaries:
+			BoundedVec<Beneficiary<AccountId, Balance, BlockNumber>, MaxPollBeneficiaries>,
+		pub reward_settings: RewardSettings<Balance>,
+		pub reward_pool_collected: bool,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub movable_votes: bool,
+		pub tie_policy: TiePolicy,
+		pub voting_mode: VotingMode,
+		pub sealed: Option<SealedSettings<BlockNumber>>,
+		pub eligibility: Eligibility<AssetId, Balance>,
+		pub votes: Votes<Balance, MaxPollOptions>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Poll details of version 6 for the given config.
+	pub type PollDetailsV6Of<T> = PollDetailsV6<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+		<T as Config>::MaxIpfsCidLength,
+		<T as Config>::MaxPollBeneficiaries,
+		<T as Config>::MaxPollOptions,
+	>;
+
+	/// Converts poll details of version 5, where the interest of beneficiaries is paid at once.
+	///
+	/// Beneficiaries who collected get the amount they were paid, which is the whole interest,
+	/// or only released milestones of the escrow of the poll.
+	fn migrate_poll<T: Config>(
+		poll_id: T::PollIndex,
+		old: v5::PollDetailsV5Of<T>,
+	) -> PollDetailsV6Of<T> {
+		let mut poll = PollDetailsV6 {
+			created_by: old.created_by,
+			deposit: old.deposit,
+			ipfs_cid: old.ipfs_cid,
+			beneficiaries: Default::default(),
+			reward_settings: old.reward_settings,
+			reward_pool_collected: old.reward_pool_collected,
+			goal: old.goal,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			movable_votes: old.movable_votes,
+			tie_policy: old.tie_policy,
+			voting_mode: old.voting_mode,
+			sealed: old.sealed,
+			eligibility: old.eligibility,
+			votes: old.votes,
+			currency: old.currency,
+			status: old.status,
+		};
+		// Polls of version 5 are all of the `Plurality` kind.
+		let winning_options = match poll.status {
+			PollStatus::Finished { winning_option, .. } => vec![winning_option],
+			PollStatus::Tied(_) if poll.tie_policy == TiePolicy::SplitInterest =>
+				poll.votes.winning_options(),
+			_ => vec![],
+		};
+		let winning_capital = poll.votes.capital_of(&winning_options);
+		let escrow = EscrowsOf::<T>::get(poll_id);
+		let beneficiaries: Vec<_> = old
+			.beneficiaries
+			.into_iter()
+			.map(|old| {
+				let interest = pro_rata(winning_capital, old.interest.into(), 10_000u32.into());
+				let collected = match (old.collected, &escrow) {
+					(0, _) => Zero::zero(),
+					(milestones, Some(escrow)) => escrow.released_of(interest, milestones),
+					(_, None) => interest,
+				};
+				Beneficiary { who: old.who, interest: old.interest, vesting: None, collected }
+			})
+			.collect();
+		// Bounds are the same as in version 5, so beneficiaries always fit.
+		poll.beneficiaries = beneficiaries.try_into().unwrap_or_default();
+		poll
+	}
+
+	/// Adds vesting schedules to beneficiaries of existing polls, and stores the interest they
+	/// collected.
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 5 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v6, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v5::PollDetailsV5Of<T>, _>(|poll_id, old| {
+				translated.saturating_inc();
+				Some(migrate_poll::<T>(poll_id, old))
+			});
+			StorageVersion::new(6).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v6", translated);
+			T::DbWeight::get().reads_writes(translated.saturating_mul(2) + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			let polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			Ok(polls.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 6,
+				"storage version should be at least 6 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			let new_polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			ensure!(new_polls <= polls, "polls were added");
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 7, where polls have a kind deciding their winner.
+pub mod v7 {
+	use super::*;
+
+	/// Poll details as they are stored in version 7.
+	#[derive(Encode, Decode)]
+	pub struct PollDetailsV7<
+		Balance: Clone + Eq + core::fmt::Debug,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions: Get<u32>,
+	> {
+		pub created_by: AccountId,
+		pub deposit: Balance,
+		pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		pub beneficiaries:
+			BoundedVec<Beneficiary<AccountId, Balance, BlockNumber>, MaxPollBeneficiaries>,
+		pub reward_settings: RewardSettings<Balance>,
+		pub reward_pool_collected: bool,
+		pub goal: Balance,
+		pub options_count: u8,
+		pub multiple_votes: bool,
+		pub movable_votes: bool,
+		pub tie_policy: TiePolicy,
+		pub voting_mode: VotingMode,
+		pub sealed: Option<SealedSettings<BlockNumber>>,
+		pub eligibility: Eligibility<AssetId, Balance>,
+		pub kind: PollKind,
+		pub votes: Votes<Balance, MaxPollOptions>,
+		pub currency: PollCurrency<AssetId>,
+		pub status: PollStatus<BlockNumber>,
+	}
+
+	/// Poll details of version 7 for the given config.
+	pub type PollDetailsV7Of<T> = PollDetailsV7<
+		BalanceOf<T>,
+		<T as SystemConfig>::AccountId,
+		AssetIdOf<T>,
+		BlockNumberOf<T>,
+		<T as Config>::MaxIpfsCidLength,
+		<T as Config>::MaxPollBeneficiaries,
+		<T as Config>::MaxPollOptions,
+	>;
+
+	/// Converts poll details of version 6, where the option with the highest stake wins.
+	fn migrate_poll<T: Config>(old: v6::PollDetailsV6Of<T>) -> PollDetailsV7Of<T> {
+		PollDetailsV7 {
+			created_by: old.created_by,
+			deposit: old.deposit,
+			ipfs_cid: old.ipfs_cid,
+			beneficiaries: old.beneficiaries,
+			reward_settings: old.reward_settings,
+			reward_pool_collected: old.reward_pool_collected,
+			goal: old.goal,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			movable_votes: old.movable_votes,
+			tie_policy: old.tie_policy,
+			voting_mode: old.voting_mode,
+			sealed: old.sealed,
+			eligibility: old.eligibility,
+			kind: PollKind::Plurality,
+			votes: old.votes,
+			currency: old.currency,
+			status: old.status,
+		}
+	}
+
+	/// Adds the `Plurality` kind to existing polls.
+	pub struct MigrateToV7<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 6 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v7, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v6::PollDetailsV6Of<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(migrate_poll::<T>(old))
+			});
+			StorageVersion::new(7).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v7", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			Ok((PollDetailsOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 7,
+				"storage version should be at least 7 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			let new_polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			ensure!(new_polls <= polls, "polls were added");
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 8, where polls have a goal mode.
+pub mod v8 {
+	use super::*;
+
+	/// Converts poll details of version 7, where only the capital of all options is checked
+	/// against the goal.
+	fn migrate_poll<T: Config>(old: v7::PollDetailsV7Of<T>) -> PollTypeOf<T> {
+		PollDetails {
+			created_by: old.created_by,
+			deposit: old.deposit,
+			ipfs_cid: old.ipfs_cid,
+			beneficiaries: old.beneficiaries,
+			reward_settings: old.reward_settings,
+			reward_pool_collected: old.reward_pool_collected,
+			goal: old.goal,
+			goal_mode: GoalMode::TotalCapital,
+			options_count: old.options_count,
+			multiple_votes: old.multiple_votes,
+			movable_votes: old.movable_votes,
+			tie_policy: old.tie_policy,
+			voting_mode: old.voting_mode,
+			sealed: old.sealed,
+			eligibility: old.eligibility,
+			kind: old.kind,
+			votes: old.votes,
+			currency: old.currency,
+			status: old.status,
+		}
+	}
+
+	/// Adds the `TotalCapital` goal mode to existing polls.
+	pub struct MigrateToV8<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 7 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v8, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			PollDetailsOf::<T>::translate::<v7::PollDetailsV7Of<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(migrate_poll::<T>(old))
+			});
+			StorageVersion::new(8).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated {} polls to v8", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			Ok((PollDetailsOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 8,
+				"storage version should be at least 8 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			let new_polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			ensure!(new_polls <= polls, "polls were added");
+			ensure!(
+				PollDetailsOf::<T>::iter_values().count() as u32 == new_polls,
+				"some polls can't be decoded",
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the storage to version 9, where every poll holds its funds in a pot of its own.
+pub mod v9 {
+	use super::*;
+	use scale_info::prelude::collections::BTreeMap;
+
+	/// Returns the amount of the currency in the shared pot, which can be moved out of it. The
+	/// existential deposit of the shared pot isn't part of it.
+	fn shared_pot<T: Config>(currency: PollCurrency<AssetIdOf<T>>) -> BalanceOf<T> {
+		let pot = Pallet::<T>::account_id();
+		match currency {
+			PollCurrency::Native => T::Currency::free_balance(&pot)
+				.saturating_sub(<T::Currency as Currency<T::AccountId>>::minimum_balance()),
+			PollCurrency::Asset(asset_id) =>
+				<T::Fungibles as Inspect<T::AccountId>>::balance(asset_id, &pot).into(),
+		}
+	}
+
+	/// Returns the currencies of polls and the amounts they owe from their pots: stakes and the
+	/// reward pool of an ongoing poll, or what is left to collect from an ended one, and
+	/// unrevealed commitments. Votes and commitments of all polls are iterated once, and their
+	/// number is returned too.
+	fn owed_by_polls<T: Config>(
+	) -> (BTreeMap<T::PollIndex, (PollCurrency<AssetIdOf<T>>, BalanceOf<T>)>, u64) {
+		let polls: BTreeMap<_, _> = PollDetailsOf::<T>::iter().collect();
+		let mut owed: BTreeMap<_, _> = polls
+			.iter()
+			.map(|(poll_id, poll)| {
+				(*poll_id, (poll.currency, Pallet::<T>::owed_by_poll(*poll_id, poll)))
+			})
+			.collect();
+		let mut entries = 0u64;
+		for (who, poll_id) in VotingOf::<T>::iter_keys() {
+			entries.saturating_inc();
+			if let (Some(poll), Some((_, sum))) = (polls.get(&poll_id), owed.get_mut(&poll_id)) {
+				*sum = sum.saturating_add(Pallet::<T>::owed_to_voter(&who, poll_id, poll));
+			}
+		}
+		for ((_, poll_id), commitment) in CommitmentsOf::<T>::iter() {
+			entries.saturating_inc();
+			match (polls.get(&poll_id), owed.get_mut(&poll_id)) {
+				(Some(poll), Some((_, sum))) if poll.voting_mode == VotingMode::Transfer =>
+					*sum = sum.saturating_add(commitment.stake),
+				_ => {},
+			}
+		}
+		(owed, entries)
+	}
+
+	/// Moves funds owed by the poll from the shared pot to the pot of the poll, as much as the
+	/// shared pot can afford. The pot of the poll is kept alive until the poll is reaped.
+	fn migrate_pot<T: Config>(
+		poll_id: T::PollIndex,
+		currency: PollCurrency<AssetIdOf<T>>,
+		owed: BalanceOf<T>,
+	) {
+		let poll_pot = Pallet::<T>::pot_account_id(poll_id);
+		frame_system::Pallet::<T>::inc_providers(&poll_pot);
+		let amount = owed.min(shared_pot::<T>(currency));
+		if amount < owed {
+			log::warn!(
+				target: LOG_TARGET,
+				"Shared pot can't afford funds owed by poll {:?}",
+				poll_id,
+			);
+		}
+		if amount.is_zero() {
+			return
+		}
+		let pot = Pallet::<T>::account_id();
+		if Pallet::<T>::transfer_balance(&pot, &poll_pot, currency, amount).is_err() {
+			log::warn!(target: LOG_TARGET, "Funds of poll {:?} can't be moved to its pot", poll_id);
+		}
+	}
+
+	/// Splits the shared pot into pots of polls, each getting the funds it owes. Rounding dust
+	/// is left in the shared pot.
+	pub struct MigrateToV9<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 8 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v9, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			// Funds owed by polls are added up first, as votes and commitments of all polls are
+			// iterated to find them.
+			let (owed, entries) = owed_by_polls::<T>();
+			let mut migrated = 0u64;
+			for (poll_id, (currency, owed)) in owed {
+				migrated.saturating_inc();
+				migrate_pot::<T>(poll_id, currency, owed);
+			}
+			StorageVersion::new(9).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Migrated pots of {} polls to v9", migrated);
+			let reads = migrated.saturating_mul(5).saturating_add(entries).saturating_add(1);
+			T::DbWeight::get().reads_writes(reads, migrated.saturating_mul(3).saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			let polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			Ok(polls.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 9,
+				"storage version should be at least 9 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			ensure!(PollDetailsOf::<T>::iter_keys().count() as u32 <= polls, "polls were added");
+			// Pots of polls should afford what polls owe.
+			Pallet::<T>::do_try_state()
+		}
+	}
+}
+
+/// Migration of the storage to version 10, where accounts with entries in a poll are indexed by
+/// the poll.
+pub mod v10 {
+	use super::*;
+
+	/// Indexes participants of polls from their votes, commitments and allowlist entries. The
+	/// settlement and the reaping are resumed from the start of the poll, as their cursors point
+	/// to keys of the previous storage.
+	pub struct MigrateToV10<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV10<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 9 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping migration to v10, on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+			let mut indexed = 0u64;
+			let keys = VotingOf::<T>::iter_keys()
+				.chain(CommitmentsOf::<T>::iter_keys())
+				.chain(AllowlistOf::<T>::iter_keys());
+			for (who, poll_id) in keys {
+				indexed.saturating_inc();
+				ParticipantsOf::<T>::insert(poll_id, who, ());
+			}
+			SettlementCursorOf::<T>::kill();
+			if let Some((phase, _)) = ReapCursorOf::<T>::get() {
+				ReapCursorOf::<T>::put((phase, None::<SettlementCursor>));
+			}
+			StorageVersion::new(10).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Indexed {} participants of polls in v10", indexed);
+			T::DbWeight::get().reads_writes(indexed + 2, indexed + 3)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			// Polls may still be in the layout of version 0, so only keys are counted.
+			let polls = PollDetailsOf::<T>::iter_keys().count() as u32;
+			Ok(polls.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let polls: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "state of pre_upgrade is invalid")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 10,
+				"storage version should be 10 after the migration",
+			);
+			// Migration to version 1 runs before and might have removed polls.
+			ensure!(PollDetailsOf::<T>::iter_keys().count() as u32 <= polls, "polls were added");
+			// Accounts with entries in polls should be their participants.
+			Pallet::<T>::do_try_state()
+		}
+	}
+}
@@ -0,0 +1,144 @@
+//! Tests for IPFS CID decoding.
+
+use super::*;
+use crate::cid::{codec, hash, Cid, CidError};
+
+/// Binary CIDv1 of `cid()`.
+const BINARY_CID: [u8; 36] = [
+	0x01, 0x70, 0x12, 0x20, 0x9d, 0x6c, 0x2b, 0xe5, 0x0f, 0x70, 0x69, 0x53, 0x47, 0x9a, 0xb9, 0xdf,
+	0x2c, 0xe3, 0xed, 0xca, 0x90, 0xb6, 0x80, 0x53, 0xc0, 0x0b, 0x30, 0x04, 0xb7, 0xf0, 0xac, 0xcb,
+	0xe1, 0xe8, 0xee, 0xdf,
+];
+
+fn binary_cid(codec: &[u8], hash: &[u8], digest_len: usize) -> Vec<u8> {
+	let mut bytes = vec![0x01];
+	bytes.extend_from_slice(codec);
+	bytes.extend_from_slice(hash);
+	bytes.push(digest_len as u8);
+	bytes.extend((0..digest_len).map(|i| i as u8));
+	bytes
+}
+
+fn base16(bytes: &[u8]) -> Vec<u8> {
+	let mut encoded = b"f".to_vec();
+	encoded.extend(bytes.iter().flat_map(|b| format!("{:02x}", b).into_bytes()));
+	encoded
+}
+
+fn create_poll_with_cid(ipfs_cid: Vec<u8>) -> DispatchResult {
+	FateriumPolls::create_poll(
+		Origin::signed(1),
+		ipfs_cid,
+		vec![],
+		RewardSettings::None,
+		10,
+		GoalMode::TotalCapital,
+		3,
+		false,
+		true,
+		TiePolicy::Refund,
+		VotingMode::Transfer,
+		None,
+		Eligibility::Anyone,
+		PollKind::Plurality,
+		PollCurrency::Native,
+		1,
+		10,
+	)
+}
+
+#[test]
+fn cid_should_be_normalized() {
+	let cids: [&[u8]; 6] = [
+		b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+		b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34",
+		b"BAFYBEIE5NQV6KD3QNFJUPGVZ34WOH3OKSC3IAU6ABMYAJN7QVTF6D2HO34",
+		b"zdj7Wg2Qkk4mYgAkVU1kppfQ2sMGz5zPwERVpeWmxCQLDxVoC",
+		b"f017012209d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf",
+		&BINARY_CID,
+	];
+	for ipfs_cid in cids {
+		let cid = Cid::parse(ipfs_cid).unwrap();
+		assert_eq!(cid.codec, codec::DAG_PB);
+		assert_eq!(cid.hash, hash::SHA2_256);
+		assert_eq!(cid.to_bytes(), BINARY_CID.to_vec());
+	}
+	assert_eq!(Cid::parse(&cid()).unwrap().version, 0);
+	assert_eq!(Cid::parse(&BINARY_CID).unwrap().version, 1);
+}
+
+#[test]
+fn invalid_cid_should_fail() {
+	// Unknown multibase, wrong alphabet and non-zero padding bits
+	assert_eq!(Cid::parse(&(0..46).collect::<Vec<u8>>()), Err(CidError::InvalidEncoding));
+	assert_eq!(
+		Cid::parse(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0"),
+		Err(CidError::InvalidEncoding)
+	);
+	assert_eq!(
+		Cid::parse(b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho35"),
+		Err(CidError::InvalidEncoding)
+	);
+	assert_eq!(
+		Cid::parse(b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2hO34"),
+		Err(CidError::InvalidEncoding)
+	);
+	assert_eq!(Cid::parse(b"f0170122"), Err(CidError::InvalidMultihash));
+	// Unsupported version
+	let mut v2 = BINARY_CID.to_vec();
+	v2[0] = 0x02;
+	assert_eq!(Cid::parse(&base16(&v2)), Err(CidError::UnsupportedVersion));
+	// Truncated digest and digest length not matching the hash function
+	assert_eq!(Cid::parse(&BINARY_CID[..35]), Err(CidError::InvalidMultihash));
+	assert_eq!(Cid::parse(&binary_cid(&[0x70], &[0x12], 20)), Err(CidError::InvalidMultihash));
+	// Non-minimal varint
+	assert_eq!(Cid::parse(&binary_cid(&[0xf0, 0x00], &[0x12], 32)), Err(CidError::InvalidEncoding));
+}
+
+#[test]
+fn create_poll_should_store_normalized_cid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(create_poll_with_cid(
+			b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34".to_vec()
+		));
+		let poll = FateriumPolls::poll_details_of(1).unwrap();
+		assert_eq!(poll.ipfs_cid.to_vec(), BINARY_CID.to_vec());
+		System::assert_has_event(Event::FateriumPolls(crate::Event::Created {
+			poll_id: 1,
+			cid: BINARY_CID.to_vec(),
+			creator: 1,
+		}));
+		// Allowed codec and hash function
+		assert_ok!(create_poll_with_cid(binary_cid(&[0x55], &[0xa0, 0xe4, 0x02], 32)));
+	});
+}
+
+#[test]
+fn create_poll_with_invalid_cid_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			create_poll_with_cid(
+				b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho3".to_vec()
+			),
+			Error::<Test>::InvalidCidEncoding
+		);
+		assert_noop!(
+			create_poll_with_cid(BINARY_CID[..35].to_vec()),
+			Error::<Test>::InvalidCidMultihash
+		);
+		let mut v2 = BINARY_CID.to_vec();
+		v2[0] = 0x02;
+		assert_noop!(create_poll_with_cid(base16(&v2)), Error::<Test>::UnsupportedCidVersion);
+		// dag-cbor isn't allowed in the mock
+		assert_noop!(
+			create_poll_with_cid(binary_cid(&[0x71], &[0x12], 32)),
+			Error::<Test>::UnsupportedCidCodec
+		);
+		// sha2-512 isn't allowed in the mock
+		assert_noop!(
+			create_poll_with_cid(binary_cid(&[0x70], &[0x13], 64)),
+			Error::<Test>::UnsupportedCidHash
+		);
+	});
+}
@@ -0,0 +1,164 @@
+//! Tests for the eligibility rules of polls.
+
+use super::*;
+use sp_core::hashing::blake2_256;
+
+/// Creates a poll like `begin_poll`, where only eligible accounts can vote.
+fn begin_poll_with_eligibility(eligibility: Eligibility<u32, Balance>) -> PollIndex {
+	begin_poll_with(1, PollSettings { eligibility, ..Default::default() })
+}
+
+/// Returns the Merkle leaf of the account.
+fn leaf(who: u64) -> H256 {
+	H256(blake2_256(&who.encode()))
+}
+
+/// Returns the Merkle node with the given children.
+fn node(a: H256, b: H256) -> H256 {
+	let (left, right) = if a <= b { (a, b) } else { (b, a) };
+	H256(blake2_256(&[left.as_bytes(), right.as_bytes()].concat()))
+}
+
+#[test]
+fn allowlist_eligibility_should_work() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let pid = begin_poll_with_eligibility(Eligibility::Allowlist);
+		let votes = make_votes(vec![5, 0, 0]);
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(2), pid, votes.clone()),
+			Error::<Test>::NotEligible,
+		);
+		assert_noop!(
+			FateriumPolls::update_allowlist(Origin::signed(2), pid, vec![2], true),
+			Error::<Test>::AccountNotAuthor,
+		);
+		assert_noop!(
+			FateriumPolls::prove_eligibility(Origin::signed(2), pid, vec![]),
+			Error::<Test>::InvalidEligibilityRule,
+		);
+		assert_ok!(FateriumPolls::update_allowlist(Origin::signed(1), pid, vec![2, 3], true));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::AllowlistUpdated {
+			poll_id: pid,
+			accounts: vec![2, 3],
+			allowed: true,
+		}));
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, votes.clone()));
+		// Removed accounts can't vote anymore
+		assert_ok!(FateriumPolls::update_allowlist(Origin::signed(1), pid, vec![3], false));
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(3), pid, votes.clone()),
+			Error::<Test>::NotEligible,
+		);
+		// Allowlist can be updated only in polls with the allowlist rule
+		let public = begin_poll(1, vec![], 10, true);
+		assert_noop!(
+			FateriumPolls::update_allowlist(Origin::signed(1), public, vec![3], true),
+			Error::<Test>::InvalidEligibilityRule,
+		);
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), public, votes));
+	});
+}
+
+#[test]
+fn merkle_eligibility_should_work() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(6);
+		let root = node(node(leaf(2), leaf(3)), node(leaf(4), leaf(5)));
+		let pid = begin_poll_with_eligibility(Eligibility::MerkleRoot(root));
+		let votes = make_votes(vec![5, 0, 0]);
+		let proof = vec![leaf(3), node(leaf(4), leaf(5))];
+		assert_eq!(FateriumPolls::merkle_root(&2, &proof), root);
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(2), pid, votes.clone()),
+			Error::<Test>::NotEligible,
+		);
+		// Proofs are valid only for their leaves
+		assert_noop!(
+			FateriumPolls::prove_eligibility(Origin::signed(6), pid, proof.clone()),
+			Error::<Test>::InvalidMerkleProof,
+		);
+		assert_noop!(
+			FateriumPolls::prove_eligibility(Origin::signed(2), pid, vec![leaf(3)]),
+			Error::<Test>::InvalidMerkleProof,
+		);
+		assert_noop!(
+			FateriumPolls::update_allowlist(Origin::signed(1), pid, vec![6], true),
+			Error::<Test>::InvalidEligibilityRule,
+		);
+		assert_ok!(FateriumPolls::prove_eligibility(Origin::signed(2), pid, proof));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::EligibilityProven {
+			who: 2,
+			poll_id: pid,
+		}));
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, votes.clone()));
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(6), pid, votes),
+			Error::<Test>::NotEligible,
+		);
+	});
+}
+
+#[test]
+fn holder_eligibility_should_work() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 5));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 3, 4));
+		let votes = make_votes(vec![5, 0, 0]);
+		// Only accounts with enough assets can vote
+		let pid =
+			begin_poll_with_eligibility(Eligibility::AssetHolder { asset_id: 0, min_balance: 5 });
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, votes.clone()));
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(3), pid, votes.clone()),
+			Error::<Test>::NotEligible,
+		);
+		// Only members of the collection can vote
+		let pid = begin_poll_with_eligibility(Eligibility::CollectionMember(0));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, votes.clone()));
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(4), pid, votes.clone()),
+			Error::<Test>::NotEligible,
+		);
+		// Eligibility is checked for commitments to sealed polls too
+		let sealed = Some(SealedSettings { reveal_period: 5, unrevealed_penalty: 0 });
+		let eligibility = Eligibility::CollectionMember(0);
+		let pid = begin_poll_with(1, PollSettings { sealed, eligibility, ..Default::default() });
+		let hash = BlakeTwo256::hash_of(&(&votes, [0u8; 32]));
+		assert_noop!(
+			FateriumPolls::commit_votes(Origin::signed(4), pid, hash, 5),
+			Error::<Test>::NotEligible,
+		);
+		assert_ok!(FateriumPolls::commit_votes(Origin::signed(2), pid, hash, 5));
+		// Asset of holders should exist
+		System::set_block_number(0);
+		assert_noop!(
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid(),
+				vec![],
+				RewardSettings::None,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				true,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Transfer,
+				None,
+				Eligibility::AssetHolder { asset_id: 7, min_balance: 1 },
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			),
+			Error::<Test>::InvalidPollDetails,
+		);
+	});
+}
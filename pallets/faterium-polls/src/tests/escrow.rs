@@ -0,0 +1,344 @@
+//! Tests for the milestone-based escrow of the beneficiaries' interest.
+
+use super::*;
+
+/// Creates a poll like `begin_poll` with beneficiary 5 taking 50%, and sets its milestones
+/// before the start. Accounts 2 and 3 vote for the winning option, and account 4 against it.
+fn begin_poll_with_milestones(milestones: Vec<u32>) -> PollIndex {
+	for acc in [2, 3, 4] {
+		set_balances(acc);
+	}
+	let pid = create_poll_with_milestones(milestones);
+	assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+	assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, make_votes(vec![4, 0, 0])));
+	assert_ok!(FateriumPolls::vote(Origin::signed(4), pid, make_votes(vec![0, 6, 0])));
+	fast_forward_to(10);
+	pid
+}
+
+/// Creates a poll with milestones like `begin_poll_with_milestones`, and starts it without votes.
+fn create_poll_with_milestones(milestones: Vec<u32>) -> PollIndex {
+	System::set_block_number(0);
+	assert_ok!(FateriumPolls::create_poll(
+		Origin::signed(1),
+		cid(),
+		vec![(5, 5000, None)],
+		RewardSettings::None,
+		10,
+		GoalMode::TotalCapital,
+		3,
+		false,
+		true,
+		TiePolicy::Refund,
+		VotingMode::Transfer,
+		None,
+		Eligibility::Anyone,
+		PollKind::Plurality,
+		PollCurrency::Native,
+		1,
+		10,
+	));
+	let pid = FateriumPolls::poll_count();
+	assert_ok!(FateriumPolls::set_milestones(Origin::signed(1), pid, milestones.clone()));
+	System::assert_last_event(Event::FateriumPolls(crate::Event::MilestonesSet {
+		poll_id: pid,
+		milestones,
+	}));
+	fast_forward_to(2);
+	pid
+}
+
+/// Runs the sign-off of the next milestone, where voter 2 approves and voter 3 rejects it.
+fn sign_off_milestone(pid: PollIndex, approve: bool) {
+	assert_ok!(FateriumPolls::request_sign_off(Origin::signed(1), pid));
+	assert_ok!(FateriumPolls::sign_off(Origin::signed(3), pid, false));
+	if approve {
+		assert_ok!(FateriumPolls::sign_off(Origin::signed(2), pid, true));
+	}
+	fast_forward_to(System::block_number() + 5);
+	assert_ok!(FateriumPolls::close_sign_off(Origin::signed(4), pid));
+}
+
+#[test]
+fn milestones_should_release_interest() {
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll_with_milestones(vec![4000, 6000]);
+		// Nothing is released before the first milestone is approved
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(5), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		// Voters collect their stakes without the interest as usual
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), 15);
+		assert_ok!(FateriumPolls::request_sign_off(Origin::signed(1), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::SignOffStarted {
+			poll_id: pid,
+			milestone: 0,
+			end: 15,
+		}));
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(1), pid),
+			Error::<Test>::SignOffInProgress,
+		);
+		// Only voters of the winning option can sign off
+		assert_noop!(
+			FateriumPolls::sign_off(Origin::signed(4), pid, false),
+			Error::<Test>::VotesNotExist,
+		);
+		assert_ok!(FateriumPolls::sign_off(Origin::signed(2), pid, true));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::SignedOff {
+			voter: 2,
+			poll_id: pid,
+			milestone: 0,
+			approve: true,
+			stake: 10,
+		}));
+		assert_noop!(
+			FateriumPolls::sign_off(Origin::signed(2), pid, false),
+			Error::<Test>::AlreadySignedOff,
+		);
+		assert_ok!(FateriumPolls::sign_off(Origin::signed(3), pid, false));
+		assert_noop!(
+			FateriumPolls::close_sign_off(Origin::signed(4), pid),
+			Error::<Test>::SignOffNotEnded,
+		);
+		fast_forward_to(15);
+		assert_noop!(
+			FateriumPolls::sign_off(Origin::signed(3), pid, true),
+			Error::<Test>::SignOffEnded,
+		);
+		assert_ok!(FateriumPolls::close_sign_off(Origin::signed(4), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::MilestoneApproved {
+			poll_id: pid,
+			milestone: 0,
+		}));
+		// 40% of the interest of 7 is released
+		assert_eq!(FateriumPolls::claimable(&5, pid).unwrap().beneficiary_interest, 2);
+		assert_ok!(FateriumPolls::collect(Origin::signed(5), pid));
+		assert_eq!(Balances::free_balance(5), 2);
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap().beneficiaries[0].collected, 2);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(5), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		// The last milestone releases the rest of the interest
+		sign_off_milestone(pid, true);
+		assert_ok!(FateriumPolls::collect(Origin::signed(5), pid));
+		assert_eq!(Balances::free_balance(5), 7);
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap().beneficiaries[0].collected, 7);
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(1), pid),
+			Error::<Test>::EscrowSettled,
+		);
+		assert_ok!(FateriumPolls::collect(Origin::signed(3), pid));
+		assert_ok!(FateriumPolls::collect(Origin::signed(4), pid));
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+	});
+}
+
+#[test]
+fn rejected_milestone_should_refund_voters() {
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll_with_milestones(vec![4000, 6000]);
+		sign_off_milestone(pid, true);
+		assert_ok!(FateriumPolls::collect(Origin::signed(5), pid));
+		assert_eq!(Balances::free_balance(5), 2);
+		// Voter 3 alone rejects the last milestone
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		sign_off_milestone(pid, false);
+		System::assert_last_event(Event::FateriumPolls(crate::Event::MilestoneRejected {
+			poll_id: pid,
+			milestone: 1,
+		}));
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(5), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(1), pid),
+			Error::<Test>::EscrowSettled,
+		);
+		// The rest of the escrow of 5 is shared by voters of the winning option
+		assert_eq!(FateriumPolls::claimable(&2, pid).unwrap().escrow_refund, 3);
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), 18);
+		assert_ok!(FateriumPolls::collect(Origin::signed(3), pid));
+		assert_eq!(Balances::free_balance(3), 19);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(3), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		// Losing voters don't share the escrow
+		assert_ok!(FateriumPolls::collect(Origin::signed(4), pid));
+		assert_eq!(Balances::free_balance(4), 20);
+		// Only the rounding dust is left in the pot
+		assert_eq!(FateriumPolls::balances_pot(pid), 1);
+	});
+}
+
+#[test]
+fn escrow_refund_should_be_paid_back_to_delegators() {
+	new_test_ext().execute_with(|| {
+		let (voter, delegator, currency) = (2, 6, PollCurrency::Native);
+		for acc in [delegator, 3, 4] {
+			set_balances(acc);
+		}
+		// The stake of voter 2 is paid by the delegator alone
+		assert_ok!(FateriumPolls::delegate(Origin::signed(delegator), voter, currency, 10));
+		let pid = create_poll_with_milestones(vec![4000, 6000]);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, make_votes(vec![4, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(4), pid, make_votes(vec![0, 6, 0])));
+		assert_eq!(Balances::free_balance(delegator), 10);
+		fast_forward_to(10);
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
+		assert_eq!(Balances::free_balance(delegator), 15);
+		sign_off_milestone(pid, true);
+		sign_off_milestone(pid, false);
+		// The escrow refund of the voter goes to the delegator as well
+		assert_eq!(FateriumPolls::claimable(&voter, pid).unwrap().escrow_refund, 3);
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
+		System::assert_has_event(Event::FateriumPolls(crate::Event::DelegatorPaid {
+			delegator,
+			delegate: voter,
+			poll_id: pid,
+			amount: 3,
+		}));
+		assert_eq!(Balances::free_balance(delegator), 18);
+		assert_eq!(Balances::free_balance(voter), 0);
+	});
+}
+
+#[test]
+fn milestones_should_fail() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		System::set_block_number(0);
+		let create = |voting_mode| {
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid(),
+				vec![(5, 5000, None)],
+				RewardSettings::None,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				false,
+				true,
+				TiePolicy::Refund,
+				voting_mode,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			)
+		};
+		assert_ok!(create(VotingMode::Lock));
+		let locked = FateriumPolls::poll_count();
+		assert_noop!(
+			FateriumPolls::set_milestones(Origin::signed(1), locked, vec![10_000]),
+			Error::<Test>::InvalidMilestones,
+		);
+		assert_ok!(create(VotingMode::Transfer));
+		let pid = FateriumPolls::poll_count();
+		assert_noop!(
+			FateriumPolls::set_milestones(Origin::signed(2), pid, vec![10_000]),
+			Error::<Test>::AccountNotAuthor,
+		);
+		assert_noop!(
+			FateriumPolls::set_milestones(Origin::signed(1), pid, vec![5000, 4000]),
+			Error::<Test>::InvalidMilestones,
+		);
+		assert_noop!(
+			FateriumPolls::set_milestones(Origin::signed(1), pid, vec![0, 10_000]),
+			Error::<Test>::InvalidMilestones,
+		);
+		assert_noop!(
+			FateriumPolls::set_milestones(Origin::signed(1), pid, vec![2000; 5]),
+			Error::<Test>::TooManyMilestones,
+		);
+		// Milestones can be removed before the start
+		assert_ok!(FateriumPolls::set_milestones(Origin::signed(1), pid, vec![10_000]));
+		assert_ok!(FateriumPolls::set_milestones(Origin::signed(1), pid, vec![]));
+		assert_eq!(FateriumPolls::escrows_of(pid), None);
+		assert_ok!(FateriumPolls::set_milestones(Origin::signed(1), pid, vec![10_000]));
+		fast_forward_to(2);
+		assert_noop!(
+			FateriumPolls::set_milestones(Origin::signed(1), pid, vec![]),
+			Error::<Test>::PollAlreadyStarted,
+		);
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(1), pid),
+			Error::<Test>::CollectOnOngoingPoll,
+		);
+		assert_noop!(
+			FateriumPolls::sign_off(Origin::signed(2), pid, true),
+			Error::<Test>::SignOffNotInProgress,
+		);
+		// Interest of a failed poll goes to nobody
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		fast_forward_to(10);
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(2), pid),
+			Error::<Test>::AccountNotAuthor,
+		);
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(1), pid),
+			Error::<Test>::NoWinningOptions,
+		);
+		let public = begin_poll(1, vec![], 10, false);
+		fast_forward_to(10);
+		assert_noop!(
+			FateriumPolls::close_sign_off(Origin::signed(1), public),
+			Error::<Test>::EscrowNotExist,
+		);
+	});
+}
+
+#[test]
+fn poll_should_be_reapable_once_escrow_is_settled() {
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll_with_milestones(vec![4000, 6000]);
+		sign_off_milestone(pid, true);
+		FateriumPolls::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+		// The last milestone is still held in escrow, however late
+		fast_forward_to(40);
+		assert_noop!(
+			FateriumPolls::reap_poll(Origin::signed(6), pid),
+			Error::<Test>::InterestNotReleased,
+		);
+		sign_off_milestone(pid, true);
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+	});
+}
+
+#[test]
+fn escrow_should_be_rejected_if_creator_does_nothing() {
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll_with_milestones(vec![4000, 6000]);
+		FateriumPolls::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+		// Only the creator can start the sign-off before the deadline of the milestone
+		fast_forward_to(39);
+		assert_noop!(
+			FateriumPolls::request_sign_off(Origin::signed(4), pid),
+			Error::<Test>::AccountNotAuthor,
+		);
+		fast_forward_to(40);
+		assert_ok!(FateriumPolls::request_sign_off(Origin::signed(4), pid));
+		// Nobody approves the milestone, so it's rejected
+		fast_forward_to(45);
+		assert_ok!(FateriumPolls::close_sign_off(Origin::signed(4), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::MilestoneRejected {
+			poll_id: pid,
+			milestone: 0,
+		}));
+		// The whole escrow of 7 is returned to voters, and the poll can be reaped
+		assert_eq!(FateriumPolls::claimable(&2, pid).unwrap().escrow_refund, 5);
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), 20);
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+	});
+}
@@ -0,0 +1,140 @@
+//! Tests for goal modes, deciding whether polls end as finished or as failed.
+
+use super::*;
+
+type TestGoalMode = GoalMode<Balance, MaxPollOptions>;
+
+fn per_option(goals: Vec<Balance>) -> TestGoalMode {
+	GoalMode::PerOption(goals.try_into().unwrap())
+}
+
+fn begin_poll_with_goal(goal: Balance, goal_mode: TestGoalMode) -> PollIndex {
+	begin_poll_with(1, PollSettings { goal, goal_mode, ..Default::default() })
+}
+
+fn status(pid: PollIndex) -> PollStatus<u64> {
+	FateriumPolls::poll_details_of(pid).unwrap().status
+}
+
+#[test]
+fn total_capital_goal_should_count_all_options() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pid = begin_poll_with_goal(10, GoalMode::TotalCapital);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![3, 4, 3])));
+		fast_forward_to(10);
+		assert_eq!(status(pid), PollStatus::Finished { winning_option: 1, end: 10 });
+	});
+}
+
+#[test]
+fn winning_capital_goal_should_be_reached_by_winner() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let failed = begin_poll_with_goal(10, GoalMode::WinningCapital);
+		let finished = begin_poll_with_goal(10, GoalMode::WinningCapital);
+		// Capital of all options reaches the goal, but the stake on the winner doesn't
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), failed, make_votes(vec![6, 5, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), finished, make_votes(vec![0, 0, 10])));
+		fast_forward_to(10);
+		assert_eq!(status(failed), PollStatus::Failed(10));
+		assert_eq!(status(finished), PollStatus::Finished { winning_option: 2, end: 10 });
+		// Stakes of the failed poll are returned
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), failed));
+		assert_eq!(Balances::free_balance(2), 20);
+	});
+}
+
+#[test]
+fn per_option_goals_should_be_reached_by_winner() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let failed = begin_poll_with_goal(1, per_option(vec![8, 3, 20]));
+		let finished = begin_poll_with_goal(1, per_option(vec![5, 10, 1]));
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), failed, make_votes(vec![6, 4, 0])));
+		// Option 1 is tied with option 0, but doesn't reach its own goal
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), finished, make_votes(vec![6, 6, 0])));
+		fast_forward_to(10);
+		assert_eq!(status(failed), PollStatus::Failed(10));
+		assert_eq!(status(finished), PollStatus::Finished { winning_option: 0, end: 10 });
+	});
+}
+
+#[test]
+fn split_interest_should_skip_tied_options_below_their_goals() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let settings = PollSettings {
+			bnfs: vec![(5, 5000)],
+			goal: 1,
+			goal_mode: per_option(vec![1, 1, 5]),
+			tie_policy: TiePolicy::SplitInterest,
+			..Default::default()
+		};
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![4, 4, 4])));
+		fast_forward_to(10);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Tied(10));
+		assert_eq!(poll.winning_options(), vec![0, 1]);
+		// Interest is paid from the stakes on the winning options only
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), 16);
+	});
+}
+
+#[test]
+fn threshold_goal_should_be_reached_by_winner() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let pids: Vec<_> =
+			(0..3).map(|_| begin_poll_with_goal(1, GoalMode::Threshold(6600))).collect();
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pids[0], make_votes(vec![6, 4, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pids[1], make_votes(vec![1, 7, 2])));
+		// Two thirds are above the threshold, even though the share is rounded down
+		assert_ok!(FateriumPolls::vote(Origin::signed(4), pids[2], make_votes(vec![0, 1, 2])));
+		fast_forward_to(10);
+		assert_eq!(status(pids[0]), PollStatus::Failed(10));
+		assert_eq!(status(pids[1]), PollStatus::Finished { winning_option: 1, end: 10 });
+		assert_eq!(status(pids[2]), PollStatus::Finished { winning_option: 2, end: 10 });
+	});
+}
+
+#[test]
+fn goal_mode_should_be_validated_on_creation() {
+	new_test_ext().execute_with(|| {
+		for (goal_mode, error) in [
+			(per_option(vec![1, 1]), Error::<Test>::InvalidPollDetails),
+			(per_option(vec![1, 0, 1]), Error::<Test>::PollGoalTooLow),
+			(GoalMode::Threshold(0), Error::<Test>::InvalidPollDetails),
+			(GoalMode::Threshold(10_001), Error::<Test>::InvalidPollDetails),
+		] {
+			assert_noop!(
+				FateriumPolls::create_poll(
+					Origin::signed(1),
+					cid(),
+					vec![],
+					RewardSettings::None,
+					10,
+					goal_mode,
+					3,
+					true,
+					true,
+					TiePolicy::Refund,
+					VotingMode::Transfer,
+					None,
+					Eligibility::Anyone,
+					PollKind::Plurality,
+					PollCurrency::Native,
+					1,
+					10,
+				),
+				error,
+			);
+		}
+	});
+}
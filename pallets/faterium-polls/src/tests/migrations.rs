@@ -0,0 +1,540 @@
+//! Tests for the storage migrations.
+
+use super::*;
+use crate::migrations::{
+	v1::{BeneficiaryV0, PollDetailsV1, PollDetailsV1Of},
+	v3::{PollDetailsV3, PollDetailsV3Of},
+	v4::{PollDetailsV4, PollDetailsV4Of},
+	v5::{PollDetailsV5, PollDetailsV5Of},
+	v6::{PollDetailsV6, PollDetailsV6Of},
+	v7::{PollDetailsV7, PollDetailsV7Of},
+};
+use frame_support::{
+	storage::unhashed,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+
+/// Puts poll details encoded in the layout of storage version 0.
+fn put_v0_poll(pid: PollIndex, bnfs: Vec<BeneficiaryV0<u64>>) {
+	let old_poll = (
+		1u64,
+		(0..46).collect::<Vec<u8>>(),
+		bnfs,
+		// RewardSettings::None
+		0u8,
+		10u64,
+		3u8,
+		true,
+		vec![10u64, 0, 0],
+		PollCurrency::<u32>::Native,
+		PollStatus::Ongoing { start: 1u64, end: 10u64 },
+	);
+	unhashed::put(&PollDetailsOf::<Test>::hashed_key_for(pid), &old_poll);
+}
+
+/// Returns a beneficiary in the layout of storage versions 0 to 5.
+fn bnf_v0(who: u64, interest: u32) -> BeneficiaryV0<u64> {
+	BeneficiaryV0 { who, interest, collected: 0 }
+}
+
+/// Converts beneficiaries into the layout of storage versions 0 to 5.
+fn to_v0_bnfs(
+	bnfs: BoundedVec<BeneficiaryOf<Test>, ConstU32<10>>,
+) -> BoundedVec<BeneficiaryV0<u64>, ConstU32<10>> {
+	let bnfs: Vec<_> = bnfs
+		.into_iter()
+		.map(|b| BeneficiaryV0 {
+			who: b.who,
+			interest: b.interest,
+			collected: (b.collected > 0).into(),
+		})
+		.collect();
+	bnfs.try_into().unwrap()
+}
+
+/// Returns poll details stored in the layout of storage versions 1 and 2.
+fn v1_poll(pid: PollIndex) -> Option<PollDetailsV1Of<Test>> {
+	unhashed::get(&PollDetailsOf::<Test>::hashed_key_for(pid))
+}
+
+/// Puts poll details encoded in the layout of storage versions 1 and 2.
+fn put_v1_poll(pid: PollIndex, poll: &PollDetailsV1Of<Test>) {
+	unhashed::put(&PollDetailsOf::<Test>::hashed_key_for(pid), poll);
+}
+
+/// Converts poll details into the layout of storage versions 1 and 2.
+fn to_v1(poll: PollTypeOf<Test>) -> PollDetailsV1Of<Test> {
+	PollDetailsV1 {
+		created_by: poll.created_by,
+		deposit: poll.deposit,
+		ipfs_cid: poll.ipfs_cid,
+		beneficiaries: to_v0_bnfs(poll.beneficiaries),
+		reward_settings: poll.reward_settings,
+		reward_pool_collected: poll.reward_pool_collected,
+		goal: poll.goal,
+		options_count: poll.options_count,
+		multiple_votes: poll.multiple_votes,
+		tie_policy: poll.tie_policy,
+		voting_mode: poll.voting_mode,
+		votes: poll.votes,
+		currency: poll.currency,
+		status: poll.status,
+	}
+}
+
+/// Converts poll details into the layout of storage version 3.
+fn to_v3(poll: PollTypeOf<Test>) -> PollDetailsV3Of<Test> {
+	PollDetailsV3 {
+		created_by: poll.created_by,
+		deposit: poll.deposit,
+		ipfs_cid: poll.ipfs_cid,
+		beneficiaries: to_v0_bnfs(poll.beneficiaries),
+		reward_settings: poll.reward_settings,
+		reward_pool_collected: poll.reward_pool_collected,
+		goal: poll.goal,
+		options_count: poll.options_count,
+		multiple_votes: poll.multiple_votes,
+		movable_votes: poll.movable_votes,
+		tie_policy: poll.tie_policy,
+		voting_mode: poll.voting_mode,
+		votes: poll.votes,
+		currency: poll.currency,
+		status: poll.status,
+	}
+}
+
+/// Converts poll details into the layout of storage version 5.
+fn to_v5(poll: PollTypeOf<Test>) -> PollDetailsV5Of<Test> {
+	PollDetailsV5 {
+		created_by: poll.created_by,
+		deposit: poll.deposit,
+		ipfs_cid: poll.ipfs_cid,
+		beneficiaries: to_v0_bnfs(poll.beneficiaries),
+		reward_settings: poll.reward_settings,
+		reward_pool_collected: poll.reward_pool_collected,
+		goal: poll.goal,
+		options_count: poll.options_count,
+		multiple_votes: poll.multiple_votes,
+		movable_votes: poll.movable_votes,
+		tie_policy: poll.tie_policy,
+		voting_mode: poll.voting_mode,
+		sealed: poll.sealed,
+		eligibility: poll.eligibility,
+		votes: poll.votes,
+		currency: poll.currency,
+		status: poll.status,
+	}
+}
+
+/// Converts poll details into the layout of storage version 6.
+fn to_v6(poll: PollTypeOf<Test>) -> PollDetailsV6Of<Test> {
+	PollDetailsV6 {
+		created_by: poll.created_by,
+		deposit: poll.deposit,
+		ipfs_cid: poll.ipfs_cid,
+		beneficiaries: poll.beneficiaries,
+		reward_settings: poll.reward_settings,
+		reward_pool_collected: poll.reward_pool_collected,
+		goal: poll.goal,
+		options_count: poll.options_count,
+		multiple_votes: poll.multiple_votes,
+		movable_votes: poll.movable_votes,
+		tie_policy: poll.tie_policy,
+		voting_mode: poll.voting_mode,
+		sealed: poll.sealed,
+		eligibility: poll.eligibility,
+		votes: poll.votes,
+		currency: poll.currency,
+		status: poll.status,
+	}
+}
+
+/// Converts poll details into the layout of storage version 7.
+fn to_v7(poll: PollTypeOf<Test>) -> PollDetailsV7Of<Test> {
+	PollDetailsV7 {
+		created_by: poll.created_by,
+		deposit: poll.deposit,
+		ipfs_cid: poll.ipfs_cid,
+		beneficiaries: poll.beneficiaries,
+		reward_settings: poll.reward_settings,
+		reward_pool_collected: poll.reward_pool_collected,
+		goal: poll.goal,
+		options_count: poll.options_count,
+		multiple_votes: poll.multiple_votes,
+		movable_votes: poll.movable_votes,
+		tie_policy: poll.tie_policy,
+		voting_mode: poll.voting_mode,
+		sealed: poll.sealed,
+		eligibility: poll.eligibility,
+		kind: poll.kind,
+		votes: poll.votes,
+		currency: poll.currency,
+		status: poll.status,
+	}
+}
+
+/// Converts poll details into the layout of storage version 4.
+fn to_v4(poll: PollTypeOf<Test>) -> PollDetailsV4Of<Test> {
+	PollDetailsV4 {
+		created_by: poll.created_by,
+		deposit: poll.deposit,
+		ipfs_cid: poll.ipfs_cid,
+		beneficiaries: to_v0_bnfs(poll.beneficiaries),
+		reward_settings: poll.reward_settings,
+		reward_pool_collected: poll.reward_pool_collected,
+		goal: poll.goal,
+		options_count: poll.options_count,
+		multiple_votes: poll.multiple_votes,
+		movable_votes: poll.movable_votes,
+		tie_policy: poll.tie_policy,
+		voting_mode: poll.voting_mode,
+		sealed: poll.sealed,
+		votes: poll.votes,
+		currency: poll.currency,
+		status: poll.status,
+	}
+}
+
+#[test]
+fn migration_to_v1_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<FateriumPolls>();
+		put_v0_poll(1, vec![bnf_v0(11, 5000)]);
+		unhashed::put(&VotingOf::<Test>::hashed_key_for((2, 1)), &(vec![10u64, 0, 0], false));
+		// Poll with more beneficiaries than the bound can't be kept
+		put_v0_poll(2, (0..11).map(|i| bnf_v0(i, 100)).collect());
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 1);
+		let poll = v1_poll(1).unwrap();
+		assert_eq!(poll.deposit, 0);
+		assert_eq!(poll.ipfs_cid.len(), 46);
+		assert_eq!(poll.beneficiaries.len(), 1);
+		assert_eq!(poll.reward_settings, RewardSettings::None);
+		assert_eq!(poll.tie_policy, TiePolicy::Refund);
+		assert_eq!(poll.voting_mode, VotingMode::Transfer);
+		assert_eq!(poll.votes, make_votes(vec![10, 0, 0]));
+		assert_eq!(poll.status, PollStatus::Ongoing { start: 1, end: 10 });
+		assert!(v1_poll(2).is_none());
+		let voter = FateriumPolls::voting_of((2, 1)).unwrap();
+		assert_eq!(voter.votes, make_votes(vec![10, 0, 0]));
+		assert!(!voter.collected);
+		// Migration runs only once
+		put_v0_poll(3, vec![]);
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert!(v1_poll(3).is_none());
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migration_to_v1_try_runtime_checks_should_pass() {
+	use crate::migrations::{
+		v1::MigrateToV1, v10::MigrateToV10, v2::MigrateToV2, v3::MigrateToV3, v4::MigrateToV4,
+		v5::MigrateToV5, v6::MigrateToV6, v7::MigrateToV7, v8::MigrateToV8, v9::MigrateToV9,
+	};
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<FateriumPolls>();
+		put_v0_poll(1, vec![bnf_v0(11, 5000)]);
+		put_v0_poll(2, (0..11).map(|i| bnf_v0(i, 100)).collect());
+		unhashed::put(&VotingOf::<Test>::hashed_key_for((2, 1)), &(vec![10u64, 0, 0], false));
+		let state = MigrateToV1::<Test>::pre_upgrade().unwrap();
+		// Checks run after all migrations of the runtime, so polls have the current layout.
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		MigrateToV2::<Test>::on_runtime_upgrade();
+		MigrateToV3::<Test>::on_runtime_upgrade();
+		MigrateToV4::<Test>::on_runtime_upgrade();
+		MigrateToV5::<Test>::on_runtime_upgrade();
+		MigrateToV6::<Test>::on_runtime_upgrade();
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		MigrateToV9::<Test>::on_runtime_upgrade();
+		MigrateToV10::<Test>::on_runtime_upgrade();
+		assert_ok!(MigrateToV1::<Test>::post_upgrade(state));
+	});
+}
+
+#[test]
+fn migration_to_v2_should_work() {
+	use crate::migrations::v2::MigrateToV2;
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll(1, vec![], 10, true);
+		let normalized = FateriumPolls::poll_details_of(pid).unwrap().ipfs_cid;
+		// Polls of version 1 have CIDs as they were given
+		let garbage: Vec<u8> = (0..46).collect();
+		let mut poll = to_v1(FateriumPolls::poll_details_of(pid).unwrap());
+		poll.ipfs_cid = cid().try_into().unwrap();
+		put_v1_poll(pid, &poll);
+		poll.ipfs_cid = garbage.clone().try_into().unwrap();
+		put_v1_poll(pid + 1, &poll);
+		StorageVersion::new(1).put::<FateriumPolls>();
+		MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 2);
+		assert_eq!(v1_poll(pid).unwrap().ipfs_cid, normalized);
+		// CIDs which can't be decoded are kept
+		assert_eq!(v1_poll(pid + 1).unwrap().ipfs_cid.to_vec(), garbage);
+	});
+}
+
+#[test]
+fn migration_to_v3_should_work() {
+	use crate::migrations::{
+		v3::MigrateToV3, v4::MigrateToV4, v5::MigrateToV5, v6::MigrateToV6, v7::MigrateToV7,
+		v8::MigrateToV8, v9::MigrateToV9,
+	};
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll_with(1, PollSettings { movable_votes: false, ..Default::default() });
+		set_balances(2);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		put_v1_poll(pid, &to_v1(poll.clone()));
+		StorageVersion::new(2).put::<FateriumPolls>();
+		MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 3);
+		MigrateToV4::<Test>::on_runtime_upgrade();
+		MigrateToV5::<Test>::on_runtime_upgrade();
+		MigrateToV6::<Test>::on_runtime_upgrade();
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		MigrateToV9::<Test>::on_runtime_upgrade();
+		// Votes can be moved in existing polls
+		let migrated = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(migrated, PollDetails { movable_votes: true, ..poll.clone() });
+		assert_ok!(FateriumPolls::move_votes(Origin::signed(2), pid, 0, 1, 5));
+		// Migration runs only once
+		let key = PollDetailsOf::<Test>::hashed_key_for(pid);
+		put_v1_poll(pid, &to_v1(poll.clone()));
+		MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(to_v1(poll).encode()));
+	});
+}
+
+#[test]
+fn migration_to_v4_should_work() {
+	use crate::migrations::{
+		v4::MigrateToV4, v5::MigrateToV5, v6::MigrateToV6, v7::MigrateToV7, v8::MigrateToV8,
+		v9::MigrateToV9,
+	};
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll(1, vec![], 10, true);
+		set_balances(2);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		let key = PollDetailsOf::<Test>::hashed_key_for(pid);
+		unhashed::put(&key, &to_v3(poll.clone()));
+		StorageVersion::new(3).put::<FateriumPolls>();
+		MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 4);
+		MigrateToV5::<Test>::on_runtime_upgrade();
+		MigrateToV6::<Test>::on_runtime_upgrade();
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		MigrateToV9::<Test>::on_runtime_upgrade();
+		// Existing polls are public
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap(), poll);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		// Migration runs only once
+		unhashed::put(&key, &to_v3(poll.clone()));
+		MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(to_v3(poll).encode()));
+	});
+}
+
+#[test]
+fn migration_to_v5_should_work() {
+	use crate::migrations::{
+		v5::MigrateToV5, v6::MigrateToV6, v7::MigrateToV7, v8::MigrateToV8, v9::MigrateToV9,
+	};
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll(1, vec![], 10, true);
+		set_balances(2);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		let key = PollDetailsOf::<Test>::hashed_key_for(pid);
+		unhashed::put(&key, &to_v4(poll.clone()));
+		StorageVersion::new(4).put::<FateriumPolls>();
+		MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 5);
+		MigrateToV6::<Test>::on_runtime_upgrade();
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		MigrateToV9::<Test>::on_runtime_upgrade();
+		// Anyone can vote in existing polls
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap(), poll);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		// Migration runs only once
+		unhashed::put(&key, &to_v4(poll.clone()));
+		MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(to_v4(poll).encode()));
+	});
+}
+
+#[test]
+fn migration_to_v6_should_work() {
+	use crate::migrations::{v6::MigrateToV6, v7::MigrateToV7, v8::MigrateToV8};
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let settings = || PollSettings { bnfs: vec![(11, 5000)], goal: 5, ..Default::default() };
+		let collected = begin_poll_with(1, settings());
+		let escrowed = begin_poll_with(1, settings());
+		let pending = begin_poll_with(1, settings());
+		for pid in [collected, escrowed, pending] {
+			assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![6, 0, 0])));
+		}
+		fast_forward_to(10);
+		let mut escrow: EscrowOf<Test> = Escrow::new(vec![4000, 6000].try_into().unwrap());
+		escrow.released = 1;
+		EscrowsOf::<Test>::insert(escrowed, escrow);
+		let mut polls = vec![];
+		for pid in [collected, escrowed, pending] {
+			let poll = FateriumPolls::poll_details_of(pid).unwrap();
+			let mut old = to_v5(poll.clone());
+			// Beneficiary collected the whole interest, or the first milestone of the escrow
+			old.beneficiaries[0].collected = (pid != pending).into();
+			unhashed::put(&PollDetailsOf::<Test>::hashed_key_for(pid), &old);
+			polls.push(poll);
+		}
+		StorageVersion::new(5).put::<FateriumPolls>();
+		MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 6);
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		// Collected interest is stored instead of the flag
+		let bnf = |pid| FateriumPolls::poll_details_of(pid).unwrap().beneficiaries[0].clone();
+		assert_eq!(bnf(collected).collected, 3);
+		assert_eq!(bnf(escrowed).collected, 1);
+		assert_eq!(bnf(pending).collected, 0);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(11), collected),
+			Error::<Test>::NothingToCollect,
+		);
+		assert_ok!(FateriumPolls::collect(Origin::signed(11), pending));
+		assert_eq!(Balances::free_balance(11), 3);
+		// Migration runs only once
+		let key = PollDetailsOf::<Test>::hashed_key_for(collected);
+		unhashed::put(&key, &to_v5(polls[0].clone()));
+		MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(to_v5(polls[0].clone()).encode()));
+	});
+}
+
+#[test]
+fn migration_to_v7_should_work() {
+	use crate::migrations::{v7::MigrateToV7, v8::MigrateToV8};
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll(1, vec![], 10, true);
+		set_balances(2);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		let key = PollDetailsOf::<Test>::hashed_key_for(pid);
+		unhashed::put(&key, &to_v6(poll.clone()));
+		StorageVersion::new(6).put::<FateriumPolls>();
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 7);
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		// Existing polls are of the plurality kind
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap(), poll);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		// Migration runs only once
+		unhashed::put(&key, &to_v6(poll.clone()));
+		MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(to_v6(poll).encode()));
+	});
+}
+
+#[test]
+fn migration_to_v8_should_work() {
+	use crate::migrations::v8::MigrateToV8;
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![3, 7, 0])));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		let key = PollDetailsOf::<Test>::hashed_key_for(pid);
+		unhashed::put(&key, &to_v7(poll.clone()));
+		StorageVersion::new(7).put::<FateriumPolls>();
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 8);
+		// Only the capital of all options is checked against the goal of existing polls
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap(), poll);
+		fast_forward_to(10);
+		let status = FateriumPolls::poll_details_of(pid).unwrap().status;
+		assert_eq!(status, PollStatus::Finished { winning_option: 1, end: 10 });
+		// Migration runs only once
+		unhashed::put(&key, &to_v7(poll.clone()));
+		MigrateToV8::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(to_v7(poll).encode()));
+	});
+}
+
+#[test]
+fn migration_to_v9_should_work() {
+	use crate::migrations::v9::MigrateToV9;
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let finished = begin_poll(1, vec![], 10, true);
+		let failed = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), finished, make_votes(vec![6, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), finished, make_votes(vec![0, 5, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), failed, make_votes(vec![3, 0, 0])));
+		fast_forward_to(10);
+		assert_ok!(FateriumPolls::collect(Origin::signed(3), finished));
+		// Funds of version 8 are in the shared pot, with its existential deposit and dust
+		let shared = FateriumPolls::account_id();
+		for pid in [finished, failed] {
+			let pot = FateriumPolls::pot_account_id(pid);
+			assert_ok!(Balances::set_balance(Origin::root(), pot, 0, 0));
+		}
+		assert_ok!(Balances::set_balance(Origin::root(), shared, 12, 0));
+		StorageVersion::new(8).put::<FateriumPolls>();
+		MigrateToV9::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 9);
+		assert_eq!(FateriumPolls::balances_pot(finished), 6);
+		assert_eq!(FateriumPolls::balances_pot(failed), 3);
+		assert_eq!(Balances::free_balance(shared), 3);
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), finished));
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), failed));
+		assert_eq!(Balances::free_balance(2), 20);
+		// Migration runs only once
+		assert_ok!(Balances::set_balance(Origin::root(), shared, 12, 0));
+		MigrateToV9::<Test>::on_runtime_upgrade();
+		assert_eq!(Balances::free_balance(shared), 12);
+		assert_eq!(FateriumPolls::balances_pot(finished), 0);
+	});
+}
+
+#[test]
+fn migration_to_v10_should_work() {
+	use crate::migrations::v10::MigrateToV10;
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let settings = PollSettings { eligibility: Eligibility::Allowlist, ..Default::default() };
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::update_allowlist(Origin::signed(1), pid, vec![2, 3], true));
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		let sealed = Some(SealedSettings { reveal_period: 5, unrevealed_penalty: 2000 });
+		let sealed = begin_poll_with(1, PollSettings { sealed, ..Default::default() });
+		let commitment = BlakeTwo256::hash_of(&(make_votes(vec![4, 0, 0]), [1u8; 32]));
+		assert_ok!(FateriumPolls::commit_votes(Origin::signed(4), sealed, commitment, 4));
+		// Participants aren't indexed in version 9, and the cursors point to other keys
+		let _: Vec<_> = ParticipantsOf::<Test>::drain().collect();
+		let cursor: SettlementCursor = vec![1u8; 8].try_into().unwrap();
+		SettlementCursorOf::<Test>::put(&cursor);
+		ReapCursorOf::<Test>::put((ReapPhase::Commitments, Some(cursor)));
+		StorageVersion::new(9).put::<FateriumPolls>();
+		MigrateToV10::<Test>::on_runtime_upgrade();
+		assert_eq!(FateriumPolls::on_chain_storage_version(), 10);
+		let mut participants: Vec<_> = ParticipantsOf::<Test>::iter_keys().collect();
+		participants.sort();
+		assert_eq!(participants, vec![(pid, 2), (pid, 3), (sealed, 4)]);
+		assert_eq!(FateriumPolls::settlement_cursor_of(), None);
+		assert_eq!(FateriumPolls::reap_cursor_of(), Some((ReapPhase::Commitments, None)));
+		assert_ok!(FateriumPolls::do_try_state());
+		// Migration runs only once
+		ParticipantsOf::<Test>::remove(pid, 3);
+		MigrateToV10::<Test>::on_runtime_upgrade();
+		assert!(!ParticipantsOf::<Test>::contains_key(pid, 3));
+	});
+}
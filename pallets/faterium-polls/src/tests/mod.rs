@@ -1,17 +1,27 @@
 //! The crate's tests.
 
+mod cid;
+mod eligibility;
+mod escrow;
+mod goal_modes;
+mod migrations;
+mod poll_kinds;
+mod reap;
+mod sealed;
+mod settlement;
+mod try_state;
 mod voting;
 
 use crate::{self as pallet_faterium_polls, *};
 use frame_support::{
 	assert_noop, assert_ok, parameter_types,
-	traits::{ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly, Hooks},
+	traits::{tokens::fungibles, ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly, Hooks},
 	weights::Weight,
 };
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
 	Perbill,
 };
 
@@ -19,6 +29,7 @@ type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 type PollIndex = u64;
 type Balance = u64;
+type MaxPollOptions = ConstU32<10>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -120,6 +131,60 @@ impl pallet_assets::Config for Test {
 	type WeightInfo = ();
 }
 
+/// Deterministic randomness, derived from the subject only.
+pub struct TestRandomness;
+
+impl frame_support::traits::Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		(BlakeTwo256::hash(subject), System::block_number())
+	}
+}
+
+parameter_types! {
+	pub static PollDepositBase: Balance = 0;
+	pub static PollDepositPerByte: Balance = 0;
+	pub static PollDepositPerBeneficiary: Balance = 0;
+}
+
+/// Allows only raw and dag-pb content types in IPFS CIDs.
+pub struct TestCidCodecs;
+
+impl frame_support::traits::Contains<u64> for TestCidCodecs {
+	fn contains(codec: &u64) -> bool {
+		matches!(*codec, crate::cid::codec::RAW | crate::cid::codec::DAG_PB)
+	}
+}
+
+/// Allows only sha2-256 and blake2b-256 hash functions in IPFS CIDs.
+pub struct TestCidHashes;
+
+impl frame_support::traits::Contains<u64> for TestCidHashes {
+	fn contains(hash: &u64) -> bool {
+		matches!(*hash, crate::cid::hash::SHA2_256 | crate::cid::hash::BLAKE2B_256)
+	}
+}
+
+/// Collection 0 has accounts 2 and 3 as members.
+pub struct TestCollections;
+
+impl frame_support::traits::Contains<(u32, u64)> for TestCollections {
+	fn contains(member: &(u32, u64)) -> bool {
+		matches!(*member, (0, 2) | (0, 3))
+	}
+}
+
+/// Receives the assets left in the pots of reaped polls.
+pub const ASSET_DUST_RECEIVER: u64 = 99;
+
+/// Deposits the assets left in the pots of reaped polls to `ASSET_DUST_RECEIVER`.
+pub struct TestAssetDust;
+
+impl frame_support::traits::OnUnbalanced<fungibles::CreditOf<u64, Assets>> for TestAssetDust {
+	fn on_nonzero_unbalanced(credit: fungibles::CreditOf<u64, Assets>) {
+		let _ = <Assets as fungibles::Balanced<u64>>::resolve(&ASSET_DUST_RECEIVER, credit);
+	}
+}
+
 impl pallet_faterium_polls::Config for Test {
 	type PollCall = Call;
 	type Event = Event;
@@ -127,9 +192,34 @@ impl pallet_faterium_polls::Config for Test {
 	type Currency = Balances;
 	type PollIndex = PollIndex;
 	type Scheduler = Scheduler;
+	type Randomness = TestRandomness;
 	type PalletsOrigin = OriginCaller;
 	type PalletId = FateriumPollsPalletId;
+	type MaxIpfsCidLength = ConstU32<64>;
+	type AllowedCidCodecs = TestCidCodecs;
+	type AllowedCidHashes = TestCidHashes;
 	type MaxPollBeneficiaries = ConstU32<10>;
+	type MaxPollOptions = MaxPollOptions;
+	type MaxRankings = ConstU32<4>;
+	type MinRankingStake = ConstU64<2>;
+	type MaxDelegators = ConstU32<3>;
+	type MaxMilestones = ConstU32<4>;
+	type SignOffPeriod = ConstU64<5>;
+	type MilestonePeriod = ConstU64<30>;
+	type MaxSettlementQueue = ConstU32<2>;
+	type ReapDelay = ConstU64<20>;
+	type Collections = TestCollections;
+	type MinPollDuration = ConstU64<5>;
+	type MaxPollDuration = ConstU64<100>;
+	type MinPollGoal = ConstU64<1>;
+	type PollDepositBase = PollDepositBase;
+	type PollDepositPerByte = PollDepositPerByte;
+	type PollDepositPerBeneficiary = PollDepositPerBeneficiary;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type Slashed = ();
+	type Dust = ();
+	type AssetDust = TestAssetDust;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -141,6 +231,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 fn next_block() {
 	System::set_block_number(System::block_number() + 1);
 	Scheduler::on_initialize(System::block_number());
+	assert_ok!(FateriumPolls::do_try_state());
 }
 
 fn fast_forward_to(n: u64) {
@@ -149,23 +240,89 @@ fn fast_forward_to(n: u64) {
 	}
 }
 
+/// Settings of a poll created by `begin_poll_with`.
+struct PollSettings {
+	bnfs: Vec<(u64, u32)>,
+	goal: Balance,
+	goal_mode: GoalMode<Balance, MaxPollOptions>,
+	multiple_votes: bool,
+	movable_votes: bool,
+	reward_settings: RewardSettings<Balance>,
+	tie_policy: TiePolicy,
+	voting_mode: VotingMode,
+	sealed: Option<SealedSettings<u64>>,
+	eligibility: Eligibility<u32, Balance>,
+	kind: PollKind,
+	vesting: Option<VestingSchedule<u64>>,
+}
+
+impl Default for PollSettings {
+	fn default() -> Self {
+		Self {
+			bnfs: vec![],
+			goal: 10,
+			goal_mode: GoalMode::TotalCapital,
+			multiple_votes: true,
+			movable_votes: true,
+			reward_settings: RewardSettings::None,
+			tie_policy: TiePolicy::Refund,
+			voting_mode: VotingMode::Transfer,
+			sealed: None,
+			eligibility: Eligibility::Anyone,
+			kind: PollKind::Plurality,
+			vesting: None,
+		}
+	}
+}
+
 fn begin_poll(who: u64, bnfs: Vec<(u64, u32)>, goal: Balance, multiple_votes: bool) -> PollIndex {
+	begin_poll_with(who, PollSettings { bnfs, goal, multiple_votes, ..Default::default() })
+}
+
+fn begin_poll_with_rewards(
+	who: u64,
+	bnfs: Vec<(u64, u32)>,
+	goal: Balance,
+	multiple_votes: bool,
+	reward_settings: RewardSettings<Balance>,
+) -> PollIndex {
+	let settings =
+		PollSettings { bnfs, goal, multiple_votes, reward_settings, ..Default::default() };
+	begin_poll_with(who, settings)
+}
+
+fn begin_poll_with_tie_policy(who: u64, bnfs: Vec<(u64, u32)>, tie_policy: TiePolicy) -> PollIndex {
+	begin_poll_with(who, PollSettings { bnfs, tie_policy, ..Default::default() })
+}
+
+fn begin_poll_with(who: u64, settings: PollSettings) -> PollIndex {
 	System::set_block_number(0);
 	let res = FateriumPolls::create_poll(
 		Origin::signed(who),
-		(0..46).collect(),
-		bnfs,
-		RewardSettings::None,
-		goal,
+		cid(),
+		settings
+			.bnfs
+			.into_iter()
+			.map(|(who, interest)| (who, interest, settings.vesting))
+			.collect(),
+		settings.reward_settings,
+		settings.goal,
+		settings.goal_mode,
 		3,
-		multiple_votes,
+		settings.multiple_votes,
+		settings.movable_votes,
+		settings.tie_policy,
+		settings.voting_mode,
+		settings.sealed,
+		settings.eligibility,
+		settings.kind,
 		PollCurrency::Native,
 		1,
 		10,
 	);
 	assert_ok!(res);
 	fast_forward_to(2);
-	1
+	FateriumPolls::poll_count()
 }
 
 fn begin_poll_with_asset(
@@ -182,12 +339,19 @@ fn begin_poll_with_asset(
 	// Create poll
 	let res = FateriumPolls::create_poll(
 		Origin::signed(who),
-		(0..46).collect(),
-		bnfs,
+		cid(),
+		bnfs.into_iter().map(|(who, interest)| (who, interest, None)).collect(),
 		RewardSettings::None,
 		10,
+		GoalMode::TotalCapital,
 		3,
 		false,
+		true,
+		TiePolicy::Refund,
+		VotingMode::Transfer,
+		None,
+		Eligibility::Anyone,
+		PollKind::Plurality,
 		PollCurrency::Asset(0),
 		1,
 		10,
@@ -199,8 +363,6 @@ fn begin_poll_with_asset(
 
 fn set_balances(acc: u64) {
 	assert_ok!(Balances::set_balance(Origin::root(), acc, 20, 0));
-	// Set existential deposit for pot
-	assert_ok!(Balances::set_balance(Origin::root(), FateriumPolls::account_id(), 1, 0));
 	assert_eq!(Balances::free_balance(acc), 20);
 }
 
@@ -213,6 +375,15 @@ fn params_should_work() {
 	});
 }
 
-fn votes(pid: PollIndex) -> Votes<Balance> {
+/// Returns a valid CIDv0.
+fn cid() -> Vec<u8> {
+	b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec()
+}
+
+fn votes(pid: PollIndex) -> Votes<Balance, MaxPollOptions> {
 	FateriumPolls::poll_details_of(pid).unwrap().votes
 }
+
+fn make_votes(stakes: Vec<Balance>) -> Votes<Balance, MaxPollOptions> {
+	Votes(stakes.try_into().unwrap())
+}
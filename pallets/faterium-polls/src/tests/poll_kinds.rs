@@ -0,0 +1,232 @@
+//! Tests for polls of ranked-choice, approval and score kinds, voted in with ballots.
+
+use super::*;
+
+type TestBallot = Ballot<Balance, MaxPollOptions>;
+
+fn ranked(ranking: Vec<u8>, stake: Balance) -> TestBallot {
+	Ballot::Ranked { ranking: ranking.try_into().unwrap(), stake }
+}
+
+fn approval(approved: Vec<u8>, stake: Balance) -> TestBallot {
+	Ballot::Approval { approved: approved.try_into().unwrap(), stake }
+}
+
+fn score(scores: Vec<u8>, stake: Balance) -> TestBallot {
+	Ballot::Score { scores: scores.try_into().unwrap(), stake }
+}
+
+fn begin_poll_of_kind(kind: PollKind) -> PollIndex {
+	begin_poll_with(1, PollSettings { kind, goal: 5, ..Default::default() })
+}
+
+#[test]
+fn ranked_choice_poll_should_be_won_by_instant_runoff() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let pid = begin_poll_of_kind(PollKind::RankedChoice);
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![4, 0, 0])),
+			Error::<Test>::InvalidPollKind,
+		);
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(2), pid, ranked(vec![0], 4)));
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(3), pid, ranked(vec![1, 0], 3)));
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(4), pid, ranked(vec![2, 1], 2)));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::BallotCast {
+			voter: 4,
+			poll_id: pid,
+			ballot: ranked(vec![2, 1], 2),
+			votes: make_votes(vec![0, 0, 2]),
+		}));
+		// Stakes are on the most preferred options
+		assert_eq!(votes(pid), make_votes(vec![4, 3, 2]));
+		assert_eq!(FateriumPolls::rankings_of(pid).len(), 3);
+		fast_forward_to(10);
+		// Option 2 is eliminated first, and its ballot is passed to option 1
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Finished { winning_option: 1, end: 10 });
+		for acc in [2, 3, 4] {
+			assert_ok!(FateriumPolls::collect(Origin::signed(acc), pid));
+			assert_eq!(Balances::free_balance(acc), 20);
+		}
+	});
+}
+
+#[test]
+fn approval_poll_should_spread_stake_across_approved_options() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let pid = begin_poll_of_kind(PollKind::Approval);
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(2), pid, approval(vec![0, 1], 10)));
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(3), pid, approval(vec![0], 6)));
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(4), pid, approval(vec![1, 2], 8)));
+		assert_eq!(FateriumPolls::voting_of((2, pid)).unwrap().votes, make_votes(vec![5, 5, 0]));
+		assert_eq!(FateriumPolls::voting_of((4, pid)).unwrap().votes, make_votes(vec![0, 4, 4]));
+		assert_eq!(votes(pid), make_votes(vec![11, 9, 4]));
+		assert_eq!(FateriumPolls::tally_of(pid), Some(make_votes(vec![16, 18, 8])));
+		assert_eq!(Balances::free_balance(2), 10);
+		fast_forward_to(10);
+		// Option 1 is approved by the highest stake, though option 0 has more of it
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Finished { winning_option: 1, end: 10 });
+	});
+}
+
+#[test]
+fn score_poll_should_be_won_by_weighted_scores() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let pid = begin_poll_of_kind(PollKind::Score { max_score: 5 });
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(2), pid, score(vec![5, 1, 0], 6)));
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(3), pid, score(vec![0, 4, 4], 9)));
+		// Remainder of the pro rata spread is on the first option with the highest score
+		assert_eq!(FateriumPolls::voting_of((3, pid)).unwrap().votes, make_votes(vec![0, 5, 4]));
+		assert_eq!(votes(pid), make_votes(vec![5, 6, 4]));
+		assert_eq!(FateriumPolls::tally_of(pid), Some(make_votes(vec![30, 42, 36])));
+		fast_forward_to(10);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Finished { winning_option: 1, end: 10 });
+	});
+}
+
+#[test]
+fn invalid_ballots_should_fail() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let plurality = begin_poll(1, vec![], 10, true);
+		assert_noop!(
+			FateriumPolls::cast_ballot(Origin::signed(2), plurality, approval(vec![0], 5)),
+			Error::<Test>::InvalidPollKind,
+		);
+		let pid = begin_poll_of_kind(PollKind::Score { max_score: 5 });
+		for ballot in [
+			approval(vec![0], 5),
+			score(vec![1, 2], 5),
+			score(vec![6, 0, 0], 5),
+			score(vec![0, 0, 0], 5),
+			score(vec![1, 0, 0], 0),
+		] {
+			assert_noop!(
+				FateriumPolls::cast_ballot(Origin::signed(2), pid, ballot),
+				Error::<Test>::InvalidBallot,
+			);
+		}
+		let pid = begin_poll_of_kind(PollKind::Approval);
+		for ballot in [approval(vec![], 5), approval(vec![0, 0], 5), approval(vec![3], 5)] {
+			assert_noop!(
+				FateriumPolls::cast_ballot(Origin::signed(2), pid, ballot),
+				Error::<Test>::InvalidBallot,
+			);
+		}
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(2), pid, approval(vec![0, 1], 4)));
+		assert_noop!(
+			FateriumPolls::cast_ballot(Origin::signed(2), pid, approval(vec![2], 4)),
+			Error::<Test>::BallotAlreadyCast,
+		);
+		// Votes spread from ballots can't be changed
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(2), pid, make_votes(vec![1, 0, 0])),
+			Error::<Test>::InvalidPollKind,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(2), pid, 0, 2, 1),
+			Error::<Test>::InvalidPollKind,
+		);
+	});
+}
+
+#[test]
+fn poll_kind_should_be_validated_on_creation() {
+	new_test_ext().execute_with(|| {
+		let sealed = Some(SealedSettings { reveal_period: 5, unrevealed_penalty: 0 });
+		for settings in [
+			PollSettings { kind: PollKind::RankedChoice, sealed, ..Default::default() },
+			PollSettings {
+				kind: PollKind::Approval,
+				tie_policy: TiePolicy::SplitInterest,
+				..Default::default()
+			},
+			PollSettings { kind: PollKind::Score { max_score: 0 }, ..Default::default() },
+		] {
+			assert_noop!(
+				FateriumPolls::create_poll(
+					Origin::signed(1),
+					cid(),
+					vec![],
+					RewardSettings::None,
+					settings.goal,
+					settings.goal_mode,
+					3,
+					settings.multiple_votes,
+					settings.movable_votes,
+					settings.tie_policy,
+					settings.voting_mode,
+					settings.sealed,
+					settings.eligibility,
+					settings.kind,
+					PollCurrency::Native,
+					1,
+					10,
+				),
+				Error::<Test>::InvalidPollDetails,
+			);
+		}
+	});
+}
+
+#[test]
+fn remove_vote_should_remove_ballot_from_tally() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let pid = begin_poll_of_kind(PollKind::Approval);
+		// Remainder of the even spread is on the first approved options
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(2), pid, approval(vec![2, 0, 1], 5)));
+		assert_eq!(votes(pid), make_votes(vec![2, 1, 2]));
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(3), pid, approval(vec![1], 3)));
+		assert_ok!(FateriumPolls::remove_vote(Origin::signed(2), pid));
+		assert_eq!(FateriumPolls::ballots_of((2, pid)), None);
+		assert_eq!(FateriumPolls::tally_of(pid), Some(make_votes(vec![0, 3, 0])));
+		assert_eq!(votes(pid), make_votes(vec![0, 3, 0]));
+		assert_eq!(Balances::free_balance(2), 20);
+		// Another ballot can be cast then
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(2), pid, approval(vec![0], 4)));
+		assert_eq!(FateriumPolls::tally_of(pid), Some(make_votes(vec![4, 3, 0])));
+	});
+}
+
+#[test]
+fn rankings_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		for acc in 2..=7 {
+			set_balances(acc);
+		}
+		let pid = begin_poll_of_kind(PollKind::RankedChoice);
+		// Rankings can't be filled up with dust stakes
+		assert_noop!(
+			FateriumPolls::cast_ballot(Origin::signed(2), pid, ranked(vec![0], 1)),
+			Error::<Test>::BallotStakeTooLow,
+		);
+		for (acc, ranking) in [(2, vec![0]), (3, vec![1]), (4, vec![2]), (5, vec![0, 1])] {
+			assert_ok!(FateriumPolls::cast_ballot(Origin::signed(acc), pid, ranked(ranking, 2)));
+		}
+		assert_noop!(
+			FateriumPolls::cast_ballot(Origin::signed(6), pid, ranked(vec![1, 0], 2)),
+			Error::<Test>::TooManyRankings,
+		);
+		// Ballots with the same ranking share an entry
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(7), pid, ranked(vec![0, 1], 3)));
+		let rankings = FateriumPolls::rankings_of(pid);
+		assert_eq!(rankings.len(), 4);
+		assert_eq!(rankings[3], (vec![0, 1].try_into().unwrap(), 5));
+		// Ranking is removed with the last ballot of it
+		assert_ok!(FateriumPolls::remove_vote(Origin::signed(4), pid));
+		assert_eq!(FateriumPolls::rankings_of(pid).len(), 3);
+		assert_ok!(FateriumPolls::cast_ballot(Origin::signed(6), pid, ranked(vec![1, 0], 2)));
+	});
+}
@@ -0,0 +1,186 @@
+//! Tests for reaping of ended polls, and archival of their summaries.
+
+use super::*;
+use frame_support::weights::Weight;
+
+/// Weight which is enough to settle and reap everything in the queues.
+fn unlimited() -> Weight {
+	Weight::from_ref_time(u64::MAX)
+}
+
+#[test]
+fn reaped_poll_should_be_archived() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let pid = begin_poll(1, vec![(5, 5000)], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, make_votes(vec![4, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(4), pid, make_votes(vec![0, 6, 0])));
+		assert_noop!(
+			FateriumPolls::reap_poll(Origin::signed(6), pid),
+			Error::<Test>::CollectOnOngoingPoll,
+		);
+		fast_forward_to(10);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_noop!(
+			FateriumPolls::reap_poll(Origin::signed(6), pid),
+			Error::<Test>::PollNotSettled,
+		);
+		// Voter collects himself, and the rest is paid out by the settlement
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		FateriumPolls::on_idle(10, unlimited());
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::ReapQueued { poll_id: pid }));
+		assert_noop!(
+			FateriumPolls::reap_poll(Origin::signed(6), pid),
+			Error::<Test>::PollAlreadyReaping,
+		);
+		FateriumPolls::on_idle(11, unlimited());
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Reaped {
+			poll_id: pid,
+			dust: 0,
+		}));
+		assert_eq!(FateriumPolls::poll_details_of(pid), None);
+		for acc in [2, 3, 4] {
+			assert_eq!(FateriumPolls::voting_of((acc, pid)), None);
+		}
+		assert_eq!(
+			FateriumPolls::archive_of(pid),
+			Some(PollSummary {
+				created_by: 1,
+				ipfs_cid: poll.ipfs_cid,
+				currency: PollCurrency::Native,
+				status: PollStatus::Finished { winning_option: 0, end: 10 },
+				capital: 20,
+				winning_capital: 14,
+				dust: 0,
+			}),
+		);
+		assert_eq!(Balances::free_balance(2), 15);
+		assert_eq!(Balances::free_balance(3), 18);
+		assert_eq!(Balances::free_balance(4), 20);
+		assert_eq!(Balances::free_balance(5), 7);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		assert!(!System::account_exists(&FateriumPolls::pot_account_id(pid)));
+		assert!(FateriumPolls::reap_queue().is_empty());
+		assert_eq!(FateriumPolls::reap_cursor_of(), None);
+		assert_noop!(FateriumPolls::reap_poll(Origin::signed(6), pid), Error::<Test>::PollInvalid);
+	});
+}
+
+#[test]
+fn reap_should_take_uncollected_interest_after_delay() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let vesting = VestingSchedule { start_offset: 100, duration: 10, cliff: 0 };
+		let settings =
+			PollSettings { bnfs: vec![(5, 5000)], vesting: Some(vesting), ..Default::default() };
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+		fast_forward_to(10);
+		FateriumPolls::on_idle(10, unlimited());
+		assert_eq!(Balances::free_balance(2), 15);
+		// Interest isn't vested yet, even after the reap delay
+		fast_forward_to(30);
+		assert_noop!(
+			FateriumPolls::reap_poll(Origin::signed(6), pid),
+			Error::<Test>::InterestNotReleased,
+		);
+		fast_forward_to(120);
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+		let issuance = Balances::total_issuance();
+		FateriumPolls::on_idle(120, unlimited());
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Reaped {
+			poll_id: pid,
+			dust: 5,
+		}));
+		assert_eq!(FateriumPolls::archive_of(pid).unwrap().dust, 5);
+		assert_eq!(Balances::free_balance(5), 0);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		assert_eq!(Balances::total_issuance(), issuance - 5);
+	});
+}
+
+#[test]
+fn reap_should_resume_across_blocks() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let settings = PollSettings { eligibility: Eligibility::Allowlist, ..Default::default() };
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::update_allowlist(Origin::signed(1), pid, vec![2, 3], true));
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![5, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, make_votes(vec![0, 5, 0])));
+		assert_ok!(FateriumPolls::emergency_cancel(Origin::signed(1), pid));
+		FateriumPolls::on_idle(2, unlimited());
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+		// Weight is enough to remove only one vote per block
+		let weight = <() as WeightInfo>::collect(0, 10, 3);
+		let removed = || {
+			[2, 3]
+				.into_iter()
+				.filter(|acc| !VotingOf::<Test>::contains_key((*acc, pid)))
+				.count()
+		};
+		FateriumPolls::on_idle(3, weight);
+		assert_eq!(removed(), 1);
+		assert!(matches!(FateriumPolls::reap_cursor_of(), Some((ReapPhase::Votes, Some(_)))));
+		FateriumPolls::on_idle(4, weight);
+		assert_eq!(removed(), 2);
+		assert_eq!(
+			FateriumPolls::poll_details_of(pid).map(|poll| poll.status),
+			Some(PollStatus::Cancelled(2)),
+		);
+		FateriumPolls::on_idle(5, unlimited());
+		assert!(!AllowlistOf::<Test>::contains_key((2, pid)));
+		assert!(!AllowlistOf::<Test>::contains_key((3, pid)));
+		assert_eq!(FateriumPolls::archive_of(pid).unwrap().status, PollStatus::Cancelled(2));
+		assert_eq!(Balances::free_balance(2), 20);
+		assert_eq!(Balances::free_balance(3), 20);
+	});
+}
+
+#[test]
+fn reap_should_refund_unrevealed_commitments() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let sealed = Some(SealedSettings { reveal_period: 5, unrevealed_penalty: 2000 });
+		let pid = begin_poll_with(1, PollSettings { sealed, ..Default::default() });
+		let commitment = BlakeTwo256::hash_of(&(make_votes(vec![10, 0, 0]), [1u8; 32]));
+		assert_ok!(FateriumPolls::commit_votes(Origin::signed(2), pid, commitment, 10));
+		fast_forward_to(15);
+		FateriumPolls::on_idle(15, unlimited());
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+		FateriumPolls::on_idle(16, unlimited());
+		System::assert_has_event(Event::FateriumPolls(crate::Event::CommitmentRefunded {
+			voter: 2,
+			poll_id: pid,
+			amount: 8,
+			penalty: 2,
+		}));
+		assert_eq!(FateriumPolls::commitments_of((2, pid)), None);
+		assert_eq!(Balances::free_balance(2), 18);
+		assert!(matches!(FateriumPolls::archive_of(pid).unwrap().status, PollStatus::Failed(_)));
+	});
+}
+
+#[test]
+fn reap_should_send_asset_dust_to_handler() {
+	new_test_ext().execute_with(|| {
+		let (pid, asset_id) = begin_poll_with_asset(1, 2, vec![], 20);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+		// Assets sent to the pot outside of the poll are left there as dust
+		let pot = FateriumPolls::pot_account_id(pid);
+		assert_ok!(Assets::mint(Origin::signed(1), asset_id, pot, 3));
+		fast_forward_to(10);
+		FateriumPolls::on_idle(10, unlimited());
+		assert_eq!(Assets::balance(asset_id, 2), 20);
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+		FateriumPolls::on_idle(11, unlimited());
+		assert_eq!(FateriumPolls::archive_of(pid).unwrap().dust, 3);
+		assert_eq!(Assets::balance(asset_id, pot), 0);
+		assert_eq!(Assets::balance(asset_id, ASSET_DUST_RECEIVER), 3);
+	});
+}
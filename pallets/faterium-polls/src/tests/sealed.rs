@@ -0,0 +1,229 @@
+//! Tests for sealed voting, where votes are committed and revealed.
+
+use super::*;
+
+/// Creates a sealed poll like `begin_poll`, with the reveal period of 5 blocks after its end.
+fn begin_sealed_poll(voting_mode: VotingMode, unrevealed_penalty: u32) -> PollIndex {
+	let sealed = Some(SealedSettings { reveal_period: 5, unrevealed_penalty });
+	begin_poll_with(1, PollSettings { voting_mode, sealed, ..Default::default() })
+}
+
+/// Returns the commitment of votes with the salt.
+fn commitment(votes: &Votes<Balance, MaxPollOptions>, salt: [u8; 32]) -> H256 {
+	BlakeTwo256::hash_of(&(votes, salt))
+}
+
+#[test]
+fn sealed_voting_should_work() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let pid = begin_sealed_poll(VotingMode::Transfer, 2000);
+		let (ballot, salt) = (make_votes(vec![12, 0, 0]), [1; 32]);
+		assert_ok!(FateriumPolls::commit_votes(
+			Origin::signed(2),
+			pid,
+			commitment(&ballot, salt),
+			12
+		));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::VotesCommitted {
+			voter: 2,
+			poll_id: pid,
+			stake: 12,
+		}));
+		let other = make_votes(vec![0, 5, 0]);
+		assert_ok!(FateriumPolls::commit_votes(
+			Origin::signed(3),
+			pid,
+			commitment(&other, [2; 32]),
+			5
+		));
+		// Stakes are held, but the tally is hidden
+		assert_eq!(FateriumPolls::balances_pot(pid), 17);
+		assert_eq!(votes(pid), make_votes(vec![0, 0, 0]));
+		assert_noop!(
+			FateriumPolls::commit_votes(Origin::signed(2), pid, commitment(&ballot, salt), 12),
+			Error::<Test>::CommitmentAlreadyExists,
+		);
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(2), pid, ballot.clone(), salt),
+			Error::<Test>::RevealPeriodNotStarted,
+		);
+		fast_forward_to(10);
+		assert_noop!(
+			FateriumPolls::commit_votes(Origin::signed(4), pid, commitment(&ballot, salt), 12),
+			Error::<Test>::CommitPeriodEnded,
+		);
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(2), pid, ballot.clone(), [2; 32]),
+			Error::<Test>::CommitmentMismatch,
+		);
+		assert_ok!(FateriumPolls::reveal_votes(Origin::signed(2), pid, ballot.clone(), salt));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::VotesRevealed {
+			voter: 2,
+			poll_id: pid,
+			votes: ballot.clone(),
+		}));
+		assert_eq!(FateriumPolls::voting_of((2, pid)).unwrap().votes, ballot);
+		assert_eq!(votes(pid), ballot);
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(2), pid, ballot.clone(), salt),
+			Error::<Test>::CommitmentNotExist,
+		);
+		// Only revealed votes are tallied at the end of the reveal period
+		fast_forward_to(15);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Finished { winning_option: 0, end: 10 });
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(3), pid, other, [2; 32]),
+			Error::<Test>::PollAlreadyFinished,
+		);
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), 20);
+		// Stake of unrevealed votes is refunded without the penalty of 20%
+		assert_ok!(FateriumPolls::refund_commitment(Origin::signed(3), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::CommitmentRefunded {
+			voter: 3,
+			poll_id: pid,
+			amount: 4,
+			penalty: 1,
+		}));
+		assert_eq!(Balances::free_balance(3), 19);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		assert_noop!(
+			FateriumPolls::refund_commitment(Origin::signed(3), pid),
+			Error::<Test>::CommitmentNotExist,
+		);
+	});
+}
+
+#[test]
+fn sealed_voting_should_fail() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let sealed = |reveal_period, unrevealed_penalty| {
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid(),
+				vec![],
+				RewardSettings::None,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				false,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Transfer,
+				Some(SealedSettings { reveal_period, unrevealed_penalty }),
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			)
+		};
+		System::set_block_number(0);
+		assert_noop!(sealed(0, 0), Error::<Test>::InvalidPollPeriod);
+		assert_noop!(sealed(5, 10_001), Error::<Test>::InvalidPollDetails);
+		assert_ok!(sealed(5, 0));
+		let pid = FateriumPolls::poll_count();
+		// Votes in public polls can't be committed
+		let public = begin_poll(1, vec![], 10, false);
+		let votes = make_votes(vec![3, 3, 0]);
+		assert_noop!(
+			FateriumPolls::commit_votes(Origin::signed(2), public, commitment(&votes, [0; 32]), 6),
+			Error::<Test>::PollNotSealed,
+		);
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(2), public, votes.clone(), [0; 32]),
+			Error::<Test>::PollNotSealed,
+		);
+		// Votes in sealed polls can only be committed
+		let single = make_votes(vec![6, 0, 0]);
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(2), pid, single.clone()),
+			Error::<Test>::PollSealed,
+		);
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(2), pid, single.clone()),
+			Error::<Test>::PollSealed,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(2), pid, 0, 1, 1),
+			Error::<Test>::PollSealed,
+		);
+		assert_noop!(FateriumPolls::remove_vote(Origin::signed(2), pid), Error::<Test>::PollSealed);
+		assert_noop!(
+			FateriumPolls::commit_votes(Origin::signed(2), pid, commitment(&votes, [0; 32]), 0),
+			Error::<Test>::InvalidPollVotes,
+		);
+		assert_noop!(
+			FateriumPolls::commit_votes(Origin::signed(2), pid, commitment(&votes, [0; 32]), 21),
+			Error::<Test>::InsufficientFunds,
+		);
+		assert_noop!(
+			FateriumPolls::refund_commitment(Origin::signed(2), pid),
+			Error::<Test>::CollectOnOngoingPoll,
+		);
+		// Revealed votes should match the stake and be valid for the poll
+		assert_ok!(FateriumPolls::commit_votes(
+			Origin::signed(2),
+			pid,
+			commitment(&votes, [0; 32]),
+			6
+		));
+		fast_forward_to(10);
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(2), pid, votes, [0; 32]),
+			Error::<Test>::MultipleVotesNotAllowed,
+		);
+		assert_noop!(
+			FateriumPolls::reveal_votes(Origin::signed(2), pid, single, [0; 32]),
+			Error::<Test>::CommitmentMismatch,
+		);
+	});
+}
+
+#[test]
+fn sealed_lock_voting_should_refund_commitments() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let pid = begin_sealed_poll(VotingMode::Lock, 2000);
+		let votes = make_votes(vec![10, 0, 0]);
+		for voter in [2, 3] {
+			assert_ok!(FateriumPolls::commit_votes(
+				Origin::signed(voter),
+				pid,
+				commitment(&votes, [voter as u8; 32]),
+				10
+			));
+			assert_eq!(FateriumPolls::locked_of(voter), 10);
+		}
+		// Penalty is slashed from the voter in lock voting mode
+		fast_forward_to(15);
+		assert_ok!(FateriumPolls::refund_commitment(Origin::signed(2), pid));
+		assert_eq!(FateriumPolls::locked_of(2), 0);
+		assert_eq!(Balances::free_balance(2), 18);
+		// Voters aren't penalized if the poll is cancelled
+		let pid = begin_sealed_poll(VotingMode::Lock, 2000);
+		assert_ok!(FateriumPolls::commit_votes(
+			Origin::signed(2),
+			pid,
+			commitment(&votes, [0; 32]),
+			10
+		));
+		assert_ok!(FateriumPolls::emergency_cancel(Origin::signed(1), pid));
+		assert_ok!(FateriumPolls::refund_commitment(Origin::signed(2), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::CommitmentRefunded {
+			voter: 2,
+			poll_id: pid,
+			amount: 10,
+			penalty: 0,
+		}));
+		assert_eq!(FateriumPolls::locked_of(2), 0);
+		assert_eq!(Balances::free_balance(2), 18);
+		assert_eq!(FateriumPolls::locked_of(3), 10);
+	});
+}
@@ -0,0 +1,147 @@
+//! Tests for the settlement of ended polls in the idle time of blocks.
+
+use super::*;
+use frame_support::weights::Weight;
+
+/// Weight which is enough to settle everything in the queue.
+fn unlimited() -> Weight {
+	Weight::from_ref_time(u64::MAX)
+}
+
+#[test]
+fn failed_poll_should_be_settled_on_idle() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![3, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, make_votes(vec![0, 2, 0])));
+		fast_forward_to(10);
+		assert_eq!(FateriumPolls::settlement_queue().to_vec(), vec![pid]);
+		FateriumPolls::on_idle(10, unlimited());
+		System::assert_has_event(Event::FateriumPolls(crate::Event::Collected {
+			who: 2,
+			poll_id: pid,
+			amount: 3,
+		}));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Settled { poll_id: pid }));
+		assert_eq!(Balances::free_balance(2), 20);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		assert!(FateriumPolls::settlement_queue().is_empty());
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(2), pid),
+			Error::<Test>::NothingToCollect,
+		);
+	});
+}
+
+#[test]
+fn settlement_should_resume_across_blocks() {
+	new_test_ext().execute_with(|| {
+		for acc in [2, 3, 4] {
+			set_balances(acc);
+		}
+		let pid = begin_poll(1, vec![(5, 5000)], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), pid, make_votes(vec![4, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(4), pid, make_votes(vec![0, 6, 0])));
+		fast_forward_to(10);
+		// Voter collects himself before the settlement reaches him
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		// Weight is enough to pay out only one vote per block
+		let weight = <() as WeightInfo>::collect(0, 10, 3);
+		let collected = || {
+			[2, 3, 4]
+				.into_iter()
+				.filter(|acc| FateriumPolls::voting_of((*acc, pid)).unwrap().collected)
+				.count()
+		};
+		FateriumPolls::on_idle(10, weight);
+		assert!(collected() >= 1);
+		FateriumPolls::on_idle(11, weight);
+		FateriumPolls::on_idle(12, weight);
+		assert_eq!(collected(), 3);
+		assert_eq!(FateriumPolls::settlement_queue().to_vec(), vec![pid]);
+		// Beneficiary is paid out once all votes are
+		FateriumPolls::on_idle(13, unlimited());
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Settled { poll_id: pid }));
+		assert_eq!(Balances::free_balance(2), 15);
+		assert_eq!(Balances::free_balance(3), 18);
+		assert_eq!(Balances::free_balance(4), 20);
+		assert_eq!(Balances::free_balance(5), 7);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		assert_eq!(FateriumPolls::settlement_cursor_of(), None);
+	});
+}
+
+#[test]
+fn polls_should_end_while_settlement_queue_is_full() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pids: Vec<_> = (0..3).map(|_| begin_poll(1, vec![], 10, true)).collect();
+		for pid in &pids {
+			assert_ok!(FateriumPolls::vote(Origin::signed(2), *pid, make_votes(vec![5, 0, 0])));
+		}
+		// All polls end in the same block, and the last one doesn't fit the queue
+		fast_forward_to(10);
+		for pid in &pids {
+			assert_eq!(FateriumPolls::poll_details_of(pid).unwrap().status, PollStatus::Failed(10));
+		}
+		assert_eq!(FateriumPolls::settlement_queue().to_vec(), pids[..2].to_vec());
+		FateriumPolls::on_idle(10, unlimited());
+		assert!(FateriumPolls::settlement_queue().is_empty());
+		assert_eq!(Balances::free_balance(2), 15);
+		// Votes of the poll which isn't queued are collected as usual
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pids[2]));
+		assert_eq!(Balances::free_balance(2), 20);
+		assert_eq!(FateriumPolls::balances_pot(pids[2]), 0);
+	});
+}
+
+#[test]
+fn polls_should_be_cancelled_while_settlement_queue_is_full() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pids: Vec<_> = (0..3).map(|_| begin_poll(1, vec![], 10, true)).collect();
+		for pid in &pids {
+			assert_ok!(FateriumPolls::vote(Origin::signed(2), *pid, make_votes(vec![5, 0, 0])));
+		}
+		for pid in &pids {
+			assert_ok!(FateriumPolls::emergency_cancel(Origin::signed(1), *pid));
+		}
+		assert_eq!(FateriumPolls::settlement_queue().to_vec(), pids[..2].to_vec());
+		FateriumPolls::on_idle(2, unlimited());
+		assert_eq!(Balances::free_balance(2), 15);
+		// The poll which isn't queued is settled when reaped
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pids[2]));
+		FateriumPolls::on_idle(3, unlimited());
+		assert_eq!(Balances::free_balance(2), 20);
+		// Nothing is left to settle or reap
+		assert_eq!(FateriumPolls::on_idle(4, unlimited()), Weight::zero());
+	});
+}
+
+#[test]
+fn settlement_should_visit_only_participants_of_the_poll() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let first = begin_poll(1, vec![], 10, true);
+		let second = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), first, make_votes(vec![5, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), second, make_votes(vec![5, 0, 0])));
+		assert!(ParticipantsOf::<Test>::contains_key(first, 2));
+		assert!(!ParticipantsOf::<Test>::contains_key(first, 3));
+		fast_forward_to(10);
+		// Weight is enough to pay out only one vote, which is the vote in the first poll
+		let weight = <() as WeightInfo>::collect(0, 10, 3);
+		FateriumPolls::on_idle(10, weight);
+		assert!(FateriumPolls::voting_of((2, first)).unwrap().collected);
+		assert!(!FateriumPolls::voting_of((3, second)).unwrap().collected);
+		FateriumPolls::on_idle(11, unlimited());
+		System::assert_has_event(Event::FateriumPolls(crate::Event::Settled { poll_id: first }));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Settled { poll_id: second }));
+		assert_eq!(Balances::free_balance(3), 20);
+	});
+}
@@ -0,0 +1,72 @@
+//! Tests for the invariants of the storage, which are checked after every block.
+
+use super::*;
+
+#[test]
+fn try_state_should_detect_insolvent_pot() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pid = begin_poll(1, vec![(5, 5000)], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+		fast_forward_to(10);
+		// Voter is owed 5 and beneficiary 5, and pot holds 10
+		let pot = FateriumPolls::pot_account_id(pid);
+		assert_ok!(Balances::set_balance(Origin::root(), pot, 9, 0));
+		assert_eq!(FateriumPolls::do_try_state(), Err("pot of a poll can't afford what it owes"));
+		assert_ok!(Balances::set_balance(Origin::root(), pot, 10, 0));
+		assert_ok!(FateriumPolls::do_try_state());
+		// Only what is left to collect is owed
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(FateriumPolls::balances_pot(pid), 5);
+		assert_ok!(FateriumPolls::do_try_state());
+	});
+}
+
+#[test]
+fn try_state_should_detect_mismatched_votes() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![4, 0, 0])));
+		assert_ok!(FateriumPolls::do_try_state());
+		VotingOf::<Test>::insert(
+			(2, pid),
+			AccountVotes { votes: make_votes(vec![3, 0, 0]), collected: false },
+		);
+		assert_eq!(
+			FateriumPolls::do_try_state(),
+			Err("votes of accounts don't add up to votes of a poll"),
+		);
+		VotingOf::<Test>::insert(
+			(2, pid + 1),
+			AccountVotes { votes: make_votes(vec![4, 0, 0]), collected: false },
+		);
+		assert_eq!(FateriumPolls::do_try_state(), Err("votes in a poll which doesn't exist"));
+	});
+}
+
+#[test]
+fn try_state_should_detect_unindexed_participants() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![4, 0, 0])));
+		ParticipantsOf::<Test>::remove(pid, 2);
+		assert_eq!(
+			FateriumPolls::do_try_state(),
+			Err("account with votes in a poll isn't its participant"),
+		);
+	});
+}
+
+#[test]
+fn try_state_should_detect_excessive_interest() {
+	new_test_ext().execute_with(|| {
+		let pid = begin_poll(1, vec![(5, 6000)], 10, true);
+		PollDetailsOf::<Test>::mutate(pid, |poll| {
+			let bnf = Beneficiary { who: 6, interest: 5000, vesting: None, collected: 0 };
+			poll.as_mut().unwrap().beneficiaries.try_push(bnf).unwrap();
+		});
+		assert_eq!(FateriumPolls::do_try_state(), Err("interest of beneficiaries exceeds 100%"));
+	});
+}
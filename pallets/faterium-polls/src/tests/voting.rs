@@ -1,6 +1,7 @@
 //! The tests for normal voting functionality.
 
 use super::*;
+use frame_support::weights::Weight;
 
 #[test]
 fn vote_without_balance_should_fail() {
@@ -8,7 +9,7 @@ fn vote_without_balance_should_fail() {
 		// Creates poll
 		let pid = begin_poll(1, vec![], 10, false);
 		// Try to vote without balance
-		let v = Votes(vec![0, 10, 0]);
+		let v = make_votes(vec![0, 10, 0]);
 		assert_noop!(
 			FateriumPolls::vote(Origin::signed(1), pid, v),
 			Error::<Test>::InsufficientFunds
@@ -24,17 +25,17 @@ fn vote_with_balances_should_work() {
 		set_balances(voter);
 		let pid = begin_poll(1, vec![], 10, false);
 		// Vote on poll
-		let v = Votes(vec![0, 10, 0]);
+		let v = make_votes(vec![0, 10, 0]);
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter), 10);
-		assert_eq!(votes(pid), Votes(vec![0, 10, 0]));
+		assert_eq!(votes(pid), make_votes(vec![0, 10, 0]));
 		// Try make multiple votes
 		assert_noop!(
-			FateriumPolls::vote(Origin::signed(voter), pid, Votes(vec![0, 5, 5])),
+			FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 5, 5])),
 			Error::<Test>::MultipleVotesNotAllowed,
 		);
 		assert_noop!(
-			FateriumPolls::vote(Origin::signed(voter), pid, Votes(vec![10, 0, 0])),
+			FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![10, 0, 0])),
 			Error::<Test>::MultipleVotesNotAllowed,
 		);
 		assert_noop!(
@@ -43,13 +44,13 @@ fn vote_with_balances_should_work() {
 		);
 		// Try vote with zero balances in votes
 		assert_noop!(
-			FateriumPolls::vote(Origin::signed(voter), pid, Votes(vec![0, 0, 0])),
+			FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 0, 0])),
 			Error::<Test>::InvalidPollVotes,
 		);
 		next_block();
 		// Remove vote
 		assert_ok!(FateriumPolls::remove_vote(Origin::signed(voter), pid));
-		assert_eq!(votes(pid), Votes(vec![0, 0, 0]));
+		assert_eq!(votes(pid), make_votes(vec![0, 0, 0]));
 		assert_eq!(Balances::free_balance(voter), 20);
 		next_block();
 		// Vote again
@@ -83,10 +84,10 @@ fn emergency_cancel_should_work() {
 		set_balances(voter);
 		let pid = begin_poll(1, vec![], 10, true);
 		// Vote on poll
-		let v = Votes(vec![0, 10, 0]);
+		let v = make_votes(vec![0, 10, 0]);
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter), 10);
-		assert_eq!(votes(pid), Votes(vec![0, 10, 0]));
+		assert_eq!(votes(pid), make_votes(vec![0, 10, 0]));
 		next_block();
 		// Cancel poll
 		assert_ok!(FateriumPolls::emergency_cancel(Origin::signed(1), pid));
@@ -114,35 +115,35 @@ fn multiple_voters_zero_interest() {
 		set_balances(5);
 		let pid = begin_poll(1, vec![], 10, true);
 		// Vote on poll #1
-		let v = Votes(vec![0, 10, 0]);
+		let v = make_votes(vec![0, 10, 0]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_1, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_1), 90);
 		// Vote on poll #2
-		let v = Votes(vec![10, 0, 50]);
+		let v = make_votes(vec![10, 0, 50]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_2, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_2), 40);
 		// Vote on poll #3
-		let v = Votes(vec![10, 10, 40]);
+		let v = make_votes(vec![10, 10, 40]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_3, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_3), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_3), 40);
 		// Check that all actual deposit in the poll
-		assert_eq!(FateriumPolls::balances_pot(), 130);
+		assert_eq!(FateriumPolls::balances_pot(pid), 130);
 		fast_forward_to(10);
 		// Collect as voter #1
 		assert_ok!(FateriumPolls::collect(Origin::signed(voter_1), pid));
 		assert_eq!(Balances::free_balance(voter_1), initial_balance);
-		assert_eq!(FateriumPolls::balances_pot(), 120);
+		assert_eq!(FateriumPolls::balances_pot(pid), 120);
 		// Collect as voter #2
 		assert_ok!(FateriumPolls::collect(Origin::signed(voter_2), pid));
 		assert_eq!(Balances::free_balance(voter_2), initial_balance);
-		assert_eq!(FateriumPolls::balances_pot(), 60);
+		assert_eq!(FateriumPolls::balances_pot(pid), 60);
 		// Collect as voter #3
 		assert_ok!(FateriumPolls::collect(Origin::signed(voter_3), pid));
 		assert_eq!(Balances::free_balance(voter_3), initial_balance);
-		assert_eq!(FateriumPolls::balances_pot(), 0);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
 	});
 }
 
@@ -157,18 +158,18 @@ fn multi_voters_multi_bnfs_full_interest() {
 		let pid = begin_poll(1, vec![(bnf_1, 5000), (bnf_2, 5000)], 10, true);
 		// Vote on poll #1
 		let voter_1 = 3;
-		let v = Votes(vec![0, 0, 70]);
+		let v = make_votes(vec![0, 0, 70]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_1, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_1), 30);
 		// Vote on poll #2
 		let voter_2 = 4;
-		let v = Votes(vec![40, 30, 20]);
+		let v = make_votes(vec![40, 30, 20]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_2, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_2), 10);
 		// Check that all actual deposit in the poll
-		assert_eq!(FateriumPolls::balances_pot(), 160);
+		assert_eq!(FateriumPolls::balances_pot(pid), 160);
 		fast_forward_to(10);
 		// Collect as voter #1 - should loose 70
 		assert_noop!(
@@ -201,18 +202,18 @@ fn multi_voters_multi_bnfs_partial_interest() {
 		assert_eq!(Balances::free_balance(bnf_2), 0);
 		// Vote on poll #1
 		let voter_1 = 3;
-		let v = Votes(vec![0, 0, 70]);
+		let v = make_votes(vec![0, 0, 70]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_1, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_1), 30);
 		// Vote on poll #2
 		let voter_2 = 4;
-		let v = Votes(vec![40, 30, 20]);
+		let v = make_votes(vec![40, 30, 20]);
 		assert_ok!(Balances::set_balance(Origin::root(), voter_2, initial_balance, 0));
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, v.clone()));
 		assert_eq!(Balances::free_balance(voter_2), 10);
 		// Check that all actual deposit in the poll
-		assert_eq!(FateriumPolls::balances_pot(), 160);
+		assert_eq!(FateriumPolls::balances_pot(pid), 160);
 		fast_forward_to(10);
 		// Collect as voter #1 - should loose only 60% from 70
 		assert_ok!(FateriumPolls::collect(Origin::signed(voter_1), pid));
@@ -229,6 +230,48 @@ fn multi_voters_multi_bnfs_partial_interest() {
 	});
 }
 
+#[test]
+fn claimable_should_match_collected() {
+	new_test_ext().execute_with(|| {
+		let initial_balance = 100;
+		// Creates poll
+		set_balances(5);
+		let bnf = 11;
+		let pid = begin_poll(1, vec![(bnf, 2500)], 10, true);
+		// Vote on poll
+		let voter = 3;
+		let v = make_votes(vec![40, 30, 20]);
+		assert_ok!(Balances::set_balance(Origin::root(), voter, initial_balance, 0));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, v.clone()));
+		// Can't preview ongoing poll
+		assert_noop!(FateriumPolls::claimable(&voter, pid), Error::<Test>::CollectOnOngoingPoll,);
+		fast_forward_to(10);
+		// Not a voter or beneficiary
+		assert_noop!(
+			FateriumPolls::claimable(&42, pid),
+			Error::<Test>::AccountNotVoterOrBeneficiary,
+		);
+		// Voter should get back 75% from 40 and full stakes of lost options
+		let claimable = FateriumPolls::claimable(&voter, pid).unwrap();
+		assert_eq!(claimable.beneficiary_interest, 0);
+		assert_eq!(claimable.voter_return, make_votes(vec![30, 30, 20]));
+		assert!(!claimable.voter_collected);
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
+		assert_eq!(Balances::free_balance(voter), 10 + claimable.total());
+		// After collect nothing left to claim
+		let claimable = FateriumPolls::claimable(&voter, pid).unwrap();
+		assert_eq!(claimable.total(), 0);
+		assert!(claimable.voter_collected);
+		// Beneficiary should get 25% from 40
+		let claimable = FateriumPolls::claimable(&bnf, pid).unwrap();
+		assert_eq!(claimable.beneficiary_interest, 10);
+		assert_eq!(claimable.voter_return, make_votes(vec![0, 0, 0]));
+		assert_ok!(FateriumPolls::collect(Origin::signed(bnf), pid));
+		assert_eq!(Balances::free_balance(bnf), 10);
+		assert_eq!(FateriumPolls::claimable(&bnf, pid).unwrap().beneficiary_collected, 10);
+	});
+}
+
 #[test]
 fn not_reaching_goal_should_fail() {
 	new_test_ext().execute_with(|| {
@@ -238,7 +281,7 @@ fn not_reaching_goal_should_fail() {
 		set_balances(voter);
 		let pid = begin_poll(1, vec![(bnf, 2500)], 100, false);
 		// Vote once
-		let v = Votes(vec![0, 0, 20]);
+		let v = make_votes(vec![0, 0, 20]);
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, v.clone()));
 		// Finish poll
 		fast_forward_to(10);
@@ -265,14 +308,14 @@ fn vote_with_assets_should_work() {
 		set_balances(voter);
 		let (pid, asset_id) = begin_poll_with_asset(poll_creator, voter, vec![], voter_balance);
 		// Vote on poll
-		let v = Votes(vec![0, 10, 0]);
+		let v = make_votes(vec![0, 10, 0]);
 		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, v.clone()));
 		assert_eq!(Assets::balance(asset_id, voter), voter_balance - 10);
-		assert_eq!(votes(pid), Votes(vec![0, 10, 0]));
+		assert_eq!(votes(pid), make_votes(vec![0, 10, 0]));
 		next_block();
 		// Remove vote
 		assert_ok!(FateriumPolls::remove_vote(Origin::signed(voter), pid));
-		assert_eq!(votes(pid), Votes(vec![0, 0, 0]));
+		assert_eq!(votes(pid), make_votes(vec![0, 0, 0]));
 		assert_eq!(Assets::balance(asset_id, voter), voter_balance);
 		next_block();
 		// Vote again
@@ -297,3 +340,1173 @@ fn vote_with_assets_should_work() {
 		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
 	});
 }
+
+#[test]
+fn losing_stakes_reward_should_work() {
+	new_test_ext().execute_with(|| {
+		let initial_balance = 100;
+		set_balances(5);
+		let bnf = 11;
+		let pid =
+			begin_poll_with_rewards(1, vec![(bnf, 1000)], 10, false, RewardSettings::LosingStakes);
+		// Two voters on the winning option, two on the lost ones.
+		let voters = [(2, make_votes(vec![30, 0, 0])), (3, make_votes(vec![10, 0, 0]))];
+		let losers = [(4, make_votes(vec![0, 20, 0])), (6, make_votes(vec![0, 0, 20]))];
+		for (voter, v) in voters.iter().chain(losers.iter()) {
+			assert_ok!(Balances::set_balance(Origin::root(), *voter, initial_balance, 0));
+			assert_ok!(FateriumPolls::vote(Origin::signed(*voter), pid, v.clone()));
+		}
+		assert_eq!(FateriumPolls::balances_pot(pid), 80);
+		fast_forward_to(10);
+		// Voter #1 should get 90% from 30 and 30/40 of lost stakes
+		let claimable = FateriumPolls::claimable(&2, pid).unwrap();
+		assert_eq!(claimable.voter_return, make_votes(vec![27, 0, 0]));
+		assert_eq!(claimable.voter_reward, 30);
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), 70 + 57);
+		// Voter #2 should get 90% from 10 and 10/40 of lost stakes
+		assert_ok!(FateriumPolls::collect(Origin::signed(3), pid));
+		assert_eq!(Balances::free_balance(3), 90 + 19);
+		// Voters on lost options lose their stakes
+		for (voter, _) in losers.iter() {
+			assert_noop!(
+				FateriumPolls::collect(Origin::signed(*voter), pid),
+				Error::<Test>::NothingToCollect,
+			);
+			assert_eq!(Balances::free_balance(*voter), 80);
+		}
+		// Beneficiary should get 10% from 40
+		assert_ok!(FateriumPolls::collect(Origin::signed(bnf), pid));
+		assert_eq!(Balances::free_balance(bnf), 4);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+	});
+}
+
+#[test]
+fn creator_pool_reward_should_work() {
+	new_test_ext().execute_with(|| {
+		let initial_balance = 100;
+		let creator = 1;
+		set_balances(creator);
+		// Creator can't fund the pool without balance
+		System::set_block_number(0);
+		assert_noop!(
+			FateriumPolls::create_poll(
+				Origin::signed(creator),
+				cid(),
+				vec![],
+				RewardSettings::CreatorPool(50),
+				10,
+				GoalMode::TotalCapital,
+				3,
+				false,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Transfer,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			),
+			Error::<Test>::InsufficientFunds,
+		);
+		assert_ok!(Balances::set_balance(Origin::root(), creator, initial_balance, 0));
+		let pid =
+			begin_poll_with_rewards(creator, vec![], 10, false, RewardSettings::CreatorPool(50));
+		assert_eq!(Balances::free_balance(creator), 50);
+		assert_eq!(FateriumPolls::balances_pot(pid), 50);
+		// Vote on poll
+		let votes = [
+			(2, make_votes(vec![30, 0, 0])),
+			(3, make_votes(vec![10, 0, 0])),
+			(4, make_votes(vec![0, 20, 0])),
+		];
+		for (voter, v) in votes.iter() {
+			assert_ok!(Balances::set_balance(Origin::root(), *voter, initial_balance, 0));
+			assert_ok!(FateriumPolls::vote(Origin::signed(*voter), pid, v.clone()));
+		}
+		fast_forward_to(10);
+		// Voter #1 should get his stake and 30/40 of the pool
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(Balances::free_balance(2), initial_balance + 37 /* 37.5 */);
+		// Voter #2 should get his stake and 10/40 of the pool
+		assert_ok!(FateriumPolls::collect(Origin::signed(3), pid));
+		assert_eq!(Balances::free_balance(3), initial_balance + 12 /* 12.5 */);
+		// Voter on lost option should get his stake back
+		assert_ok!(FateriumPolls::collect(Origin::signed(4), pid));
+		assert_eq!(Balances::free_balance(4), initial_balance);
+		// The pool is spent, creator can't take it back
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(creator), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		assert_eq!(FateriumPolls::balances_pot(pid), 1);
+	});
+}
+
+#[test]
+fn creator_pool_should_return_on_failure() {
+	new_test_ext().execute_with(|| {
+		let initial_balance = 100;
+		let creator = 1;
+		let voter = 2;
+		set_balances(voter);
+		assert_ok!(Balances::set_balance(Origin::root(), creator, initial_balance, 0));
+		let pid =
+			begin_poll_with_rewards(creator, vec![], 100, false, RewardSettings::CreatorPool(50));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![10, 0, 0])));
+		fast_forward_to(10);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Failed(10));
+		// Voter gets only his stake back
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
+		assert_eq!(Balances::free_balance(voter), 20);
+		// Creator takes back the whole pool, only once
+		assert_ok!(FateriumPolls::collect(Origin::signed(creator), pid));
+		assert_eq!(Balances::free_balance(creator), initial_balance);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(creator), pid),
+			Error::<Test>::NothingToCollect,
+		);
+	});
+}
+
+#[test]
+fn tie_with_refund_policy_should_work() {
+	new_test_ext().execute_with(|| {
+		let bnf = 11;
+		let voter_1 = 2;
+		let voter_2 = 3;
+		set_balances(voter_1);
+		set_balances(voter_2);
+		let pid = begin_poll_with_tie_policy(1, vec![(bnf, 5000)], TiePolicy::Refund);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, make_votes(vec![0, 10, 0])));
+		fast_forward_to(10);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Tied(10));
+		// Beneficiary gets nothing
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(bnf), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		// Voters get their full stakes back
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_1), pid));
+		assert_eq!(Balances::free_balance(voter_1), 20);
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_2), pid));
+		assert_eq!(Balances::free_balance(voter_2), 20);
+	});
+}
+
+#[test]
+fn tie_with_split_interest_policy_should_work() {
+	new_test_ext().execute_with(|| {
+		let bnf = 11;
+		let voter_1 = 2;
+		let voter_2 = 3;
+		let voter_3 = 4;
+		set_balances(voter_1);
+		set_balances(voter_2);
+		set_balances(voter_3);
+		let pid = begin_poll_with_tie_policy(1, vec![(bnf, 5000)], TiePolicy::SplitInterest);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, make_votes(vec![0, 10, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_3), pid, make_votes(vec![0, 0, 4])));
+		fast_forward_to(10);
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Tied(10));
+		assert_eq!(poll.winning_options(), vec![0, 1]);
+		// Beneficiary gets 50% from both tied options
+		assert_ok!(FateriumPolls::collect(Origin::signed(bnf), pid));
+		assert_eq!(Balances::free_balance(bnf), 10);
+		// Voters on tied options lose 50% of their stakes
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_1), pid));
+		assert_eq!(Balances::free_balance(voter_1), 15);
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_2), pid));
+		assert_eq!(Balances::free_balance(voter_2), 15);
+		// Voter on lost option gets his stake back
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_3), pid));
+		assert_eq!(Balances::free_balance(voter_3), 20);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+	});
+}
+
+#[test]
+fn tie_with_random_policy_should_work() {
+	new_test_ext().execute_with(|| {
+		let bnf = 11;
+		let voter_1 = 2;
+		let voter_2 = 3;
+		set_balances(voter_1);
+		set_balances(voter_2);
+		let pid = begin_poll_with_tie_policy(1, vec![(bnf, 5000)], TiePolicy::Random);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, make_votes(vec![0, 10, 0])));
+		fast_forward_to(10);
+		// One of the tied options wins
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		let winning_option = match poll.status {
+			PollStatus::Finished { winning_option, end } => {
+				assert_eq!(end, 10);
+				winning_option
+			},
+			_ => panic!("poll not finished"),
+		};
+		assert!(winning_option == 0 || winning_option == 1);
+		// Beneficiary gets 50% from the winning option only
+		assert_ok!(FateriumPolls::collect(Origin::signed(bnf), pid));
+		assert_eq!(Balances::free_balance(bnf), 5);
+		let (winner, loser) =
+			if winning_option == 0 { (voter_1, voter_2) } else { (voter_2, voter_1) };
+		assert_ok!(FateriumPolls::collect(Origin::signed(winner), pid));
+		assert_eq!(Balances::free_balance(winner), 15);
+		assert_ok!(FateriumPolls::collect(Origin::signed(loser), pid));
+		assert_eq!(Balances::free_balance(loser), 20);
+	});
+}
+
+#[test]
+fn lock_voting_should_work() {
+	new_test_ext().execute_with(|| {
+		let bnf = 11;
+		let voter_1 = 2;
+		let voter_2 = 3;
+		set_balances(voter_1);
+		set_balances(voter_2);
+		let settings = PollSettings {
+			bnfs: vec![(bnf, 5000)],
+			voting_mode: VotingMode::Lock,
+			..Default::default()
+		};
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_1), pid, make_votes(vec![10, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter_2), pid, make_votes(vec![0, 4, 0])));
+		// Stakes stay in the voters' accounts, but can't be spent
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		assert_eq!(Balances::free_balance(voter_1), 20);
+		assert_eq!(FateriumPolls::locked_of(voter_1), 10);
+		assert_noop!(
+			Balances::transfer(Origin::signed(voter_1), bnf, 15),
+			pallet_balances::Error::<Test>::LiquidityRestrictions,
+		);
+		// Locked stakes can't be used for voting again
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(voter_1), pid, make_votes(vec![11, 0, 0])),
+			Error::<Test>::InsufficientFunds,
+		);
+		fast_forward_to(10);
+		// Beneficiary is paid by the voters on their collect
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(bnf), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		let claimable = FateriumPolls::claimable(&voter_1, pid).unwrap();
+		assert_eq!(claimable.voter_return, make_votes(vec![5, 0, 0]));
+		assert_eq!(claimable.voter_locked, 10);
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_1), pid));
+		assert_eq!(Balances::free_balance(voter_1), 15);
+		assert_eq!(Balances::free_balance(bnf), 5);
+		assert_eq!(FateriumPolls::locked_of(voter_1), 0);
+		assert_ok!(Balances::transfer(Origin::signed(voter_1), bnf, 15));
+		// Voter on lost option gets his stake unlocked
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter_2), pid));
+		assert_eq!(Balances::free_balance(voter_2), 20);
+		assert_eq!(FateriumPolls::locked_of(voter_2), 0);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(voter_2), pid),
+			Error::<Test>::NothingToCollect,
+		);
+	});
+}
+
+#[test]
+fn lock_voting_remove_vote_should_work() {
+	new_test_ext().execute_with(|| {
+		let voter = 2;
+		set_balances(voter);
+		let settings = PollSettings { voting_mode: VotingMode::Lock, ..Default::default() };
+		let pid_1 = begin_poll_with(1, settings);
+		let settings = PollSettings { voting_mode: VotingMode::Lock, ..Default::default() };
+		let pid_2 = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid_1, make_votes(vec![15, 0, 0])));
+		// Locks of different polls are summed up
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(voter), pid_2, make_votes(vec![10, 0, 0])),
+			Error::<Test>::InsufficientFunds,
+		);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid_2, make_votes(vec![5, 0, 0])));
+		assert_eq!(FateriumPolls::locked_of(voter), 20);
+		assert_ok!(FateriumPolls::remove_vote(Origin::signed(voter), pid_1));
+		assert_eq!(FateriumPolls::locked_of(voter), 5);
+		assert_ok!(FateriumPolls::remove_vote(Origin::signed(voter), pid_2));
+		assert_eq!(FateriumPolls::locked_of(voter), 0);
+		assert_eq!(Balances::free_balance(voter), 20);
+		assert_ok!(Balances::transfer(Origin::signed(voter), 11, 20));
+	});
+}
+
+#[test]
+fn lock_voting_should_fail_with_asset_or_losing_stakes() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid(),
+				vec![],
+				RewardSettings::LosingStakes,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				false,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Lock,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			),
+			Error::<Test>::InvalidPollDetails,
+		);
+		// Assets can't be locked
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 20));
+		assert_noop!(
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid(),
+				vec![],
+				RewardSettings::None,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				false,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Lock,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Asset(0),
+				1,
+				10,
+			),
+			Error::<Test>::InvalidPollCurrency,
+		);
+	});
+}
+
+#[test]
+fn create_poll_should_enforce_limits() {
+	new_test_ext().execute_with(|| {
+		let create = |cid: Vec<u8>, bnfs: Vec<(u64, u32, _)>, goal, options_count, start, end| {
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid,
+				bnfs,
+				RewardSettings::None,
+				goal,
+				GoalMode::TotalCapital,
+				options_count,
+				false,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Transfer,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				start,
+				end,
+			)
+		};
+		let cid = cid();
+		assert_noop!(
+			create((0..45).collect(), vec![], 10, 3, 1, 10),
+			Error::<Test>::InvalidCidEncoding
+		);
+		assert_noop!(
+			create((0..130).collect(), vec![], 10, 3, 1, 10),
+			Error::<Test>::InvalidIpfsCid
+		);
+		assert_noop!(create(cid.clone(), vec![], 10, 1, 1, 10), Error::<Test>::TooFewPollOptions);
+		assert_noop!(create(cid.clone(), vec![], 10, 11, 1, 10), Error::<Test>::TooManyPollOptions);
+		let bnfs = (0..11).map(|i| (i, 100, None)).collect();
+		assert_noop!(create(cid.clone(), bnfs, 10, 3, 1, 10), Error::<Test>::TooManyBeneficiaries);
+		assert_noop!(create(cid.clone(), vec![], 0, 3, 1, 10), Error::<Test>::PollGoalTooLow);
+		assert_noop!(create(cid.clone(), vec![], 10, 3, 1, 5), Error::<Test>::PollTooShort);
+		assert_noop!(create(cid.clone(), vec![], 10, 3, 1, 102), Error::<Test>::PollTooLong);
+		assert_ok!(create(cid.clone(), vec![], 10, 10, 1, 6));
+		let bnfs = (0..10).map(|i| (i, 100, None)).collect();
+		assert_ok!(create(cid, bnfs, 1, 3, 1, 101));
+	});
+}
+
+#[test]
+fn poll_deposit_should_be_reserved_and_returned() {
+	new_test_ext().execute_with(|| {
+		PollDepositBase::set(5);
+		PollDepositPerByte::set(1);
+		PollDepositPerBeneficiary::set(2);
+		let (creator, voter) = (1, 5);
+		set_balances(voter);
+		// Creator can't afford the deposit of 5 + 36 * 1 + 1 * 2 = 43, as CID is stored in binary
+		assert_ok!(Balances::set_balance(Origin::root(), creator, 42, 0));
+		assert_noop!(
+			FateriumPolls::create_poll(
+				Origin::signed(creator),
+				cid(),
+				vec![(2, 1000, None)],
+				RewardSettings::None,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				true,
+				true,
+				TiePolicy::Refund,
+				VotingMode::Transfer,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			),
+			Error::<Test>::InsufficientDeposit
+		);
+		// Deposit is reserved on creation
+		assert_ok!(Balances::set_balance(Origin::root(), creator, 100, 0));
+		let pid = begin_poll(creator, vec![(2, 1000)], 10, true);
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap().deposit, 43);
+		assert_eq!(Balances::reserved_balance(creator), 43);
+		assert_eq!(Balances::free_balance(creator), 57);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 10, 0])));
+		// Deposit is kept after the end of the poll, and returned when it's reaped
+		fast_forward_to(10);
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap().deposit, 43);
+		assert_eq!(Balances::reserved_balance(creator), 43);
+		let reap = |pid| {
+			FateriumPolls::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+			assert_ok!(FateriumPolls::reap_poll(Origin::signed(voter), pid));
+			FateriumPolls::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+		};
+		reap(pid);
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(Balances::free_balance(creator), 100);
+		// Deposit is kept after emergency cancel as well
+		let pid = begin_poll(creator, vec![], 10, true);
+		assert_eq!(Balances::reserved_balance(creator), 41);
+		assert_ok!(FateriumPolls::emergency_cancel(Origin::signed(creator), pid));
+		assert_eq!(Balances::reserved_balance(creator), 41);
+		reap(pid);
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(Balances::free_balance(creator), 100);
+	});
+}
+
+#[test]
+fn force_cancel_should_slash_deposit() {
+	new_test_ext().execute_with(|| {
+		PollDepositBase::set(4);
+		let (creator, voter) = (1, 5);
+		set_balances(voter);
+		assert_ok!(Balances::set_balance(Origin::root(), creator, 20, 0));
+		let pid = begin_poll(creator, vec![], 10, true);
+		assert_eq!(Balances::reserved_balance(creator), 4);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 10, 0])));
+		// Only force origin can force cancel
+		assert_noop!(
+			FateriumPolls::force_cancel(Origin::signed(creator), pid),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		let total_issuance = Balances::total_issuance();
+		assert_ok!(FateriumPolls::force_cancel(Origin::root(), pid));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.status, PollStatus::Cancelled(2));
+		assert_eq!(poll.deposit, 0);
+		// Deposit is slashed
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(Balances::free_balance(creator), 16);
+		assert_eq!(Balances::total_issuance(), total_issuance - 4);
+		assert_noop!(
+			FateriumPolls::force_cancel(Origin::root(), pid),
+			Error::<Test>::PollAlreadyFinished
+		);
+		// Voter collects the stake back
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
+		assert_eq!(Balances::free_balance(voter), 20);
+	});
+}
+
+#[test]
+fn force_cancel_should_slash_deposit_of_ended_poll() {
+	new_test_ext().execute_with(|| {
+		PollDepositBase::set(4);
+		let creator = 1;
+		assert_ok!(Balances::set_balance(Origin::root(), creator, 20, 0));
+		let pid = begin_poll(creator, vec![], 10, true);
+		// Creator cancels the poll first, which doesn't save the deposit
+		assert_ok!(FateriumPolls::emergency_cancel(Origin::signed(creator), pid));
+		assert_eq!(Balances::reserved_balance(creator), 4);
+		let total_issuance = Balances::total_issuance();
+		assert_ok!(FateriumPolls::force_cancel(Origin::root(), pid));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::DepositSlashed {
+			poll_id: pid,
+			who: creator,
+			amount: 4,
+		}));
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(Balances::free_balance(creator), 16);
+		assert_eq!(Balances::total_issuance(), total_issuance - 4);
+		// Deposit of a poll which ended is slashed as well, without cancelling it
+		let ended = begin_poll(creator, vec![], 10, true);
+		fast_forward_to(10);
+		assert_ok!(FateriumPolls::force_cancel(Origin::root(), ended));
+		assert_eq!(FateriumPolls::poll_details_of(ended).unwrap().status, PollStatus::Failed(10));
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(Balances::free_balance(creator), 12);
+		// Reaped polls can't be slashed
+		FateriumPolls::on_idle(10, Weight::from_ref_time(u64::MAX));
+		assert_ok!(FateriumPolls::reap_poll(Origin::signed(6), pid));
+		FateriumPolls::on_idle(11, Weight::from_ref_time(u64::MAX));
+		assert_noop!(FateriumPolls::force_cancel(Origin::root(), pid), Error::<Test>::PollInvalid);
+	});
+}
+
+#[test]
+fn withdraw_votes_should_work() {
+	new_test_ext().execute_with(|| {
+		let voter = 2;
+		set_balances(voter);
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![5, 10, 0])));
+		assert_eq!(Balances::free_balance(voter), 5);
+		// Can't withdraw more than voted, or invalid votes
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(voter), pid, make_votes(vec![0, 0, 1])),
+			Error::<Test>::InsufficientVotes,
+		);
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(voter), pid, make_votes(vec![0, 0, 0])),
+			Error::<Test>::InvalidPollVotes,
+		);
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(voter), pid, make_votes(vec![1, 1])),
+			Error::<Test>::InvalidPollVotes,
+		);
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(3), pid, make_votes(vec![1, 0, 0])),
+			Error::<Test>::VotesNotExist,
+		);
+		// Withdraw a part of votes
+		assert_ok!(FateriumPolls::withdraw_votes(
+			Origin::signed(voter),
+			pid,
+			make_votes(vec![5, 4, 0])
+		));
+		assert_eq!(Balances::free_balance(voter), 14);
+		assert_eq!(votes(pid), make_votes(vec![0, 6, 0]));
+		assert_eq!(
+			FateriumPolls::voting_of((voter, pid)).unwrap().votes,
+			make_votes(vec![0, 6, 0])
+		);
+		// Withdraw the rest of votes
+		assert_ok!(FateriumPolls::withdraw_votes(
+			Origin::signed(voter),
+			pid,
+			make_votes(vec![0, 6, 0])
+		));
+		assert_eq!(Balances::free_balance(voter), 20);
+		assert_eq!(votes(pid), make_votes(vec![0, 0, 0]));
+		assert!(FateriumPolls::voting_of((voter, pid)).is_none());
+		// Can't withdraw from finished poll
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 10, 0])));
+		fast_forward_to(10);
+		assert_noop!(
+			FateriumPolls::withdraw_votes(Origin::signed(voter), pid, make_votes(vec![0, 1, 0])),
+			Error::<Test>::PollAlreadyFinished,
+		);
+	});
+}
+
+#[test]
+fn lock_voting_withdraw_votes_should_work() {
+	new_test_ext().execute_with(|| {
+		let voter = 2;
+		set_balances(voter);
+		let settings = PollSettings { voting_mode: VotingMode::Lock, ..Default::default() };
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![15, 5, 0])));
+		assert_eq!(FateriumPolls::locked_of(voter), 20);
+		assert_ok!(FateriumPolls::withdraw_votes(
+			Origin::signed(voter),
+			pid,
+			make_votes(vec![5, 5, 0])
+		));
+		assert_eq!(FateriumPolls::locked_of(voter), 10);
+		assert_eq!(votes(pid), make_votes(vec![10, 0, 0]));
+		assert_eq!(Balances::free_balance(voter), 20);
+		assert_ok!(Balances::transfer(Origin::signed(voter), 11, 10));
+	});
+}
+
+#[test]
+fn move_votes_should_work() {
+	new_test_ext().execute_with(|| {
+		let (voter, other) = (2, 3);
+		set_balances(voter);
+		set_balances(other);
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![5, 10, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(other), pid, make_votes(vec![0, 0, 7])));
+		// Can't move more than voted on the option, or invalid options
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 0, 2, 6),
+			Error::<Test>::InsufficientVotes,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 2, 0, 1),
+			Error::<Test>::InsufficientVotes,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 0, 0, 1),
+			Error::<Test>::InvalidPollVotes,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 0, 3, 1),
+			Error::<Test>::InvalidPollVotes,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 0, 2, 0),
+			Error::<Test>::InvalidPollVotes,
+		);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(4), pid, 0, 2, 1),
+			Error::<Test>::VotesNotExist,
+		);
+		// Move a part of votes, without any balance movement
+		assert_ok!(FateriumPolls::move_votes(Origin::signed(voter), pid, 1, 2, 4));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::VotesMoved {
+			voter,
+			poll_id: pid,
+			from: 1,
+			to: 2,
+			amount: 4,
+		}));
+		assert_eq!(Balances::free_balance(voter), 5);
+		assert_eq!(FateriumPolls::balances_pot(pid), 22);
+		assert_eq!(votes(pid), make_votes(vec![5, 6, 11]));
+		assert_eq!(
+			FateriumPolls::voting_of((voter, pid)).unwrap().votes,
+			make_votes(vec![5, 6, 4])
+		);
+		// Moved votes are returned as any others
+		assert_ok!(FateriumPolls::withdraw_votes(
+			Origin::signed(voter),
+			pid,
+			make_votes(vec![5, 6, 4])
+		));
+		assert_eq!(Balances::free_balance(voter), 20);
+		assert_eq!(votes(pid), make_votes(vec![0, 0, 7]));
+		// Can't move in finished poll
+		fast_forward_to(10);
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(other), pid, 2, 0, 1),
+			Error::<Test>::PollAlreadyFinished,
+		);
+	});
+}
+
+#[test]
+fn single_vote_move_votes_should_move_all_votes() {
+	new_test_ext().execute_with(|| {
+		let voter = 2;
+		set_balances(voter);
+		let pid = begin_poll(1, vec![], 10, false);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 10, 0])));
+		// Votes can't be split between options
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 1, 0, 4),
+			Error::<Test>::MultipleVotesNotAllowed,
+		);
+		assert_ok!(FateriumPolls::move_votes(Origin::signed(voter), pid, 1, 0, 10));
+		assert_eq!(votes(pid), make_votes(vec![10, 0, 0]));
+		assert_eq!(
+			FateriumPolls::voting_of((voter, pid)).unwrap().votes,
+			make_votes(vec![10, 0, 0])
+		);
+	});
+}
+
+#[test]
+fn move_votes_should_fail_if_disabled() {
+	new_test_ext().execute_with(|| {
+		let voter = 2;
+		set_balances(voter);
+		let pid = begin_poll_with(1, PollSettings { movable_votes: false, ..Default::default() });
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![0, 10, 0])));
+		assert_noop!(
+			FateriumPolls::move_votes(Origin::signed(voter), pid, 1, 0, 10),
+			Error::<Test>::VoteMovesNotAllowed,
+		);
+	});
+}
+
+/// Creates a poll at block 1, which starts at block 5 and ends at block 10.
+fn create_future_poll(creator: u64) -> PollIndex {
+	System::set_block_number(1);
+	assert_ok!(FateriumPolls::create_poll(
+		Origin::signed(creator),
+		cid(),
+		vec![],
+		RewardSettings::None,
+		10,
+		GoalMode::TotalCapital,
+		3,
+		true,
+		true,
+		TiePolicy::Refund,
+		VotingMode::Transfer,
+		None,
+		Eligibility::Anyone,
+		PollKind::Plurality,
+		PollCurrency::Native,
+		5,
+		10,
+	));
+	FateriumPolls::poll_count()
+}
+
+#[test]
+fn update_poll_should_work() {
+	new_test_ext().execute_with(|| {
+		PollDepositPerBeneficiary::set(2);
+		let creator = 1;
+		set_balances(creator);
+		let pid = create_future_poll(creator);
+		// Update beneficiaries, reward settings, goal and end
+		assert_ok!(FateriumPolls::update_poll(
+			Origin::signed(creator),
+			pid,
+			None,
+			Some(vec![(11, 5000, None)]),
+			Some(RewardSettings::CreatorPool(5)),
+			Some(20),
+			Some(15),
+		));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Updated {
+			poll_id: pid,
+			cid: None,
+			beneficiaries: Some(vec![Beneficiary::new(11, 5000, None)]),
+			reward_settings: Some(RewardSettings::CreatorPool(5)),
+			goal: Some(20),
+			end: Some(15),
+		}));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.beneficiaries.to_vec(), vec![Beneficiary::new(11, 5000, None)]);
+		assert_eq!(poll.reward_settings, RewardSettings::CreatorPool(5));
+		assert_eq!(poll.goal, 20);
+		assert_eq!(poll.status, PollStatus::Ongoing { start: 5, end: 15 });
+		// Deposit is increased and reward pool is transferred to the pot
+		assert_eq!(poll.deposit, 2);
+		assert_eq!(Balances::reserved_balance(creator), 2);
+		assert_eq!(Balances::free_balance(creator), 13);
+		assert_eq!(FateriumPolls::balances_pot(pid), 5);
+		// Remove beneficiaries and reward pool, which are returned to the creator
+		assert_ok!(FateriumPolls::update_poll(
+			Origin::signed(creator),
+			pid,
+			Some(b"zdj7Wg2Qkk4mYgAkVU1kppfQ2sMGz5zPwERVpeWmxCQLDxVoC".to_vec()),
+			Some(vec![]),
+			Some(RewardSettings::None),
+			None,
+			None,
+		));
+		let poll = FateriumPolls::poll_details_of(pid).unwrap();
+		assert_eq!(poll.ipfs_cid.to_vec(), FateriumPolls::normalize_cid(&cid()).unwrap());
+		assert_eq!(poll.goal, 20);
+		assert_eq!(poll.deposit, 0);
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(Balances::free_balance(creator), 20);
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+		// End of the poll is rescheduled
+		fast_forward_to(10);
+		assert!(FateriumPolls::poll_details_of(pid).unwrap().status.is_ongoing());
+		fast_forward_to(15);
+		assert!(!FateriumPolls::poll_details_of(pid).unwrap().status.is_ongoing());
+	});
+}
+
+#[test]
+fn update_poll_should_fail() {
+	new_test_ext().execute_with(|| {
+		let creator = 1;
+		let pid = create_future_poll(creator);
+		assert_noop!(
+			FateriumPolls::update_poll(Origin::signed(2), pid, None, None, None, Some(20), None),
+			Error::<Test>::AccountNotAuthor,
+		);
+		assert_noop!(
+			FateriumPolls::update_poll(Origin::signed(creator), 2, None, None, None, None, None),
+			Error::<Test>::PollInvalid,
+		);
+		assert_noop!(
+			FateriumPolls::update_poll(
+				Origin::signed(creator),
+				pid,
+				None,
+				Some(vec![(11, 5000, None), (12, 5001, None)]),
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::InvalidPollDetails,
+		);
+		assert_noop!(
+			FateriumPolls::update_poll(
+				Origin::signed(creator),
+				pid,
+				None,
+				None,
+				None,
+				Some(0),
+				None
+			),
+			Error::<Test>::PollGoalTooLow,
+		);
+		assert_noop!(
+			FateriumPolls::update_poll(
+				Origin::signed(creator),
+				pid,
+				None,
+				None,
+				None,
+				None,
+				Some(7)
+			),
+			Error::<Test>::PollTooShort,
+		);
+		assert_noop!(
+			FateriumPolls::update_poll(
+				Origin::signed(creator),
+				pid,
+				Some(b"Qm".to_vec()),
+				None,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::InvalidCidEncoding,
+		);
+		// Can't update a poll which has votes
+		let set_votes = |stakes| {
+			PollDetailsOf::<Test>::mutate(pid, |poll| {
+				poll.as_mut().unwrap().votes = make_votes(stakes);
+			})
+		};
+		set_votes(vec![1, 0, 0]);
+		assert_noop!(
+			FateriumPolls::update_poll(
+				Origin::signed(creator),
+				pid,
+				None,
+				None,
+				None,
+				Some(20),
+				None
+			),
+			Error::<Test>::PollHasVotes,
+		);
+		set_votes(vec![0, 0, 0]);
+		// Can't update after the start of the poll
+		fast_forward_to(5);
+		assert_noop!(
+			FateriumPolls::update_poll(
+				Origin::signed(creator),
+				pid,
+				None,
+				None,
+				None,
+				Some(20),
+				None
+			),
+			Error::<Test>::PollAlreadyStarted,
+		);
+	});
+}
+
+#[test]
+fn delegate_and_undelegate_should_work() {
+	new_test_ext().execute_with(|| {
+		let (delegate, currency) = (2, PollCurrency::Native);
+		assert_noop!(
+			FateriumPolls::delegate(Origin::signed(delegate), delegate, currency, 10),
+			Error::<Test>::InvalidDelegation,
+		);
+		assert_noop!(
+			FateriumPolls::delegate(Origin::signed(3), delegate, currency, 0),
+			Error::<Test>::InvalidDelegation,
+		);
+		for delegator in 3..6 {
+			assert_ok!(FateriumPolls::delegate(Origin::signed(delegator), delegate, currency, 10));
+		}
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Delegated {
+			delegator: 5,
+			delegate,
+			currency,
+			amount: 10,
+		}));
+		assert_noop!(
+			FateriumPolls::delegate(Origin::signed(6), delegate, currency, 10),
+			Error::<Test>::TooManyDelegators,
+		);
+		// Delegating again replaces the allowance
+		assert_ok!(FateriumPolls::delegate(Origin::signed(4), delegate, currency, 7));
+		assert_eq!(
+			FateriumPolls::delegations_of((delegate, currency)).to_vec(),
+			vec![(3, 10), (4, 7), (5, 10)],
+		);
+		assert_ok!(FateriumPolls::undelegate(Origin::signed(4), delegate, currency));
+		System::assert_last_event(Event::FateriumPolls(crate::Event::Undelegated {
+			delegator: 4,
+			delegate,
+			currency,
+		}));
+		assert_eq!(
+			FateriumPolls::delegations_of((delegate, currency)).to_vec(),
+			vec![(3, 10), (5, 10)]
+		);
+		assert_noop!(
+			FateriumPolls::undelegate(Origin::signed(4), delegate, currency),
+			Error::<Test>::DelegationNotExist,
+		);
+		assert_noop!(
+			FateriumPolls::undelegate(Origin::signed(3), delegate, PollCurrency::Asset(0)),
+			Error::<Test>::DelegationNotExist,
+		);
+	});
+}
+
+#[test]
+fn delegated_votes_should_be_paid_back_on_collect() {
+	new_test_ext().execute_with(|| {
+		let (voter, currency) = (2, PollCurrency::Native);
+		assert_ok!(Balances::set_balance(Origin::root(), voter, 16, 0));
+		for acc in [3, 4] {
+			set_balances(acc);
+		}
+		let pid = begin_poll(1, vec![(11, 1000)], 10, true);
+		assert_ok!(FateriumPolls::delegate(Origin::signed(3), voter, currency, 10));
+		assert_ok!(FateriumPolls::delegate(Origin::signed(4), voter, currency, 15));
+		// Own funds are spent first, keeping the voter alive, and the rest is paid by delegators
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![30, 0, 0])));
+		assert_eq!(Balances::free_balance(voter), 1);
+		assert_eq!(Balances::free_balance(3), 14);
+		assert_eq!(Balances::free_balance(4), 11);
+		assert_eq!(FateriumPolls::delegated_stakes_of((voter, pid)).to_vec(), vec![(3, 6), (4, 9)]);
+		assert_eq!(FateriumPolls::delegations_of((voter, currency)).to_vec(), vec![(3, 4), (4, 6)]);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![10, 0, 0])));
+		assert_eq!(Balances::free_balance(voter), 1);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(4), 5);
+		assert_eq!(FateriumPolls::balances_pot(pid), 40);
+		assert_eq!(
+			FateriumPolls::delegated_stakes_of((voter, pid)).to_vec(),
+			vec![(3, 10), (4, 15)]
+		);
+		// Allowances which are spent entirely are removed
+		assert!(FateriumPolls::delegations_of((voter, currency)).is_empty());
+		assert_noop!(
+			FateriumPolls::undelegate(Origin::signed(3), voter, currency),
+			Error::<Test>::DelegationNotExist,
+		);
+		fast_forward_to(10);
+		// Delegators get their share of 90% from 40
+		assert_ok!(FateriumPolls::collect(Origin::signed(voter), pid));
+		assert_eq!(Balances::free_balance(3), 10 + 9);
+		assert_eq!(Balances::free_balance(4), 5 + 13);
+		assert_eq!(Balances::free_balance(voter), 1 + 14);
+		// Stakes are kept until the poll is reaped
+		assert_eq!(
+			FateriumPolls::delegated_stakes_of((voter, pid)).to_vec(),
+			vec![(3, 10), (4, 15)]
+		);
+		assert_ok!(FateriumPolls::collect(Origin::signed(11), pid));
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+	});
+}
+
+#[test]
+fn delegators_should_be_kept_alive() {
+	new_test_ext().execute_with(|| {
+		let (voter, delegator, currency) = (2, 3, PollCurrency::Native);
+		assert_ok!(Balances::set_balance(Origin::root(), delegator, 10, 0));
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::delegate(Origin::signed(delegator), voter, currency, 10));
+		// Delegator can't spend the existential deposit
+		assert_noop!(
+			FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![10, 0, 0])),
+			Error::<Test>::InsufficientFunds,
+		);
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![9, 0, 0])));
+		assert_eq!(Balances::free_balance(delegator), 1);
+		assert_eq!(FateriumPolls::delegated_stakes_of((voter, pid)).to_vec(), vec![(delegator, 9)]);
+		assert_eq!(FateriumPolls::balances_pot(pid), 9);
+	});
+}
+
+#[test]
+fn delegated_votes_should_be_paid_back_on_withdraw_and_remove() {
+	new_test_ext().execute_with(|| {
+		let (voter, delegator, currency) = (2, 3, PollCurrency::Native);
+		set_balances(delegator);
+		assert_ok!(Balances::set_balance(Origin::root(), voter, 11, 0));
+		let pid = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::delegate(Origin::signed(delegator), voter, currency, 10));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![10, 10, 0])));
+		assert_eq!(Balances::free_balance(voter), 1);
+		assert_eq!(Balances::free_balance(delegator), 10);
+		// Withdrawn capital is paid back pro rata to stakes
+		assert_ok!(FateriumPolls::withdraw_votes(
+			Origin::signed(voter),
+			pid,
+			make_votes(vec![4, 0, 0])
+		));
+		System::assert_has_event(Event::FateriumPolls(crate::Event::DelegatorPaid {
+			delegator,
+			delegate: voter,
+			poll_id: pid,
+			amount: 2,
+		}));
+		assert_eq!(Balances::free_balance(voter), 3);
+		assert_eq!(Balances::free_balance(delegator), 12);
+		assert_eq!(FateriumPolls::delegated_stakes_of((voter, pid)).to_vec(), vec![(delegator, 8)]);
+		assert_ok!(FateriumPolls::remove_vote(Origin::signed(voter), pid));
+		assert_eq!(Balances::free_balance(voter), 11);
+		assert_eq!(Balances::free_balance(delegator), 20);
+		assert!(FateriumPolls::delegated_stakes_of((voter, pid)).is_empty());
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+	});
+}
+
+#[test]
+fn lock_voting_should_not_spend_delegated_votes() {
+	new_test_ext().execute_with(|| {
+		let (voter, delegator, currency) = (2, 3, PollCurrency::Native);
+		set_balances(voter);
+		set_balances(delegator);
+		let settings = PollSettings { voting_mode: VotingMode::Lock, ..Default::default() };
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::delegate(Origin::signed(delegator), voter, currency, 10));
+		assert_ok!(FateriumPolls::vote(Origin::signed(voter), pid, make_votes(vec![5, 0, 0])));
+		assert_eq!(FateriumPolls::locked_of(voter), 5);
+		assert_eq!(Balances::free_balance(delegator), 20);
+		assert_eq!(
+			FateriumPolls::delegations_of((voter, currency)).to_vec(),
+			vec![(delegator, 10)]
+		);
+		assert!(FateriumPolls::delegated_stakes_of((voter, pid)).is_empty());
+	});
+}
+
+#[test]
+fn vesting_should_release_interest_linearly() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		let vesting = VestingSchedule { start_offset: 2, duration: 4, cliff: 2 };
+		let settings =
+			PollSettings { bnfs: vec![(11, 5000)], vesting: Some(vesting), ..Default::default() };
+		let pid = begin_poll_with(1, settings);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), pid, make_votes(vec![10, 0, 0])));
+		// Vesting starts 2 blocks after the end, and nothing is vested until the cliff
+		fast_forward_to(13);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(11), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		fast_forward_to(14);
+		assert_eq!(FateriumPolls::claimable(&11, pid).unwrap().beneficiary_interest, 2);
+		assert_ok!(FateriumPolls::collect(Origin::signed(11), pid));
+		assert_eq!(Balances::free_balance(11), 2);
+		assert_noop!(
+			FateriumPolls::collect(Origin::signed(11), pid),
+			Error::<Test>::NothingToCollect,
+		);
+		fast_forward_to(15);
+		assert_ok!(FateriumPolls::collect(Origin::signed(11), pid));
+		assert_eq!(Balances::free_balance(11), 3);
+		// Rest of the interest is vested at the end of the schedule
+		fast_forward_to(20);
+		assert_ok!(FateriumPolls::collect(Origin::signed(11), pid));
+		assert_eq!(Balances::free_balance(11), 5);
+		assert_eq!(FateriumPolls::poll_details_of(pid).unwrap().beneficiaries[0].collected, 5);
+		assert_ok!(FateriumPolls::collect(Origin::signed(2), pid));
+		assert_eq!(FateriumPolls::balances_pot(pid), 0);
+	});
+}
+
+#[test]
+fn vesting_should_fail_if_invalid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		let create = |vesting, voting_mode| {
+			FateriumPolls::create_poll(
+				Origin::signed(1),
+				cid(),
+				vec![(11, 5000, Some(vesting))],
+				RewardSettings::None,
+				10,
+				GoalMode::TotalCapital,
+				3,
+				true,
+				true,
+				TiePolicy::Refund,
+				voting_mode,
+				None,
+				Eligibility::Anyone,
+				PollKind::Plurality,
+				PollCurrency::Native,
+				1,
+				10,
+			)
+		};
+		let zero = VestingSchedule { start_offset: 0, duration: 0, cliff: 0 };
+		assert_noop!(create(zero, VotingMode::Transfer), Error::<Test>::InvalidPollDetails);
+		let cliff = VestingSchedule { start_offset: 0, duration: 4, cliff: 5 };
+		assert_noop!(create(cliff, VotingMode::Transfer), Error::<Test>::InvalidPollDetails);
+		// Interest of lock voting polls is paid by voters, so it can't vest
+		let valid = VestingSchedule { start_offset: 0, duration: 4, cliff: 0 };
+		assert_noop!(create(valid, VotingMode::Lock), Error::<Test>::InvalidPollDetails);
+		assert_ok!(create(valid, VotingMode::Transfer));
+	});
+}
+
+#[test]
+fn polls_should_hold_funds_in_own_pots() {
+	new_test_ext().execute_with(|| {
+		set_balances(2);
+		set_balances(3);
+		let first = begin_poll(1, vec![], 10, true);
+		let second = begin_poll(1, vec![], 10, true);
+		assert_ok!(FateriumPolls::vote(Origin::signed(2), first, make_votes(vec![6, 0, 0])));
+		assert_ok!(FateriumPolls::vote(Origin::signed(3), second, make_votes(vec![4, 0, 0])));
+		assert_ne!(FateriumPolls::pot_account_id(first), FateriumPolls::pot_account_id(second));
+		assert_eq!(FateriumPolls::balances_pot(first), 6);
+		assert_eq!(FateriumPolls::balances_pot(second), 4);
+		// Shortage in the pot of one poll doesn't touch funds of another
+		let pot = FateriumPolls::pot_account_id(second);
+		assert_ok!(Balances::set_balance(Origin::root(), pot, 2, 0));
+		assert_noop!(
+			FateriumPolls::remove_vote(Origin::signed(3), second),
+			Error::<Test>::PotInsufficientFunds,
+		);
+		assert_ok!(FateriumPolls::remove_vote(Origin::signed(2), first));
+		assert_eq!(Balances::free_balance(2), 20);
+		assert_eq!(FateriumPolls::balances_pot(first), 0);
+		assert_eq!(FateriumPolls::balances_pot(second), 2);
+	});
+}
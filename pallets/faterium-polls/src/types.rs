@@ -1,7 +1,13 @@
 //! Various basic types for use in the assets pallet.
 
 use super::*;
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H256, U256};
+use sp_runtime::traits::{UniqueSaturatedFrom, UniqueSaturatedInto};
 
 pub type DepositBalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
@@ -9,6 +15,7 @@ pub type DepositBalanceOf<T> =
 pub type IpfsCid = Vec<u8>;
 
 #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum PollCurrency<AssetId> {
 	/// AssetId from the Assets Pallet.
 	Asset(AssetId),
@@ -17,66 +24,328 @@ pub enum PollCurrency<AssetId> {
 }
 
 /// Enumeration for the poll reward settings.
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
-pub enum RewardSettings {
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RewardSettings<Balance> {
 	/// No rewards for participators/winners in the poll.
 	None,
+	/// Stakes on lost options are split among voters of the winning option, pro rata to their
+	/// stake on the winning option. Voters lose their stakes on lost options.
+	LosingStakes,
+	/// Creator funds a reward pool on poll creation, which is split among voters of the winning
+	/// option, pro rata to their stake on the winning option. If the poll fails, gets cancelled or
+	/// nobody voted for the winning option, the pool is returned to the creator.
+	CreatorPool(Balance),
+}
+
+/// Enumeration for the poll tie policy, used if several options have the same highest stake.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TiePolicy {
+	/// Poll ends as `Tied`, and all stakes are returned to the voters.
+	Refund,
+	/// Poll ends as `Tied`, and all tied options are treated as winning ones, so beneficiaries
+	/// get their interest from the stakes on every tied option.
+	SplitInterest,
+	/// Tie is broken with on-chain randomness, and poll ends as `Finished`.
+	///
+	/// NOTE: the randomness is only as strong as the `Randomness` of the runtime. A source like
+	/// `pallet_randomness_collective_flip` can be predicted and influenced by the author of the
+	/// block in which the poll ends, who can then pick the winner among the tied options.
+	Random,
+}
+
+/// Enumeration for the way voters' stakes are held while the poll is not settled.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VotingMode {
+	/// Stakes are transferred to the pot of the pallet, and returned to the voters on collect.
+	Transfer,
+	/// Stakes stay in the voters' accounts under a lock. On the voter's collect, only the
+	/// beneficiaries' interest is transferred from the voter, and the rest is unlocked.
+	///
+	/// Available only for polls in the native currency and without `LosingStakes` rewards.
+	Lock,
+}
+
+/// Settings of a sealed poll, where voters commit the hash of their votes until the end of the
+/// poll, and reveal them in the reveal period after it. Only revealed votes are tallied.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SealedSettings<BlockNumber> {
+	/// The number of blocks after the end of the poll, in which votes can be revealed.
+	pub reveal_period: BlockNumber,
+	/// The part of the stake slashed from voters who don't reveal their votes, in basis points
+	/// (e.g. 5 = 0.05%; 1000 = 10%). Zero refunds the whole stake.
+	pub unrevealed_penalty: u32,
+}
+
+/// Votes committed to a sealed poll, which are not revealed yet.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Commitment<Balance, Hash> {
+	/// The hash of the votes and a salt, chosen by the voter.
+	pub hash: Hash,
+	/// The stake held for the votes, which must be equal to their capital.
+	pub stake: Balance,
+}
+
+/// Enumeration for the rule an account should satisfy to vote in the poll.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Eligibility<AssetId, Balance> {
+	/// Any account can vote.
+	Anyone,
+	/// Only accounts added to the allowlist of the poll by its creator can vote.
+	Allowlist,
+	/// Only accounts which proved to be leaves of the Merkle tree with the given root can vote,
+	/// so large lists can stay off-chain. Leaves are BLAKE2-256 hashes of SCALE-encoded
+	/// accounts, and every node is the hash of its two children in ascending order.
+	MerkleRoot(H256),
+	/// Only accounts which hold at least the given balance of the asset can vote.
+	AssetHolder { asset_id: AssetId, min_balance: Balance },
+	/// Only members of the collection with the given id can vote.
+	CollectionMember(u32),
+}
+
+/// Enumeration for the poll kind, which is the rule deciding the winner from ballots of voters.
+///
+/// Voters of polls of kinds other than `Plurality` cast a [`Ballot`] of the kind, and its stake
+/// is spread across options in the votes of the voter. The spread stake is returned, rewarded
+/// and pays interest like in `Plurality` polls, but the winner is decided from the ballots.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PollKind {
+	/// The option with the highest stake wins.
+	Plurality,
+	/// Voters rank options, and the stake is on the most preferred one. Options ranked first by
+	/// the lowest stake are eliminated in rounds, and their ballots are passed to the next
+	/// preferences, until an option is ranked first by the majority of the stake.
+	RankedChoice,
+	/// Voters approve options, and the stake is spread evenly across them. The option approved
+	/// by the highest stake wins.
+	Approval,
+	/// Voters score options from zero up to `max_score`, and the stake is spread across them pro
+	/// rata to the scores. The option with the highest sum of scores weighted by stakes wins.
+	Score { max_score: u8 },
+}
+
+/// Enumeration for the goal semantics of the poll, deciding whether it ends as `Finished` or as
+/// `Failed`.
+///
+/// In every mode the capital of all options must reach the goal of the poll. Options of a tie
+/// which don't satisfy the mode are dropped from it, and the poll fails if none of them does.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(Balance: MaxEncodedLen, MaxPollOptions: Get<u32>))]
+#[scale_info(skip_type_params(MaxPollOptions))]
+#[cfg_attr(
+	feature = "std",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "Balance: Serialize",
+		deserialize = "Balance: Deserialize<'de>, MaxPollOptions: Get<u32>"
+	))
+)]
+pub enum GoalMode<Balance: Clone + Eq + core::fmt::Debug, MaxPollOptions: Get<u32>> {
+	/// Only the capital of all options must reach the goal.
+	TotalCapital,
+	/// The stake on the winning option must reach the goal as well.
+	WinningCapital,
+	/// Every option has a goal of its own, which the stake on the winning option must reach.
+	PerOption(BoundedVec<Balance, MaxPollOptions>),
+	/// The stake on the winning option must be at least the given part of the capital of all
+	/// options, in basis points (e.g. 5000 = 50%; 6600 = 66%).
+	Threshold(u32),
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy + core::fmt::Debug, MaxPollOptions: Get<u32>>
+	GoalMode<Balance, MaxPollOptions>
+{
+	/// Checks that the mode is valid for a poll with the given number of options.
+	pub fn validate(&self, options_count: u8) -> bool {
+		match self {
+			Self::TotalCapital | Self::WinningCapital => true,
+			Self::PerOption(goals) =>
+				goals.len() == usize::from(options_count) && goals.iter().all(|g| !g.is_zero()),
+			Self::Threshold(threshold) => *threshold > 0 && *threshold <= 10_000,
+		}
+	}
+
+	/// Returns true if the winning option satisfies the mode, given the goal and the votes of the
+	/// poll. The capital of all options is checked against the goal separately.
+	pub fn is_reached_by(
+		&self,
+		option: u8,
+		goal: Balance,
+		votes: &Votes<Balance, MaxPollOptions>,
+	) -> bool {
+		let stake = votes.capital_of(&[option]);
+		match self {
+			Self::TotalCapital => true,
+			Self::WinningCapital => stake >= goal,
+			Self::PerOption(goals) =>
+				goals.get(usize::from(option)).map_or(false, |goal| stake >= *goal),
+			// Share of the stake is rounded down, so the threshold is never passed by rounding.
+			Self::Threshold(threshold) =>
+				pro_rata(stake, 10_000u32.into(), votes.capital()) >= (*threshold).into(),
+		}
+	}
 }
 
 /// Details of a poll.
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct PollDetails<Balance, AccountId, AssetId, BlockNumber> {
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(
+	Balance: MaxEncodedLen,
+	AccountId: MaxEncodedLen,
+	AssetId: MaxEncodedLen,
+	BlockNumber: MaxEncodedLen,
+	MaxIpfsCidLength: Get<u32>,
+	MaxPollBeneficiaries: Get<u32>,
+	MaxPollOptions: Get<u32>,
+))]
+#[scale_info(skip_type_params(MaxIpfsCidLength, MaxPollBeneficiaries, MaxPollOptions))]
+#[cfg_attr(
+	feature = "std",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "Balance: Serialize, AccountId: Serialize, AssetId: Serialize, \
+			BlockNumber: Serialize",
+		deserialize = "Balance: Deserialize<'de>, AccountId: Deserialize<'de>, \
+			AssetId: Deserialize<'de>, BlockNumber: Deserialize<'de>, \
+			MaxIpfsCidLength: Get<u32>, MaxPollBeneficiaries: Get<u32>, MaxPollOptions: Get<u32>"
+	))
+)]
+pub struct PollDetails<
+	Balance: Clone + Eq + core::fmt::Debug,
+	AccountId: Clone + Eq + core::fmt::Debug,
+	AssetId: Clone + Eq + core::fmt::Debug,
+	BlockNumber: Clone + Eq + core::fmt::Debug,
+	MaxIpfsCidLength: Get<u32>,
+	MaxPollBeneficiaries: Get<u32>,
+	MaxPollOptions: Get<u32>,
+> {
 	/// Account who created this poll.
 	pub created_by: AccountId,
+	/// The amount currently reserved from the creator for this poll.
+	pub deposit: Balance,
 	/// IPFS CID with all contextual information regarding this poll.
-	pub ipfs_cid: IpfsCid,
+	pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
 	/// Beneficiaries of this poll, who will get winning deposit.
 	///
-	/// Vector of [Account, Interest, IsCollected], where sum of all percentages never
+	/// Vector of [Account, Interest, Vesting, Collected], where sum of all percentages never
 	/// more than 100%, or 10_000u32 (e.g. 5 = 0.05%; 1000 = 10%).
 	///
 	/// If empty, all stakes can be returned to the voters after the end of the poll.
-	pub beneficiaries: Vec<Beneficiary<AccountId>>,
+	pub beneficiaries:
+		BoundedVec<Beneficiary<AccountId, Balance, BlockNumber>, MaxPollBeneficiaries>,
 	/// Reward settings of the poll.
-	pub reward_settings: RewardSettings,
-	/// The goal or minimum target amount on one option for the poll to happen.
+	pub reward_settings: RewardSettings<Balance>,
+	/// Is unspent reward pool collected back by the creator.
+	pub reward_pool_collected: bool,
+	/// The goal, or the minimum capital of all options for the poll to happen.
 	pub goal: Balance,
+	/// What else the poll should reach to happen, besides its goal.
+	pub goal_mode: GoalMode<Balance, MaxPollOptions>,
 	/// The number of poll options.
 	pub options_count: u8,
 	/// Make it possible to vote for multiple options.
 	pub multiple_votes: bool,
+	/// Make it possible for voters to move their votes between options.
+	pub movable_votes: bool,
+	/// How the poll is settled if several options have the same highest stake.
+	pub tie_policy: TiePolicy,
+	/// How voters' stakes are held while the poll is not settled.
+	pub voting_mode: VotingMode,
+	/// Settings of the sealed poll, or `None` if votes are public.
+	pub sealed: Option<SealedSettings<BlockNumber>>,
+	/// The rule an account should satisfy to vote in the poll.
+	pub eligibility: Eligibility<AssetId, Balance>,
+	/// The rule deciding the winner of the poll.
+	pub kind: PollKind,
 	/// Info regrading stake on poll options.
-	pub votes: Votes<Balance>,
+	pub votes: Votes<Balance, MaxPollOptions>,
 	/// Currency of the poll.
 	pub currency: PollCurrency<AssetId>,
 	/// Status of the poll.
 	pub status: PollStatus<BlockNumber>,
 }
 
-impl<Balance: AtLeast32BitUnsigned + Copy, AccountId: Clone + Eq, AssetId, BlockNumber>
-	PollDetails<Balance, AccountId, AssetId, BlockNumber>
+impl<
+		Balance: AtLeast32BitUnsigned + Copy + core::fmt::Debug,
+		AccountId: Clone + Eq + core::fmt::Debug,
+		AssetId: Clone + Eq + core::fmt::Debug,
+		BlockNumber: AtLeast32BitUnsigned + Copy + core::fmt::Debug,
+		MaxIpfsCidLength: Get<u32>,
+		MaxPollBeneficiaries: Get<u32>,
+		MaxPollOptions: Get<u32>,
+	>
+	PollDetails<
+		Balance,
+		AccountId,
+		AssetId,
+		BlockNumber,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions,
+	>
 {
 	/// Creates a new PollDetails with Ongoing status and empty Tally.
 	pub fn new(
 		created_by: AccountId,
-		ipfs_cid: IpfsCid,
-		beneficiaries: Vec<Beneficiary<AccountId>>,
-		reward_settings: RewardSettings,
+		ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+		beneficiaries: BoundedVec<
+			Beneficiary<AccountId, Balance, BlockNumber>,
+			MaxPollBeneficiaries,
+		>,
+		reward_settings: RewardSettings<Balance>,
 		goal: Balance,
+		goal_mode: GoalMode<Balance, MaxPollOptions>,
 		options_count: u8,
 		multiple_votes: bool,
+		movable_votes: bool,
+		tie_policy: TiePolicy,
+		voting_mode: VotingMode,
+		sealed: Option<SealedSettings<BlockNumber>>,
+		eligibility: Eligibility<AssetId, Balance>,
+		kind: PollKind,
 		currency: PollCurrency<AssetId>,
 		start: BlockNumber,
 		end: BlockNumber,
 	) -> Self {
 		Self {
 			created_by,
+			deposit: Zero::zero(),
 			ipfs_cid,
 			beneficiaries,
 			reward_settings,
+			reward_pool_collected: false,
 			goal,
+			goal_mode,
 			options_count,
 			multiple_votes,
+			movable_votes,
+			tie_policy,
+			voting_mode,
+			sealed,
+			eligibility,
+			kind,
 			votes: Votes::new(options_count),
 			currency,
 			status: PollStatus::Ongoing { start, end },
@@ -84,16 +353,13 @@ impl<Balance: AtLeast32BitUnsigned + Copy, AccountId: Clone + Eq, AssetId, Block
 	}
 
 	/// Returns true if struct valid, false otherwise.
+	///
+	/// Limits configured in the runtime are checked separately, on poll creation.
 	pub fn validate(&self) -> bool {
-		// IPFS CID v0 is 46 bytes; IPFS CID v1 is 59 bytes.
-		let len = self.ipfs_cid.len();
-		if len != 46 && len != 59 {
+		if self.votes.0.len() != usize::from(self.options_count) {
 			return false
 		}
-		if self.options_count > 10 {
-			return false
-		}
-		if self.options_count < 2 {
+		if !self.goal_mode.validate(self.options_count) {
 			return false
 		}
 		if self.beneficiaries.len() > 0 {
@@ -105,6 +371,36 @@ impl<Balance: AtLeast32BitUnsigned + Copy, AccountId: Clone + Eq, AssetId, Block
 				return false
 			}
 		}
+		// In lock voting mode the interest is paid by voters on their collect, so it can't vest.
+		for vesting in self.beneficiaries.iter().filter_map(|b| b.vesting.as_ref()) {
+			if !vesting.validate() || self.voting_mode == VotingMode::Lock {
+				return false
+			}
+		}
+		if let RewardSettings::CreatorPool(pool) = self.reward_settings {
+			if pool.is_zero() {
+				return false
+			}
+		}
+		if self.voting_mode == VotingMode::Lock &&
+			self.reward_settings == RewardSettings::LosingStakes
+		{
+			return false
+		}
+		if let Some(sealed) = &self.sealed {
+			if sealed.unrevealed_penalty > 10_000u32 {
+				return false
+			}
+		}
+		// Ballots are public, and ties of other kinds aren't decided by stakes on options.
+		if self.kind != PollKind::Plurality &&
+			(self.sealed.is_some() || self.tie_policy == TiePolicy::SplitInterest)
+		{
+			return false
+		}
+		if self.kind == (PollKind::Score { max_score: 0 }) {
+			return false
+		}
 		if !self.status.is_ongoing() {
 			return false
 		}
@@ -112,7 +408,10 @@ impl<Balance: AtLeast32BitUnsigned + Copy, AccountId: Clone + Eq, AssetId, Block
 	}
 
 	/// Finds and returns beneficiary by account id.
-	pub fn get_beneficiary(&self, account: &AccountId) -> Option<Beneficiary<AccountId>> {
+	pub fn get_beneficiary(
+		&self,
+		account: &AccountId,
+	) -> Option<Beneficiary<AccountId, Balance, BlockNumber>> {
 		self.beneficiaries.iter().find(|&x| x.who.eq(account)).cloned()
 	}
 
@@ -120,7 +419,7 @@ impl<Balance: AtLeast32BitUnsigned + Copy, AccountId: Clone + Eq, AssetId, Block
 	pub fn get_mut_beneficiary(
 		&mut self,
 		account: &AccountId,
-	) -> Option<&mut Beneficiary<AccountId>> {
+	) -> Option<&mut Beneficiary<AccountId, Balance, BlockNumber>> {
 		self.beneficiaries.iter_mut().find(|x| x.who.eq(account))
 	}
 
@@ -128,33 +427,99 @@ impl<Balance: AtLeast32BitUnsigned + Copy, AccountId: Clone + Eq, AssetId, Block
 		self.beneficiaries.iter().fold(0u32, |a, b| a.saturating_add(b.interest))
 	}
 
-	pub fn winning_option(&self) -> Option<u8> {
+	/// Returns options which won the poll, or empty vector if stakes should be returned.
+	///
+	/// Several options can win only if the poll is tied and its tie policy is `SplitInterest`.
+	/// Tied options which don't satisfy the goal mode of the poll aren't winning ones.
+	pub fn winning_options(&self) -> Vec<u8> {
 		match self.status {
-			PollStatus::Finished { winning_option, .. } => Some(winning_option),
-			_ => None,
+			PollStatus::Finished { winning_option, .. } => vec![winning_option],
+			PollStatus::Tied(_) if self.tie_policy == TiePolicy::SplitInterest => self
+				.votes
+				.winning_options()
+				.into_iter()
+				.filter(|o| self.goal_mode.is_reached_by(*o, self.goal, &self.votes))
+				.collect(),
+			_ => vec![],
 		}
 	}
 }
 
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct Beneficiary<AccountId> {
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Beneficiary<AccountId, Balance, BlockNumber> {
 	/// AccountId of the beneficiary.
 	pub who: AccountId,
 	/// Beneficiary interest, can't be more than 10_000u32.
 	/// Can be converted to percentage (e.g. 5 = 0.05%; 1000 = 10%).
 	pub interest: u32,
-	/// Is beneficiary collected winning option from the poll.
-	pub collected: bool,
+	/// Vesting schedule of the interest, or `None` if it's paid at once.
+	pub vesting: Option<VestingSchedule<BlockNumber>>,
+	/// The part of the interest already collected by the beneficiary.
+	pub collected: Balance,
+}
+
+impl<AccountId, Balance: Zero, BlockNumber> Beneficiary<AccountId, Balance, BlockNumber> {
+	pub fn new(
+		who: AccountId,
+		interest: u32,
+		vesting: Option<VestingSchedule<BlockNumber>>,
+	) -> Self {
+		Self { who, interest, vesting, collected: Zero::zero() }
+	}
+}
+
+/// Linear vesting of the interest of a beneficiary, which starts after the end of the poll.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VestingSchedule<BlockNumber> {
+	/// The number of blocks after the end of the poll, at which vesting starts.
+	pub start_offset: BlockNumber,
+	/// The number of blocks in which the whole interest vests.
+	pub duration: BlockNumber,
+	/// The number of blocks after the start of vesting, before which nothing can be collected.
+	pub cliff: BlockNumber,
 }
 
-impl<AccountId> Beneficiary<AccountId> {
-	pub fn new(who: AccountId, interest: u32) -> Self {
-		Self { who, interest, collected: false }
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> VestingSchedule<BlockNumber> {
+	/// Ensures vesting takes some time, and the cliff is within it.
+	pub fn validate(&self) -> bool {
+		!self.duration.is_zero() && self.cliff <= self.duration
+	}
+
+	/// Returns the block at which the whole amount is vested, for the poll which ended at `end`.
+	pub fn vested_at(&self, end: BlockNumber) -> BlockNumber {
+		end.saturating_add(self.start_offset).saturating_add(self.duration)
+	}
+
+	/// Returns the part of the amount vested at `now`, for the poll which ended at `end`.
+	pub fn vested<Balance: AtLeast32BitUnsigned + Copy>(
+		&self,
+		amount: Balance,
+		end: BlockNumber,
+		now: BlockNumber,
+	) -> Balance {
+		let start = end.saturating_add(self.start_offset);
+		if now < start.saturating_add(self.cliff) {
+			return Zero::zero()
+		}
+		let elapsed = now.saturating_sub(start);
+		if elapsed >= self.duration {
+			return amount
+		}
+		let elapsed: u128 = elapsed.unique_saturated_into();
+		let duration: u128 = self.duration.unique_saturated_into();
+		pro_rata(
+			amount,
+			Balance::unique_saturated_from(elapsed),
+			Balance::unique_saturated_from(duration),
+		)
 	}
 }
 
 /// Status of a poll, present, cancelled, or past.
-#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum PollStatus<BlockNumber> {
 	/// Poll is happening, the args are the block number at which it will start and end.
 	Ongoing {
@@ -174,6 +539,8 @@ pub enum PollStatus<BlockNumber> {
 	},
 	/// Poll finished but the goal hasn't been reached.
 	Failed(BlockNumber),
+	/// Poll finished at a given block, but several options have the same highest stake.
+	Tied(BlockNumber),
 }
 
 impl<BlockNumber> PollStatus<BlockNumber> {
@@ -185,29 +552,299 @@ impl<BlockNumber> PollStatus<BlockNumber> {
 	}
 }
 
+impl<BlockNumber: Copy> PollStatus<BlockNumber> {
+	/// Returns the block at which voting in the poll ended, if it's settled and not cancelled.
+	pub fn end(&self) -> Option<BlockNumber> {
+		match self {
+			PollStatus::Finished { end, .. } | PollStatus::Failed(end) | PollStatus::Tied(end) =>
+				Some(*end),
+			_ => None,
+		}
+	}
+}
+
+/// Sign-off of a milestone by voters of the winning options, weighted by their stake.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct SignOff<Balance, BlockNumber> {
+	/// The block at which the sign-off ends.
+	pub end: BlockNumber,
+	/// Stake of voters who approved the milestone.
+	pub approvals: Balance,
+	/// Stake of voters who rejected the milestone.
+	pub rejections: Balance,
+}
+
+/// Escrow of the beneficiaries' interest, which is released in milestones approved by voters.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(Balance: MaxEncodedLen, BlockNumber: MaxEncodedLen, MaxMilestones: Get<u32>))]
+#[scale_info(skip_type_params(MaxMilestones))]
+pub struct Escrow<
+	Balance: Clone + Eq + core::fmt::Debug,
+	BlockNumber: Clone + Eq + core::fmt::Debug,
+	MaxMilestones: Get<u32>,
+> {
+	/// Shares of the interest released by milestones, in basis points summing to 10_000.
+	pub milestones: BoundedVec<u32, MaxMilestones>,
+	/// The number of milestones approved so far.
+	pub released: u8,
+	/// Sign-off of the next milestone, if it's in progress.
+	pub sign_off: Option<SignOff<Balance, BlockNumber>>,
+	/// Is a milestone rejected, so the rest of the escrow is returned to voters.
+	pub rejected: bool,
+}
+
+impl<
+		Balance: AtLeast32BitUnsigned + Copy + core::fmt::Debug,
+		BlockNumber: Clone + Eq + core::fmt::Debug,
+		MaxMilestones: Get<u32>,
+	> Escrow<Balance, BlockNumber, MaxMilestones>
+{
+	pub fn new(milestones: BoundedVec<u32, MaxMilestones>) -> Self {
+		Self { milestones, released: 0, sign_off: None, rejected: false }
+	}
+
+	/// Ensures every milestone releases a part of the interest, and all of them release it
+	/// entirely.
+	pub fn validate(&self) -> bool {
+		!self.milestones.is_empty() &&
+			self.milestones.len() <= u8::MAX as usize &&
+			self.milestones.iter().all(|share| *share > 0) &&
+			self.milestones.iter().map(|share| *share as u64).sum::<u64>() == 10_000
+	}
+
+	/// Returns the share of the interest released by the first `count` milestones.
+	pub fn share_of(&self, count: u8) -> u32 {
+		self.milestones.iter().take(count as usize).sum()
+	}
+
+	/// Returns the part of the amount released by the first `count` milestones.
+	pub fn released_of(&self, amount: Balance, count: u8) -> Balance {
+		pro_rata(amount, self.share_of(count).into(), 10_000u32.into())
+	}
+
+	/// Returns whether all milestones are released, or the escrow is rejected.
+	pub fn is_settled(&self) -> bool {
+		self.rejected || self.released as usize >= self.milestones.len()
+	}
+}
+
+/// Compact record of a reaped poll, kept after its details and votes were removed.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(
+	Balance: MaxEncodedLen,
+	AccountId: MaxEncodedLen,
+	AssetId: MaxEncodedLen,
+	BlockNumber: MaxEncodedLen,
+	MaxIpfsCidLength: Get<u32>,
+))]
+#[scale_info(skip_type_params(MaxIpfsCidLength))]
+pub struct PollSummary<
+	Balance: Clone + Eq + core::fmt::Debug,
+	AccountId: Clone + Eq + core::fmt::Debug,
+	AssetId: Clone + Eq + core::fmt::Debug,
+	BlockNumber: Clone + Eq + core::fmt::Debug,
+	MaxIpfsCidLength: Get<u32>,
+> {
+	/// Account who created the poll.
+	pub created_by: AccountId,
+	/// IPFS CID with all contextual information regarding the poll.
+	pub ipfs_cid: BoundedVec<u8, MaxIpfsCidLength>,
+	/// Currency of the poll.
+	pub currency: PollCurrency<AssetId>,
+	/// Outcome of the poll.
+	pub status: PollStatus<BlockNumber>,
+	/// Total stake on all poll options.
+	pub capital: Balance,
+	/// Stake on the winning options.
+	pub winning_capital: Balance,
+	/// Funds which were left in the pot when the poll was reaped.
+	pub dust: Balance,
+}
+
+/// Storage of a poll which is being removed when it's reaped, in the order it's removed.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ReapPhase {
+	/// Votes are paid out and removed, together with ballots, escrow participations and
+	/// delegated stakes of voters.
+	Votes,
+	/// Unrevealed commitments are refunded and removed.
+	Commitments,
+	/// Accounts are removed from the allowlist.
+	Allowlist,
+	/// Beneficiaries and the creator are paid out, funds left in the pot are taken, and
+	/// details and tallies of the poll are replaced by its summary.
+	Archive,
+}
+
+/// Participation of a voter in the escrow of a poll.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct EscrowParticipation {
+	/// The number of milestones signed off by the voter.
+	pub signed_off: u8,
+	/// Is the voter refunded his share of the rejected escrow.
+	pub refunded: bool,
+}
+
+/// Breakdown of what an account can collect from a finished, failed or cancelled poll.
+#[derive(
+	CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxPollOptions))]
+#[cfg_attr(
+	feature = "std",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "Balance: Serialize",
+		deserialize = "Balance: Deserialize<'de>, MaxPollOptions: Get<u32>"
+	))
+)]
+pub struct Claimable<Balance: Clone + Eq + core::fmt::Debug, MaxPollOptions: Get<u32>> {
+	/// Interest that can be collected as a beneficiary of the poll.
+	///
+	/// Always zero in `Lock` voting mode, where voters pay the interest on their collect.
+	pub beneficiary_interest: Balance,
+	/// Interest already collected by the beneficiary.
+	pub beneficiary_collected: Balance,
+	/// Stake that can be returned to the voter, per poll option.
+	pub voter_return: Votes<Balance, MaxPollOptions>,
+	/// Reward that can be collected by the voter, according to the poll reward settings.
+	pub voter_reward: Balance,
+	/// Is voter already collected his stake.
+	pub voter_collected: bool,
+	/// Stake of the voter, locked in his account until collect, in `Lock` voting mode.
+	pub voter_locked: Balance,
+	/// Unspent reward pool that can be returned to the creator of the poll.
+	pub creator_refund: Balance,
+	/// Share of the escrowed interest returned to the voter after a milestone was rejected.
+	pub escrow_refund: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy + core::fmt::Debug, MaxPollOptions: Get<u32>>
+	Claimable<Balance, MaxPollOptions>
+{
+	/// Returns the total amount that can be collected.
+	pub fn total(&self) -> Balance {
+		self.beneficiary_interest
+			.saturating_add(self.voter_return.capital())
+			.saturating_add(self.voter_reward)
+			.saturating_add(self.creator_refund)
+			.saturating_add(self.escrow_refund)
+	}
+}
+
+/// Returns `amount * part / total` rounded down, computed without intermediate overflow.
+///
+/// Returns zero if `total` is zero.
+pub fn pro_rata<Balance: AtLeast32BitUnsigned + Copy>(
+	amount: Balance,
+	part: Balance,
+	total: Balance,
+) -> Balance {
+	if total.is_zero() {
+		return Zero::zero()
+	}
+	let amount: u128 = amount.unique_saturated_into();
+	let part: u128 = part.unique_saturated_into();
+	let total: u128 = total.unique_saturated_into();
+	let result = U256::from(amount) * U256::from(part) / U256::from(total);
+	let result = if result > U256::from(u128::MAX) { u128::MAX } else { result.low_u128() };
+	result.unique_saturated_into()
+}
+
 /// A vote for a poll of a particular account.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct AccountVotes<Balance> {
-	pub votes: Votes<Balance>,
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(Balance: MaxEncodedLen, MaxPollOptions: Get<u32>))]
+#[scale_info(skip_type_params(MaxPollOptions))]
+#[cfg_attr(
+	feature = "std",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "Balance: Serialize",
+		deserialize = "Balance: Deserialize<'de>, MaxPollOptions: Get<u32>"
+	))
+)]
+pub struct AccountVotes<Balance: Clone + Eq + core::fmt::Debug, MaxPollOptions: Get<u32>> {
+	pub votes: Votes<Balance, MaxPollOptions>,
 	pub collected: bool,
 }
 
 /// A vote for a poll.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct Votes<Balance>(pub Vec<Balance>);
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(Balance: MaxEncodedLen, MaxPollOptions: Get<u32>))]
+#[scale_info(skip_type_params(MaxPollOptions))]
+#[cfg_attr(
+	feature = "std",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "Balance: Serialize",
+		deserialize = "Balance: Deserialize<'de>, MaxPollOptions: Get<u32>"
+	))
+)]
+pub struct Votes<Balance: Clone + Eq + core::fmt::Debug, MaxPollOptions: Get<u32>>(
+	pub BoundedVec<Balance, MaxPollOptions>,
+);
 
-impl<Balance: AtLeast32BitUnsigned + Copy> Votes<Balance> {
+impl<Balance: AtLeast32BitUnsigned + Copy + core::fmt::Debug, MaxPollOptions: Get<u32>>
+	Votes<Balance, MaxPollOptions>
+{
+	/// Creates zero votes for the given number of options.
+	///
+	/// Returns no options if `options_count` exceeds the bound, which fails poll validation.
 	pub fn new(options_count: u8) -> Self {
-		Self((0..options_count).map(|_| Balance::zero()).collect())
+		let votes: Vec<Balance> = (0..options_count).map(|_| Balance::zero()).collect();
+		Self(votes.try_into().unwrap_or_default())
 	}
 
-	/// Returns winning option. Returns last max element.
-	///
-	/// TODO: Handle 2 winning options better.
-	pub fn winning_option(&self) -> Option<u8> {
-		let winning_option =
-			self.0.iter().enumerate().max_by_key(|(_idx, &val)| val).map(|(idx, _val)| idx);
-		winning_option.map(|x| x as u8)
+	/// Returns all options with the highest stake. Returns more than one option on a tie.
+	pub fn winning_options(&self) -> Vec<u8> {
+		let max = match self.0.iter().max() {
+			Some(max) => *max,
+			None => return vec![],
+		};
+		self.0
+			.iter()
+			.enumerate()
+			.filter(|(_, &val)| val == max)
+			.map(|(i, _)| i as u8)
+			.collect()
 	}
 
 	pub fn validate(&self, options_count: u8) -> bool {
@@ -226,25 +863,202 @@ impl<Balance: AtLeast32BitUnsigned + Copy> Votes<Balance> {
 		self.0.iter().fold(Balance::zero(), |a, b| a.saturating_add(*b))
 	}
 
+	/// Returns the sum of stakes on the given options.
+	pub fn capital_of(&self, options: &[u8]) -> Balance {
+		options
+			.iter()
+			.filter_map(|&i| self.0.get(i as usize))
+			.fold(Balance::zero(), |a, b| a.saturating_add(*b))
+	}
+
 	/// Add an account's vote into the tally. Returns None if invalid option or overflow.
-	pub fn add(&mut self, votes: &Votes<Balance>) -> Option<()> {
+	pub fn add(&mut self, votes: &Self) -> Option<()> {
 		if votes.0.len() != self.0.len() {
 			return None
 		}
-		for (i, b) in votes.0.iter().enumerate() {
-			self.0[i] = self.0[i].checked_add(&b)?;
+		for (a, b) in self.0.iter_mut().zip(votes.0.iter()) {
+			*a = a.checked_add(b)?;
 		}
 		Some(())
 	}
 
 	/// Remove an account's vote from the tally. Returns None if invalid option or overflow.
-	pub fn remove(&mut self, votes: &Votes<Balance>) -> Option<()> {
+	pub fn remove(&mut self, votes: &Self) -> Option<()> {
 		if votes.0.len() != self.0.len() {
 			return None
 		}
-		for (i, b) in votes.0.iter().enumerate() {
-			self.0[i] = self.0[i].checked_sub(&b)?;
+		for (a, b) in self.0.iter_mut().zip(votes.0.iter()) {
+			*a = a.checked_sub(b)?;
+		}
+		Some(())
+	}
+
+	/// Move a stake from one option to another. Returns None if invalid options or underflow.
+	pub fn move_stake(&mut self, from: u8, to: u8, amount: Balance) -> Option<()> {
+		if from == to {
+			return None
 		}
+		let from_stake = self.0.get(from as usize)?.checked_sub(&amount)?;
+		let to_stake = self.0.get(to as usize)?.checked_add(&amount)?;
+		*self.0.get_mut(from as usize)? = from_stake;
+		*self.0.get_mut(to as usize)? = to_stake;
 		Some(())
 	}
 }
+
+/// A ballot for a poll of a kind other than `Plurality`.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[codec(mel_bound(Balance: MaxEncodedLen, MaxPollOptions: Get<u32>))]
+#[scale_info(skip_type_params(MaxPollOptions))]
+pub enum Ballot<Balance: Clone + Eq + core::fmt::Debug, MaxPollOptions: Get<u32>> {
+	/// Options of a `RankedChoice` poll, from the most preferred one. Options can be left out.
+	Ranked { ranking: BoundedVec<u8, MaxPollOptions>, stake: Balance },
+	/// Options approved in an `Approval` poll.
+	Approval { approved: BoundedVec<u8, MaxPollOptions>, stake: Balance },
+	/// Score of every option of a `Score` poll.
+	Score { scores: BoundedVec<u8, MaxPollOptions>, stake: Balance },
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy + core::fmt::Debug, MaxPollOptions: Get<u32>>
+	Ballot<Balance, MaxPollOptions>
+{
+	pub fn stake(&self) -> Balance {
+		match self {
+			Self::Ranked { stake, .. } |
+			Self::Approval { stake, .. } |
+			Self::Score { stake, .. } => *stake,
+		}
+	}
+
+	/// Checks that the ballot is of the given poll kind, and that its options are valid.
+	pub fn validate(&self, kind: PollKind, options_count: u8) -> bool {
+		let unique = |options: &[u8]| {
+			!options.is_empty() &&
+				options.iter().all(|&o| o < options_count) &&
+				options.iter().enumerate().all(|(i, o)| !options[..i].contains(o))
+		};
+		match (self, kind) {
+			(Self::Ranked { ranking, .. }, PollKind::RankedChoice) => unique(ranking),
+			(Self::Approval { approved, .. }, PollKind::Approval) => unique(approved),
+			(Self::Score { scores, .. }, PollKind::Score { max_score }) =>
+				scores.len() == options_count as usize &&
+					scores.iter().all(|&s| s <= max_score) &&
+					scores.iter().any(|&s| s > 0),
+			_ => false,
+		}
+	}
+
+	/// Spreads the stake across options, as the votes of the voter.
+	///
+	/// The stake of a ranked ballot is on the most preferred option. The stake of an approval
+	/// ballot is spread evenly, and the remainder is on the first approved options. The stake of
+	/// a score ballot is spread pro rata to the scores, and the remainder is on the first option
+	/// with the highest score. Should be called only on a valid ballot.
+	pub fn to_votes(&self, options_count: u8) -> Votes<Balance, MaxPollOptions> {
+		let mut votes = Votes::<Balance, MaxPollOptions>::new(options_count);
+		match self {
+			Self::Ranked { ranking, stake } =>
+				if let Some(v) = ranking.first().and_then(|&o| votes.0.get_mut(o as usize)) {
+					*v = *stake;
+				},
+			Self::Approval { approved, stake } => {
+				let count = Balance::from(approved.len() as u32);
+				if count.is_zero() {
+					return votes
+				}
+				let share = *stake / count;
+				let remainder: u32 = (*stake % count).unique_saturated_into();
+				for (i, &o) in approved.iter().enumerate() {
+					if let Some(v) = votes.0.get_mut(o as usize) {
+						*v = if (i as u32) < remainder {
+							share + Balance::from(1u32)
+						} else {
+							share
+						};
+					}
+				}
+			},
+			Self::Score { scores, stake } => {
+				let total = Balance::from(scores.iter().map(|&s| s as u32).sum::<u32>());
+				let mut spread = Balance::zero();
+				for (v, &s) in votes.0.iter_mut().zip(scores.iter()) {
+					*v = pro_rata(*stake, Balance::from(s), total);
+					spread = spread.saturating_add(*v);
+				}
+				let max = scores.iter().max().copied().unwrap_or_default();
+				let top = scores.iter().position(|&s| s == max);
+				if let Some(v) = top.and_then(|i| votes.0.get_mut(i)) {
+					*v = v.saturating_add(stake.saturating_sub(spread));
+				}
+			},
+		}
+		votes
+	}
+
+	/// Returns the weight of the ballot on every option, which is added to the tally of an
+	/// `Approval` or a `Score` poll: the stake on each approved option, or the stake multiplied
+	/// by the score of each option. Ranked ballots have no such weights.
+	pub fn weights(&self, options_count: u8) -> Option<Votes<Balance, MaxPollOptions>> {
+		let mut votes = Votes::<Balance, MaxPollOptions>::new(options_count);
+		match self {
+			Self::Ranked { .. } => return None,
+			Self::Approval { approved, stake } =>
+				for &o in approved.iter() {
+					*votes.0.get_mut(o as usize)? = *stake;
+				},
+			Self::Score { scores, stake } =>
+				for (v, &s) in votes.0.iter_mut().zip(scores.iter()) {
+					*v = stake.saturating_mul(Balance::from(s));
+				},
+		}
+		Some(votes)
+	}
+}
+
+/// Returns the winners of a `RankedChoice` poll by instant-runoff, from rankings and stakes of
+/// all ballots.
+///
+/// Every ballot counts for its most preferred option which is not eliminated yet. Options with
+/// the lowest count are eliminated in rounds, until an option is counted by the majority of
+/// the stake, or all remaining options have the same count. Returns more than one option on a
+/// tie, and all options if nobody voted.
+pub fn instant_runoff<Balance: AtLeast32BitUnsigned + Copy>(
+	rankings: &[(&[u8], Balance)],
+	options_count: u8,
+) -> Vec<u8> {
+	let mut eliminated = vec![false; options_count as usize];
+	loop {
+		let mut counts = vec![Balance::zero(); options_count as usize];
+		let mut total = Balance::zero();
+		for (ranking, stake) in rankings {
+			let preferred = ranking.iter().find(|&&o| !eliminated.get(o as usize).unwrap_or(&true));
+			if let Some(&o) = preferred {
+				counts[o as usize] = counts[o as usize].saturating_add(*stake);
+				total = total.saturating_add(*stake);
+			}
+		}
+		let remaining: Vec<u8> = (0..options_count).filter(|&o| !eliminated[o as usize]).collect();
+		let (min, max) = match (
+			remaining.iter().map(|&o| counts[o as usize]).min(),
+			remaining.iter().map(|&o| counts[o as usize]).max(),
+		) {
+			(Some(min), Some(max)) => (min, max),
+			_ => return vec![],
+		};
+		if max > total.saturating_sub(max) || min == max {
+			return remaining.into_iter().filter(|&o| counts[o as usize] == max).collect()
+		}
+		for o in remaining.into_iter().filter(|&o| counts[o as usize] == min) {
+			eliminated[o as usize] = true;
+		}
+	}
+}
@@ -65,6 +65,12 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// Identifier of an asset in the assets pallet.
+pub type AssetId = u32;
+
+/// Index of a poll in the faterium polls pallet.
+pub type PollIndex = u64;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -104,7 +110,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 110,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -279,7 +285,7 @@ parameter_types! {
 impl pallet_assets::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
-	type AssetId = u32;
+	type AssetId = AssetId;
 	type Currency = Balances;
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type AssetDeposit = AssetDeposit;
@@ -315,6 +321,41 @@ impl pallet_scheduler::Config for Runtime {
 
 parameter_types! {
 	pub const FateriumPollsPalletId: PalletId = PalletId(*b"py/ftmpl");
+	pub const MaxIpfsCidLength: u32 = 64;
+	pub const MaxPollBeneficiaries: u32 = 10;
+	pub const MaxPollOptions: u32 = 10;
+	pub const MaxRankings: u32 = 256;
+	pub const MinRankingStake: Balance = 10_000_000_000_000;
+	pub const MaxDelegators: u32 = 32;
+	pub const MaxMilestones: u32 = 10;
+	pub const SignOffPeriod: BlockNumber = 7 * DAYS;
+	pub const MilestonePeriod: BlockNumber = 90 * DAYS;
+	pub const MaxSettlementQueue: u32 = 100;
+	pub const ReapDelay: BlockNumber = 180 * DAYS;
+	pub const MinPollDuration: BlockNumber = 10 * MINUTES;
+	pub const MaxPollDuration: BlockNumber = 365 * DAYS;
+	pub const MinPollGoal: Balance = 1;
+	pub const PollDepositBase: Balance = 1_000_000_000_000;
+	pub const PollDepositPerByte: Balance = 10_000_000_000;
+	pub const PollDepositPerBeneficiary: Balance = 100_000_000_000;
+}
+
+/// Content types allowed in IPFS CIDs of polls: raw, dag-pb, dag-cbor and dag-json.
+pub struct PollCidCodecs;
+impl frame_support::traits::Contains<u64> for PollCidCodecs {
+	fn contains(codec: &u64) -> bool {
+		use pallet_faterium_polls::cid::codec::*;
+		matches!(*codec, RAW | DAG_PB | DAG_CBOR | DAG_JSON)
+	}
+}
+
+/// Hash functions allowed in IPFS CIDs of polls: sha2-256, sha2-512, blake2b-256 and blake3.
+pub struct PollCidHashes;
+impl frame_support::traits::Contains<u64> for PollCidHashes {
+	fn contains(hash: &u64) -> bool {
+		use pallet_faterium_polls::cid::hash::*;
+		matches!(*hash, SHA2_256 | SHA2_512 | BLAKE2B_256 | BLAKE3)
+	}
 }
 
 /// Configure the pallet-faterium-polls in pallets/faterium-polls.
@@ -323,11 +364,39 @@ impl pallet_faterium_polls::Config for Runtime {
 	type Event = Event;
 	type Fungibles = Assets;
 	type Currency = Balances;
-	type PollIndex = u64;
+	type PollIndex = PollIndex;
 	type Scheduler = Scheduler;
+	// Block authors can predict and influence this source, so `TiePolicy::Random` is only
+	// suitable for polls where the block author has no stake in the outcome of a tie.
+	type Randomness = RandomnessCollectiveFlip;
 	type PalletsOrigin = OriginCaller;
 	type PalletId = FateriumPollsPalletId;
-	type MaxPollBeneficiaries = ConstU32<10>;
+	type MaxIpfsCidLength = MaxIpfsCidLength;
+	type AllowedCidCodecs = PollCidCodecs;
+	type AllowedCidHashes = PollCidHashes;
+	type MaxPollBeneficiaries = MaxPollBeneficiaries;
+	type MaxPollOptions = MaxPollOptions;
+	type MaxRankings = MaxRankings;
+	type MinRankingStake = MinRankingStake;
+	type MaxDelegators = MaxDelegators;
+	type MaxMilestones = MaxMilestones;
+	type SignOffPeriod = SignOffPeriod;
+	type MilestonePeriod = MilestonePeriod;
+	type MaxSettlementQueue = MaxSettlementQueue;
+	type ReapDelay = ReapDelay;
+	// There are no collections in the runtime yet.
+	type Collections = frame_support::traits::Nothing;
+	type MinPollDuration = MinPollDuration;
+	type MaxPollDuration = MaxPollDuration;
+	type MinPollGoal = MinPollGoal;
+	type PollDepositBase = PollDepositBase;
+	type PollDepositPerByte = PollDepositPerByte;
+	type PollDepositPerBeneficiary = PollDepositPerBeneficiary;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type Slashed = ();
+	type Dust = ();
+	type AssetDust = ();
+	type WeightInfo = weights::pallet_faterium_polls::WeightInfo<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -375,6 +444,20 @@ pub type SignedExtra = (
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
+/// Storage migrations to run on runtime upgrade, each one is skipped once applied.
+pub type Migrations = (
+	pallet_faterium_polls::migrations::v1::MigrateToV1<Runtime>,
+	pallet_faterium_polls::migrations::v2::MigrateToV2<Runtime>,
+	pallet_faterium_polls::migrations::v3::MigrateToV3<Runtime>,
+	pallet_faterium_polls::migrations::v4::MigrateToV4<Runtime>,
+	pallet_faterium_polls::migrations::v5::MigrateToV5<Runtime>,
+	pallet_faterium_polls::migrations::v6::MigrateToV6<Runtime>,
+	pallet_faterium_polls::migrations::v7::MigrateToV7<Runtime>,
+	pallet_faterium_polls::migrations::v8::MigrateToV8<Runtime>,
+	pallet_faterium_polls::migrations::v9::MigrateToV9<Runtime>,
+	pallet_faterium_polls::migrations::v10::MigrateToV10<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -382,6 +465,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -549,6 +633,61 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_faterium_polls_rpc_runtime_api::FateriumPollsApi<
+		Block,
+		AccountId,
+		Balance,
+		AssetId,
+		BlockNumber,
+		PollIndex,
+		MaxIpfsCidLength,
+		MaxPollBeneficiaries,
+		MaxPollOptions,
+	> for Runtime {
+		fn poll_details(
+			poll_id: PollIndex,
+		) -> Option<pallet_faterium_polls::PollDetails<
+			Balance,
+			AccountId,
+			AssetId,
+			BlockNumber,
+			MaxIpfsCidLength,
+			MaxPollBeneficiaries,
+			MaxPollOptions,
+		>> {
+			FateriumPolls::poll_details_of(poll_id)
+		}
+
+		fn account_votes(
+			who: AccountId,
+			poll_id: PollIndex,
+		) -> Option<pallet_faterium_polls::AccountVotes<Balance, MaxPollOptions>> {
+			FateriumPolls::voting_of((who, poll_id))
+		}
+
+		fn balances_pot(poll_id: PollIndex) -> Balance {
+			FateriumPolls::balances_pot(poll_id)
+		}
+
+		fn asset_pot(poll_id: PollIndex, asset_id: AssetId) -> Balance {
+			FateriumPolls::asset_pot(poll_id, asset_id)
+		}
+
+		fn poll_status(poll_id: PollIndex) -> Option<pallet_faterium_polls::PollStatus<BlockNumber>> {
+			FateriumPolls::poll_details_of(poll_id).map(|poll| poll.status)
+		}
+
+		fn claimable(
+			who: AccountId,
+			poll_id: PollIndex,
+		) -> Result<
+			pallet_faterium_polls::Claimable<Balance, MaxPollOptions>,
+			sp_runtime::DispatchError,
+		> {
+			FateriumPolls::claimable(&who, poll_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
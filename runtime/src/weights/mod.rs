@@ -1,3 +1,4 @@
 //! A list of the different weight modules for our runtime.
 
+pub mod pallet_faterium_polls;
 pub mod pallet_scheduler;
@@ -0,0 +1,265 @@
+//! Weights for `pallet_faterium_polls`
+//!
+//! NOTE: these values are estimates derived from the storage accesses of each call. The pallet
+//! hasn't been benchmarked yet, so this file must be regenerated with `./scripts/benchmark.sh`
+//! on the reference hardware before relying on it in production.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_faterium_polls`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_faterium_polls::WeightInfo for WeightInfo<T> {
+	// Storage: FateriumPolls PollCount (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: FateriumPolls PollDetailsOf (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn create_poll(b: u32, o: u32, ) -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(1_000_000 as u64).saturating_mul(b as u64))
+			.saturating_add(Weight::from_ref_time(250_000 as u64).saturating_mul(o as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn update_poll(b: u32, ) -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(1_000_000 as u64).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls SettlementQueue (r:1 w:1)
+	fn emergency_cancel() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls SettlementQueue (r:1 w:1)
+	fn force_cancel() -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:1 w:1)
+	// Storage: FateriumPolls LockedOf (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls DelegationsOf (r:1 w:1)
+	// Storage: FateriumPolls DelegatedStakesOf (r:1 w:1)
+	// Storage: FateriumPolls ParticipantsOf (r:0 w:1)
+	fn vote(o: u32, d: u32, ) -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(300_000 as u64).saturating_mul(o as u64))
+			.saturating_add(Weight::from_ref_time(25_000_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(d as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(d as u64)))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:1 w:1)
+	// Storage: FateriumPolls LockedOf (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls DelegatedStakesOf (r:1 w:1)
+	// Storage: FateriumPolls BallotsOf (r:1 w:1)
+	// Storage: FateriumPolls TallyOf (r:1 w:1)
+	fn remove_vote(o: u32, d: u32, ) -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(300_000 as u64).saturating_mul(o as u64))
+			.saturating_add(Weight::from_ref_time(25_000_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(d as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(d as u64)))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:1 w:1)
+	// Storage: FateriumPolls LockedOf (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls DelegatedStakesOf (r:1 w:1)
+	fn withdraw_votes(o: u32, d: u32, ) -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(300_000 as u64).saturating_mul(o as u64))
+			.saturating_add(Weight::from_ref_time(25_000_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(d as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(d as u64)))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:1 w:1)
+	fn move_votes(o: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(200_000 as u64).saturating_mul(o as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: FateriumPolls DelegationsOf (r:1 w:1)
+	fn delegate(d: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(100_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FateriumPolls DelegationsOf (r:1 w:1)
+	fn undelegate(d: u32, ) -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(100_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls CommitmentsOf (r:1 w:1)
+	// Storage: FateriumPolls LockedOf (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls ParticipantsOf (r:0 w:1)
+	fn commit_votes() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: FateriumPolls CommitmentsOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:0 w:1)
+	fn reveal_votes(o: u32, ) -> Weight {
+		Weight::from_ref_time(35_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(300_000 as u64).saturating_mul(o as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls CommitmentsOf (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn refund_commitment() -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls AllowlistOf (r:0 w:1)
+	// Storage: FateriumPolls ParticipantsOf (r:0 w:1)
+	fn update_allowlist(a: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(3_000_000 as u64).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls AllowlistOf (r:0 w:1)
+	// Storage: FateriumPolls ParticipantsOf (r:0 w:1)
+	fn prove_eligibility(p: u32, ) -> Weight {
+		Weight::from_ref_time(22_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(400_000 as u64).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls EscrowsOf (r:0 w:1)
+	fn set_milestones(m: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(100_000 as u64).saturating_mul(m as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls EscrowsOf (r:1 w:1)
+	fn request_sign_off() -> Weight {
+		Weight::from_ref_time(22_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls EscrowsOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:1 w:0)
+	// Storage: FateriumPolls EscrowParticipationOf (r:1 w:1)
+	fn sign_off() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: FateriumPolls EscrowsOf (r:1 w:1)
+	fn close_sign_off() -> Weight {
+		Weight::from_ref_time(18_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls VotingOf (r:1 w:1)
+	// Storage: FateriumPolls LockedOf (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:2 w:1)
+	// Storage: FateriumPolls DelegatedStakesOf (r:1 w:1)
+	fn collect(b: u32, o: u32, d: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(30_000_000 as u64).saturating_mul(b as u64))
+			.saturating_add(Weight::from_ref_time(500_000 as u64).saturating_mul(o as u64))
+			.saturating_add(Weight::from_ref_time(25_000_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(b as u64)))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(b as u64)))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(d as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(d as u64)))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls SettlementQueue (r:1 w:1)
+	// Storage: FateriumPolls TallyOf (r:1 w:0)
+	// Storage: FateriumPolls RankingsOf (r:1 w:0)
+	fn enact_poll_end(o: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(200_000 as u64).saturating_mul(o as u64))
+			.saturating_add(Weight::from_ref_time(1_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:0)
+	// Storage: FateriumPolls SettlementQueue (r:1 w:0)
+	// Storage: FateriumPolls ReapQueue (r:1 w:1)
+	// Storage: FateriumPolls EscrowsOf (r:1 w:0)
+	fn reap_poll() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FateriumPolls PollDetailsOf (r:1 w:1)
+	// Storage: FateriumPolls BallotsOf (r:1 w:1)
+	// Storage: FateriumPolls TallyOf (r:1 w:1)
+	// Storage: FateriumPolls VotingOf (r:0 w:1)
+	// Storage: FateriumPolls LockedOf (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FateriumPolls DelegationsOf (r:1 w:1)
+	// Storage: FateriumPolls DelegatedStakesOf (r:1 w:1)
+	// Storage: FateriumPolls ParticipantsOf (r:0 w:1)
+	fn cast_ballot(o: u32, d: u32, ) -> Weight {
+		Weight::from_ref_time(55_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(600_000 as u64).saturating_mul(o as u64))
+			.saturating_add(Weight::from_ref_time(25_000_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(d as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(d as u64)))
+	}
+}
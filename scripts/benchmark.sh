@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# This script is meant to be run on the reference hardware, from the root of the repository.
+# It benchmarks pallet_faterium_polls and regenerates its default weights and runtime weights.
+set -e
+
+echo "*** Building the node with runtime benchmarks"
+
+cargo build --release -p faterium-node --features runtime-benchmarks
+
+benchmark() {
+	./target/release/faterium-node benchmark pallet \
+		--chain=dev \
+		--steps=50 \
+		--repeat=20 \
+		--pallet=pallet_faterium_polls \
+		--extrinsic='*' \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--heap-pages=4096 \
+		"$@"
+}
+
+echo "*** Benchmarking pallet_faterium_polls"
+
+benchmark \
+	--output=./pallets/faterium-polls/src/weights.rs \
+	--template=./.maintain/frame-weight-template.hbs
+benchmark --output=./runtime/src/weights/pallet_faterium_polls.rs